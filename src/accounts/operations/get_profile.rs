@@ -58,7 +58,7 @@ impl<'a> GetProfile for Accounts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<Account>>()
+            .decode::<ApiResponseBody<Account>, _>()
             .await?;
 
         Ok(account_response.data)
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Account {
     pub id: u32,
     pub name: String,
@@ -15,4 +16,8 @@ pub struct Account {
 
     #[serde(rename = "isVerified")]
     pub is_verified: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
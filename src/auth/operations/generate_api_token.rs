@@ -62,7 +62,7 @@ impl<'a> GenerateApiToken for Auth<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiTokenResponse>()
+            .decode::<ApiTokenResponse, _>()
             .await?;
 
         Ok(response.token)
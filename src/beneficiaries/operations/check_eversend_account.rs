@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{beneficiaries::Beneficiaries, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckAccountParams {
     /// Email address of user. Optional if phone is provided
     pub email: Option<String>,
@@ -24,9 +25,14 @@ impl From<CheckEversendAccountError> for EversendError<CheckEversendAccountError
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckEversendAccountStatus {
     #[serde(rename = "accountExists")]
-    pub account_exists: bool
+    pub account_exists: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Check Eversend Account](https://eversend.readme.io/reference/check-eversend-account)
@@ -88,7 +94,7 @@ impl<'a> CheckEversendAccount for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CheckEversendAccountStatus>>()
+            .decode::<ApiResponseBody<CheckEversendAccountStatus>, _>()
             .await?;
 
         Ok(response.data.account_exists)
@@ -139,6 +145,6 @@ mod tests {
             .unwrap();
 
         mock.assert();
-        assert_eq!(account_status, true);
+        assert!(account_status);
     }
 }
@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{beneficiaries::Beneficiaries, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBeneficaryParams {
     /// The first name.
     #[serde(rename = "firstName")]
@@ -49,9 +50,14 @@ impl From<CreateBeneficiaryError> for EversendError<CreateBeneficiaryError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBeneficiaryApiResponse {
     pub code: u16,
-    pub success: bool
+    pub success: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Create Beneficiary](https://eversend.readme.io/reference/create-beneficiaries)
@@ -116,7 +122,7 @@ impl<'a> CreateBeneficiary for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<CreateBeneficiaryApiResponse>()
+            .decode::<CreateBeneficiaryApiResponse, _>()
             .await?;
 
         Ok(())
@@ -166,7 +172,7 @@ mod tests {
 
         eversend
             .beneficiaries()
-            .create_beneficiary(&params)
+            .create_beneficiary(params)
             .await
             .unwrap();
 
@@ -15,9 +15,14 @@ impl From<DeleteBeneficiaryError> for EversendError<DeleteBeneficiaryError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeleteBeneficiaryApiResponse {
     pub code: u16,
-    pub success: bool
+    pub success: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Delete A Beneficiary](https://eversend.readme.io/reference/delete-a-beneficiary)
@@ -72,7 +77,7 @@ impl<'a> DeleteBeneficiary for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<DeleteBeneficiaryApiResponse>()
+            .decode::<DeleteBeneficiaryApiResponse, _>()
             .await?;
 
         Ok(())
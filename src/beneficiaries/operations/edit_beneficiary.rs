@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{beneficiaries::Beneficiaries, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EditBeneficiaryParams {
     /// The first name.
     #[serde(rename = "firstName")]
@@ -92,9 +93,14 @@ pub trait EditBeneficiary {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EditBeneficiaryResponse {
     pub code: u16,
     pub success: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[async_trait]
@@ -115,7 +121,7 @@ impl<'a> EditBeneficiary for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<EditBeneficiaryResponse>()
+            .decode::<EditBeneficiaryResponse, _>()
             .await?;
         Ok(())
     }
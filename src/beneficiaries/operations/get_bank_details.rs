@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{beneficiaries::{BankDetails, Beneficiaries}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetBankDetailsParams {
     /// Bank account number.
     #[serde(rename = "accountNumber")]
@@ -86,7 +87,7 @@ impl<'a> GetBankDetails for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<BankDetails>>()
+            .decode::<ApiResponseBody<BankDetails>, _>()
             .await?;
 
         Ok(response.data)
@@ -4,30 +4,71 @@ use thiserror::Error;
 
 use crate::{beneficiaries::{Beneficiaries, Beneficiary}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
-#[derive(Serialize)]
+/// Filters for [`GetBeneficiaries`].
+///
+/// Every filter is optional and left out of the request when unset, in which case the API applies
+/// its own default.
+#[derive(Serialize, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetBeneficiariesParams {
-    /// Defaults to momo
-    #[serde(rename = "type")]
-    pub beneficary_type: String,
+    /// Options are momo and bank
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub beneficary_type: Option<String>,
 
-    /// Defaults to frank
-    pub search: String,
+    /// Search by name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
 
-    /// Defaults to 100
-    pub limit: u32,
+    /// Page size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
 
-    /// Defaults to 1
-    pub page: u32,
+    /// Start from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
 }
 
-impl Default for GetBeneficiariesParams {
-    fn default() -> Self {
-        Self {
-            beneficary_type: String::from("momo"),
-            search: String::from("frank"),
-            limit: 100,
-            page: 1
-        }
+impl GetBeneficiariesParams {
+    /// Returns a [`GetBeneficiariesParamsBuilder`] with no filters set.
+    pub fn builder() -> GetBeneficiariesParamsBuilder {
+        GetBeneficiariesParamsBuilder::default()
+    }
+}
+
+/// A builder for [`GetBeneficiariesParams`].
+#[derive(Default)]
+pub struct GetBeneficiariesParamsBuilder {
+    params: GetBeneficiariesParams,
+}
+
+impl GetBeneficiariesParamsBuilder {
+    /// Only return beneficiaries of the given type, e.g. momo or bank.
+    pub fn set_type(mut self, beneficary_type: &str) -> Self {
+        self.params.beneficary_type = Some(beneficary_type.to_string());
+        self
+    }
+
+    /// Search beneficiaries by name.
+    pub fn set_search(mut self, search: &str) -> Self {
+        self.params.search = Some(search.to_string());
+        self
+    }
+
+    /// Sets the page size.
+    pub fn set_limit(mut self, limit: u32) -> Self {
+        self.params.limit = Some(limit);
+        self
+    }
+
+    /// Sets the page to fetch, starting from 1.
+    pub fn set_page(mut self, page: u32) -> Self {
+        self.params.page = Some(page);
+        self
+    }
+
+    /// Returns the constructed [`GetBeneficiariesParams`].
+    pub fn build(self) -> GetBeneficiariesParams {
+        self.params
     }
 }
 
@@ -97,7 +138,7 @@ impl<'a> GetBeneficiaries for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<BeneficiariesApiResponse>>()
+            .decode::<ApiResponseBody<BeneficiariesApiResponse>, _>()
             .await?;
 
         Ok(result.data.beneficiaries)
@@ -189,4 +230,15 @@ mod tests {
         mock.assert();
 
     }
+
+    #[test]
+    fn it_skips_unset_filters() {
+        let params = GetBeneficiariesParams::builder()
+            .set_type("bank")
+            .set_page(2)
+            .build();
+
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({ "type": "bank", "page": 2 }));
+        assert_eq!(serde_json::to_value(GetBeneficiariesParams::default()).unwrap(), json!({}));
+    }
 }
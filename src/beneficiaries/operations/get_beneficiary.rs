@@ -72,7 +72,7 @@ impl<'a> GetBeneficiary for Beneficiaries<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<GetBeneficaryApiResponse>>()
+            .decode::<ApiResponseBody<GetBeneficaryApiResponse>, _>()
             .await?;
 
         let mut beneficiaries_list = response.data.beneficiary;
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BankDetails {
     /// Bank account name.
     pub account_name: String,
@@ -10,4 +11,8 @@ pub struct BankDetails {
 
     /// Bank code from Get Delivery Banks.
     pub bank_code: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
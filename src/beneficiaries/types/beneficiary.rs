@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Beneficiary {
     /// Beneficiary's ID.
     pub id: u32,
@@ -53,4 +54,8 @@ pub struct Beneficiary {
     /// Is Momo? Deafults to true.
     #[serde(rename = "isMomo")]
     pub is_momo: bool,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -0,0 +1,300 @@
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use eversend_rust_sdk::{
+    crypto::AssetId,
+    transactions::{TransactionCurrencyOption, TransactionStatusOption, TransactionTypeOption},
+    TransactionRef,
+};
+use serde::de::DeserializeOwned;
+
+use crate::output::OutputFormat;
+
+/// Command-line tool for the Eversend API.
+///
+/// Credentials are read from EVERSEND_CLIENT_ID and EVERSEND_CLIENT_SECRET, or from a profile in
+/// ~/.config/eversend/credentials (EVERSEND_CREDENTIALS overrides the path):
+///
+///     [default]
+///     client_id = ...
+///     client_secret = ...
+#[derive(Parser)]
+#[command(name = "eversend", version, verbatim_doc_comment)]
+pub struct Args {
+    /// Credentials profile to use instead of the environment.
+    #[arg(long, global = true, env = "EVERSEND_PROFILE")]
+    pub profile: Option<String>,
+
+    /// How results are printed.
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+
+    /// Print what a mutating command would do, without doing it.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Wallets of the account.
+    #[command(subcommand)]
+    Wallets(WalletsCommand),
+
+    /// Transactions of the account.
+    #[command(subcommand)]
+    Transactions(TransactionsCommand),
+
+    /// Send money out of a wallet.
+    #[command(subcommand)]
+    Payout(PayoutCommand),
+
+    /// Saved payout beneficiaries.
+    #[command(subcommand)]
+    Beneficiaries(BeneficiariesCommand),
+
+    /// Crypto deposits.
+    #[command(subcommand)]
+    Crypto(CryptoCommand),
+}
+
+#[derive(Subcommand)]
+pub enum WalletsCommand {
+    /// List all wallets.
+    List,
+
+    /// Show a single wallet.
+    Get {
+        /// Wallet currency, e.g. UGX.
+        currency: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TransactionsCommand {
+    /// List transactions matching a set of filters.
+    List(ListTransactionsArgs),
+
+    /// Show a single transaction.
+    Get {
+        transaction_id: String,
+    },
+}
+
+#[derive(ClapArgs)]
+pub struct ListTransactionsArgs {
+    /// First day to include, as YYYY-MM-DD.
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// Last day to include, as YYYY-MM-DD.
+    #[arg(long)]
+    pub to: Option<String>,
+
+    /// pending, successful or failed.
+    #[arg(long, value_parser = parse_api_value::<TransactionStatusOption>)]
+    pub status: Option<TransactionStatusOption>,
+
+    /// collection, exchange or payout.
+    #[arg(long = "type", value_parser = parse_api_value::<TransactionTypeOption>)]
+    pub transaction_type: Option<TransactionTypeOption>,
+
+    /// Currency, e.g. UGX.
+    #[arg(long, value_parser = parse_api_value::<TransactionCurrencyOption>)]
+    pub currency: Option<TransactionCurrencyOption>,
+
+    /// Transaction reference to search for.
+    #[arg(long)]
+    pub search: Option<String>,
+
+    #[arg(long)]
+    pub page: Option<u32>,
+
+    #[arg(long)]
+    pub limit: Option<u32>,
+
+    /// Fetch every page instead of a single one.
+    #[arg(long, conflicts_with = "page")]
+    pub all: bool,
+}
+
+#[derive(Subcommand)]
+pub enum PayoutCommand {
+    /// Pay out to a mobile money account.
+    ///
+    /// The payout is quoted first. With --dry-run, the quotation is printed and nothing is paid out.
+    Momo(MomoPayoutArgs),
+}
+
+#[derive(ClapArgs)]
+pub struct MomoPayoutArgs {
+    /// Destination country ALPHA-2 code, e.g. UG.
+    #[arg(long)]
+    pub country: String,
+
+    /// Phone number in international format.
+    #[arg(long)]
+    pub phone: String,
+
+    #[arg(long)]
+    pub first_name: String,
+
+    #[arg(long)]
+    pub last_name: String,
+
+    #[arg(long)]
+    pub amount: u32,
+
+    /// Whether the amount is taken from the source wallet or received at the destination.
+    #[arg(long, value_enum, default_value_t = AmountType::Source)]
+    pub amount_type: AmountType,
+
+    /// Wallet currency to pay from, e.g. UGX.
+    #[arg(long)]
+    pub source_wallet: String,
+
+    /// Currency received, e.g. UGX.
+    #[arg(long)]
+    pub destination_currency: String,
+
+    /// Reference of the payout. Generated when not set.
+    #[arg(long)]
+    pub transaction_ref: Option<TransactionRef>,
+
+    /// Wait for the payout to succeed or fail.
+    #[arg(long)]
+    pub wait: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum AmountType {
+    Source,
+    Destination,
+}
+
+impl AmountType {
+    /// The amount type as sent by the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Source => "SOURCE",
+            Self::Destination => "DESTINATION",
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum BeneficiariesCommand {
+    /// List beneficiaries.
+    List {
+        /// Name, phone or account number to search for.
+        #[arg(long)]
+        search: Option<String>,
+
+        #[arg(long)]
+        page: Option<u32>,
+
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+
+    /// Show a single beneficiary.
+    Get {
+        beneficiary_id: u32,
+    },
+
+    /// Delete a beneficiary.
+    Delete {
+        beneficiary_id: u32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CryptoCommand {
+    /// Crypto deposit addresses.
+    #[command(subcommand)]
+    Addresses(CryptoAddressesCommand),
+}
+
+#[derive(Subcommand)]
+pub enum CryptoAddressesCommand {
+    /// List deposit addresses.
+    List,
+
+    /// Create a deposit address.
+    Create {
+        /// Asset from `Fetch Asset Chains`, e.g. TRX_USDT_S2UZ.
+        #[arg(long)]
+        asset_id: AssetId,
+
+        /// Customer email or unique identifier.
+        #[arg(long)]
+        description: String,
+
+        /// Customer name.
+        #[arg(long)]
+        owner_name: String,
+
+        #[arg(long)]
+        purpose: Option<String>,
+    },
+}
+
+/// A value of an API enum, which deserializes anything it does not know as `Unknown`.
+trait ApiValue: DeserializeOwned {
+    fn is_known(&self) -> bool;
+}
+
+impl ApiValue for TransactionCurrencyOption {
+    fn is_known(&self) -> bool {
+        !matches!(self, Self::Unknown(_))
+    }
+}
+
+impl ApiValue for TransactionStatusOption {
+    fn is_known(&self) -> bool {
+        !matches!(self, Self::Unknown(_))
+    }
+}
+
+impl ApiValue for TransactionTypeOption {
+    fn is_known(&self) -> bool {
+        !matches!(self, Self::Unknown(_))
+    }
+}
+
+/// Parses a value the way the API spells it, ignoring case, e.g. `UGX` or `pending`.
+fn parse_api_value<T: ApiValue>(value: &str) -> Result<T, String> {
+    [value.to_string(), value.to_lowercase(), value.to_uppercase()]
+        .into_iter()
+        .filter_map(|value| serde_json::from_value::<T>(serde_json::Value::String(value)).ok())
+        .find(ApiValue::is_known)
+        .ok_or_else(|| format!("unknown value `{}`", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn it_has_a_valid_command_tree() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn it_parses_api_values_ignoring_case() {
+        assert_eq!(parse_api_value::<TransactionCurrencyOption>("ugx"), Ok(TransactionCurrencyOption::UGX));
+        assert_eq!(parse_api_value::<TransactionStatusOption>("Pending"), Ok(TransactionStatusOption::PENDING));
+        assert!(parse_api_value::<TransactionStatusOption>("done").is_err());
+    }
+
+    #[test]
+    fn it_parses_global_flags_after_the_subcommand() {
+        let args = Args::try_parse_from(["eversend", "beneficiaries", "delete", "206", "--dry-run", "-o", "json"]).unwrap();
+
+        assert!(args.dry_run);
+        assert!(matches!(args.output, OutputFormat::Json));
+        assert!(matches!(args.command, Command::Beneficiaries(BeneficiariesCommand::Delete { beneficiary_id: 206 })));
+    }
+}
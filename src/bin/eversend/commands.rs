@@ -0,0 +1,7 @@
+//! One module per top-level command.
+
+pub mod beneficiaries;
+pub mod crypto;
+pub mod payout;
+pub mod transactions;
+pub mod wallets;
@@ -0,0 +1,81 @@
+use eversend_rust_sdk::beneficiaries::{
+    Beneficiary,
+    DeleteBeneficiary,
+    GetBeneficiaries,
+    GetBeneficiariesParams,
+    GetBeneficiary,
+};
+
+use crate::{args::BeneficiariesCommand, error::CliError, output::{optional, Table, Tabular}, Context};
+
+pub async fn run(context: &Context, command: BeneficiariesCommand) -> Result<(), CliError> {
+    let beneficiaries = context.eversend.beneficiaries();
+
+    match command {
+        BeneficiariesCommand::List { search, page, limit } => {
+            let params = GetBeneficiariesParams {
+                search,
+                page,
+                limit,
+                ..GetBeneficiariesParams::default()
+            };
+
+            let listed = beneficiaries.get_beneficiaries(&params).await?;
+            context.output.print(listed.as_slice())
+        },
+        BeneficiariesCommand::Get { beneficiary_id } => {
+            let beneficiary = beneficiaries.get_beneficiary(beneficiary_id).await?;
+            context.output.print(&beneficiary)
+        },
+        BeneficiariesCommand::Delete { beneficiary_id } => {
+            // Looked up first, so a dry run shows who would be deleted and a typo fails early.
+            let beneficiary = beneficiaries.get_beneficiary(beneficiary_id).await?;
+
+            if context.dry_run {
+                return context.output.dry_run("delete this beneficiary", &beneficiary);
+            }
+
+            beneficiaries.delete_beneficiary(beneficiary_id).await?;
+            context.output.notice(&format!("Deleted beneficiary {}", beneficiary_id))
+        },
+    }
+}
+
+impl Tabular for [Beneficiary] {
+    fn table(&self) -> Table {
+        let mut table = Table::new(&["ID", "NAME", "COUNTRY", "PHONE", "BANK", "ACCOUNT"]);
+
+        for beneficiary in self {
+            table.push(vec![
+                beneficiary.id.to_string(),
+                format!("{} {}", beneficiary.first_name, beneficiary.last_name),
+                beneficiary.country.clone(),
+                optional(&beneficiary.phone_number),
+                optional(&beneficiary.bank_name),
+                optional(&beneficiary.bank_account_number),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl Tabular for Beneficiary {
+    fn table(&self) -> Table {
+        Table::record(vec![
+            ("id", self.id.to_string()),
+            ("first_name", self.first_name.clone()),
+            ("last_name", self.last_name.clone()),
+            ("email", optional(&self.email)),
+            ("phone_number", optional(&self.phone_number)),
+            ("country", self.country.clone()),
+            ("bank_name", optional(&self.bank_name)),
+            ("bank_code", optional(&self.bank_code)),
+            ("bank_account_name", optional(&self.bank_account_name)),
+            ("bank_account_number", optional(&self.bank_account_number)),
+            ("momo", self.is_momo.to_string()),
+            ("bank", self.is_bank.to_string()),
+            ("eversend", self.is_eversend.to_string()),
+        ])
+    }
+}
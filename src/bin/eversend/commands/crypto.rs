@@ -0,0 +1,66 @@
+use eversend_rust_sdk::crypto::{CreateCryptoAddress, CreateCryptoAddressParams, CryptoAddress, FetchCryptoAddresses};
+
+use crate::{
+    args::{CryptoAddressesCommand, CryptoCommand},
+    error::CliError,
+    output::{Table, Tabular},
+    Context,
+};
+
+pub async fn run(context: &Context, command: CryptoCommand) -> Result<(), CliError> {
+    let crypto = context.eversend.crypto();
+
+    match command {
+        CryptoCommand::Addresses(CryptoAddressesCommand::List) => {
+            let addresses = crypto.fetch_crypto_addresses().await?;
+            context.output.print(addresses.as_slice())
+        },
+        CryptoCommand::Addresses(CryptoAddressesCommand::Create { asset_id, description, owner_name, purpose }) => {
+            let params = CreateCryptoAddressParams {
+                asset_id,
+                destination_address_description: description,
+                owner_name,
+                purpose,
+            };
+
+            if context.dry_run {
+                return context.output.dry_run("create this address", &params);
+            }
+
+            let address = crypto.create_crypto_address(&params).await?;
+            context.output.print(&address)
+        },
+    }
+}
+
+impl Tabular for [CryptoAddress] {
+    fn table(&self) -> Table {
+        let mut table = Table::new(&["ADDRESS", "COIN", "OWNER", "DESCRIPTION", "CREATED"]);
+
+        for address in self {
+            table.push(vec![
+                address.address.clone(),
+                address.coin.clone(),
+                address.owner_name.clone(),
+                address.destination_address_description.clone(),
+                address.created_at.clone(),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl Tabular for CryptoAddress {
+    fn table(&self) -> Table {
+        Table::record(vec![
+            ("address", self.address.clone()),
+            ("coin", self.coin.clone()),
+            ("owner_name", self.owner_name.clone()),
+            ("description", self.destination_address_description.clone()),
+            ("purpose", self.purpose.clone()),
+            ("created_at", self.created_at.clone()),
+            ("updated_at", self.updated_at.clone()),
+        ])
+    }
+}
@@ -0,0 +1,84 @@
+use eversend_rust_sdk::{
+    payouts::{self, PayoutRecipient, PayoutRequest, PayoutState, SendPayout},
+    transactions::WaitOptions,
+    TransactionRef,
+};
+use serde_json::json;
+
+use crate::{
+    args::{MomoPayoutArgs, PayoutCommand},
+    error::CliError,
+    output::{optional, Table, Tabular},
+    Context,
+};
+
+pub async fn run(context: &Context, command: PayoutCommand) -> Result<(), CliError> {
+    match command {
+        PayoutCommand::Momo(args) => momo(context, args).await,
+    }
+}
+
+async fn momo(context: &Context, args: MomoPayoutArgs) -> Result<(), CliError> {
+    let transaction_ref = args.transaction_ref.unwrap_or_else(TransactionRef::generate);
+
+    let request = PayoutRequest {
+        amount: args.amount,
+        amount_type: args.amount_type.as_str().to_string(),
+        country: args.country,
+        destination_currency: args.destination_currency,
+        recipient: PayoutRecipient::Momo {
+            first_name: args.first_name,
+            last_name: args.last_name,
+            phone_number: args.phone,
+        },
+        source_wallet: args.source_wallet,
+        transaction_ref: Some(transaction_ref.clone()),
+    };
+
+    // Quoting does not move money, so a dry run still shows the fees and exchange rate.
+    let mut payout = context.eversend.payouts().send(request).await?;
+
+    if context.dry_run {
+        return context.output.dry_run(
+            "submit this payout",
+            &json!({
+                "transaction_ref": transaction_ref,
+                "quotation": payout.quotation(),
+            }),
+        );
+    }
+
+    payout.approve().await?;
+
+    if args.wait {
+        payout.wait(&WaitOptions::default()).await?;
+    }
+
+    match payout.state() {
+        PayoutState::Submitted(transaction) => context.output.print(transaction),
+        PayoutState::Pending(transaction) | PayoutState::Completed(transaction) => context.output.print(transaction),
+        PayoutState::Failed(transaction) => {
+            context.output.print(transaction)?;
+            Err(CliError::Eversend(format!("payout {} failed", transaction.transaction_id)))
+        },
+        PayoutState::Quoted(_) => unreachable!("the payout was approved"),
+    }
+}
+
+impl Tabular for payouts::Transaction {
+    fn table(&self) -> Table {
+        Table::record(vec![
+            ("id", self.transaction_id.clone()),
+            ("ref", optional(&self.transaction_ref)),
+            ("type", self.transaction_type.clone()),
+            ("status", self.status.clone()),
+            ("currency", self.currency.clone()),
+            ("amount", self.amount.to_string()),
+            ("fees", self.fees.to_string()),
+            ("destination_amount", self.destination_amount.clone()),
+            ("destination_currency", self.destination_currency.clone()),
+            ("destination_country", self.destination_country.clone()),
+            ("created_at", self.created_at.clone()),
+        ])
+    }
+}
@@ -0,0 +1,129 @@
+use eversend_rust_sdk::transactions::{
+    GetTransaction,
+    GetTransactionParams,
+    GetTransactions,
+    GetTransactionsError,
+    GetTransactionsParams,
+    Transaction,
+};
+
+use crate::{
+    args::{ListTransactionsArgs, TransactionsCommand},
+    error::CliError,
+    output::{api_name, optional, Table, Tabular},
+    Context,
+};
+
+pub async fn run(context: &Context, command: TransactionsCommand) -> Result<(), CliError> {
+    let transactions = context.eversend.transactions();
+
+    match command {
+        TransactionsCommand::List(args) => {
+            let all = args.all;
+            let mut params = filters(args).map_err(|err| CliError::Eversend(err.to_string()))?;
+            let mut listed = Vec::new();
+
+            loop {
+                let page = transactions.get_transactions(&params).await?;
+                let has_next_page = all && page.has_next_page() && !page.transactions.is_empty();
+                listed.extend(page.transactions);
+
+                if !has_next_page {
+                    break;
+                }
+
+                params.page = Some(page.page + 1);
+            }
+
+            context.output.print(listed.as_slice())
+        },
+        TransactionsCommand::Get { transaction_id } => {
+            let transaction = transactions
+                .get_transaction(&GetTransactionParams { transaction_id })
+                .await?;
+
+            context.output.print(&transaction)
+        },
+    }
+}
+
+fn filters(args: ListTransactionsArgs) -> Result<GetTransactionsParams, GetTransactionsError> {
+    let mut builder = GetTransactionsParams::builder();
+
+    if let Some(from) = &args.from {
+        builder = builder.set_from(from);
+    }
+
+    if let Some(to) = &args.to {
+        builder = builder.set_to(to);
+    }
+
+    if let Some(status) = args.status {
+        builder = builder.set_status(status);
+    }
+
+    if let Some(transaction_type) = args.transaction_type {
+        builder = builder.set_type(transaction_type);
+    }
+
+    if let Some(currency) = args.currency {
+        builder = builder.set_currency(currency);
+    }
+
+    if let Some(search) = &args.search {
+        builder = builder.set_search(search);
+    }
+
+    if let Some(limit) = args.limit {
+        builder = builder.set_limit(limit);
+    }
+
+    builder = builder.set_page(args.page.unwrap_or(1));
+
+    builder.build()
+}
+
+impl Tabular for [Transaction] {
+    fn table(&self) -> Table {
+        let mut table = Table::new(&["ID", "REF", "TYPE", "STATUS", "CURRENCY", "AMOUNT", "FEES", "CREATED"]);
+
+        for transaction in self {
+            table.push(vec![
+                transaction.transaction_id.clone(),
+                optional(&transaction.transaction_ref),
+                api_name(&transaction.transaction_type),
+                transaction.status.as_str().to_string(),
+                api_name(&transaction.currency),
+                transaction.amount.clone(),
+                optional(&transaction.fees),
+                transaction.created_at.clone(),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl Tabular for Transaction {
+    fn table(&self) -> Table {
+        Table::record(vec![
+            ("id", self.transaction_id.clone()),
+            ("ref", optional(&self.transaction_ref)),
+            ("type", api_name(&self.transaction_type)),
+            ("status", self.status.as_str().to_string()),
+            ("currency", api_name(&self.currency)),
+            ("amount", self.amount.clone()),
+            ("fees", optional(&self.fees)),
+            ("balance_before", self.balance_before.clone()),
+            ("balance_after", self.balance_after.clone()),
+            ("destination_amount", self.destination_amount.clone()),
+            ("destination_currency", optional(&self.destination_currency)),
+            ("destination_country", optional(&self.destination_country)),
+            ("beneficiary", optional(&self.beneficiary)),
+            ("reason", optional(&self.reason)),
+            ("refunded", self.is_refunded.to_string()),
+            ("created_at", self.created_at.clone()),
+            ("updated_at", self.updated_at.clone()),
+        ])
+    }
+}
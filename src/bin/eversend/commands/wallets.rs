@@ -0,0 +1,50 @@
+use eversend_rust_sdk::wallets::{GetWallet, GetWallets, Wallet, WalletId};
+
+use crate::{args::WalletsCommand, error::CliError, output::{Table, Tabular}, Context};
+
+pub async fn run(context: &Context, command: WalletsCommand) -> Result<(), CliError> {
+    let wallets = context.eversend.wallets();
+
+    match command {
+        WalletsCommand::List => {
+            let response = wallets.get_wallets().await?;
+            context.output.print(response.data.as_slice())
+        },
+        WalletsCommand::Get { currency } => {
+            let wallet = wallets.get_wallet(&WalletId::from(currency)).await?;
+            context.output.print(&wallet)
+        },
+    }
+}
+
+impl Tabular for [Wallet] {
+    fn table(&self) -> Table {
+        let mut table = Table::new(&["CURRENCY", "NAME", "BALANCE", "ENABLED", "MAIN"]);
+
+        for wallet in self {
+            table.push(vec![
+                wallet.currency.clone(),
+                wallet.name.clone(),
+                wallet.amount.to_string(),
+                wallet.enabled.to_string(),
+                wallet.is_main.to_string(),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl Tabular for Wallet {
+    fn table(&self) -> Table {
+        Table::record(vec![
+            ("currency", self.currency.clone()),
+            ("currency_type", self.currency_type.clone()),
+            ("name", self.name.clone()),
+            ("balance", self.amount.to_string()),
+            ("balance_in_base_currency", self.amount_in_base_currency.to_string()),
+            ("enabled", self.enabled.to_string()),
+            ("main", self.is_main.to_string()),
+        ])
+    }
+}
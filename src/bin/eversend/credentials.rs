@@ -0,0 +1,252 @@
+use std::{collections::HashMap, env, fmt, fs, io, path::PathBuf};
+
+use thiserror::Error;
+
+/// The profile used when none is given.
+const DEFAULT_PROFILE: &str = "default";
+
+/// An error returned while looking up credentials.
+#[derive(Debug, Error)]
+pub enum CredentialsError {
+    #[error(
+        "no credentials found, set EVERSEND_CLIENT_ID and EVERSEND_CLIENT_SECRET or add a [{}] profile to {}",
+        DEFAULT_PROFILE,
+        path.display()
+    )]
+    Missing { path: PathBuf },
+
+    #[error("could not read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+
+    #[error("{}:{line}: {message}", path.display())]
+    Parse { path: PathBuf, line: usize, message: &'static str },
+
+    #[error("profile `{profile}` not found in {}", path.display())]
+    UnknownProfile { profile: String, path: PathBuf },
+
+    #[error("profile `{profile}` in {} has no `{key}`", path.display())]
+    MissingKey { profile: String, key: &'static str, path: PathBuf },
+}
+
+/// API credentials of a merchant.
+#[derive(PartialEq, Eq)]
+pub struct Credentials {
+    pub client_id: String,
+    pub client_secret: String,
+
+    /// Overrides the API base URL, e.g. to use a sandbox.
+    pub base_url: Option<String>,
+}
+
+impl fmt::Debug for Credentials {
+    /// Leaves the secret out, so it does not end up in logs or error reports.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"[redacted]")
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// Looks up credentials.
+    ///
+    /// A named profile is read from the credentials file. Otherwise EVERSEND_CLIENT_ID and
+    /// EVERSEND_CLIENT_SECRET are used when both are set, falling back to the default profile.
+    /// EVERSEND_BASE_URL overrides the base URL in every case.
+    pub fn resolve(profile: Option<&str>) -> Result<Self, CredentialsError> {
+        let base_url = var("EVERSEND_BASE_URL");
+
+        if profile.is_none() {
+            if let (Some(client_id), Some(client_secret)) = (var("EVERSEND_CLIENT_ID"), var("EVERSEND_CLIENT_SECRET")) {
+                return Ok(Self {
+                    client_id,
+                    client_secret,
+                    base_url,
+                });
+            }
+        }
+
+        let path = credentials_path();
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound && profile.is_none() => {
+                return Err(CredentialsError::Missing { path });
+            },
+            Err(source) => return Err(CredentialsError::Read { path, source }),
+        };
+
+        let credentials = Self::from_profile(&contents, profile.unwrap_or(DEFAULT_PROFILE))
+            .map_err(|err| err.at(path))?;
+
+        Ok(Self {
+            base_url: base_url.or(credentials.base_url),
+            ..credentials
+        })
+    }
+
+    /// Reads a profile from the contents of a credentials file.
+    fn from_profile(contents: &str, profile: &str) -> Result<Self, CredentialsError> {
+        let profiles = parse_profiles(contents)?;
+
+        let mut values = profiles
+            .get(profile)
+            .cloned()
+            .ok_or_else(|| CredentialsError::UnknownProfile {
+                profile: profile.to_string(),
+                path: PathBuf::new(),
+            })?;
+
+        let mut require = |key: &'static str| {
+            values.remove(key).ok_or_else(|| CredentialsError::MissingKey {
+                profile: profile.to_string(),
+                key,
+                path: PathBuf::new(),
+            })
+        };
+
+        Ok(Self {
+            client_id: require("client_id")?,
+            client_secret: require("client_secret")?,
+            base_url: values.remove("base_url"),
+        })
+    }
+}
+
+impl CredentialsError {
+    /// Sets the path of the credentials file the error refers to.
+    fn at(mut self, file: PathBuf) -> Self {
+        match &mut self {
+            Self::Missing { path }
+            | Self::Read { path, .. }
+            | Self::Parse { path, .. }
+            | Self::UnknownProfile { path, .. }
+            | Self::MissingKey { path, .. } => *path = file,
+        }
+
+        self
+    }
+}
+
+fn var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// EVERSEND_CREDENTIALS, or `eversend/credentials` in the user's config directory.
+fn credentials_path() -> PathBuf {
+    if let Some(path) = var("EVERSEND_CREDENTIALS") {
+        return PathBuf::from(path);
+    }
+
+    let config = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_default();
+
+    config.join("eversend").join("credentials")
+}
+
+/// Parses `[profile]` sections of `key = value` lines. Blank lines and lines starting with `#` or
+/// `;` are ignored.
+fn parse_profiles(contents: &str) -> Result<HashMap<String, HashMap<String, String>>, CredentialsError> {
+    let mut profiles = HashMap::<String, HashMap<String, String>>::new();
+    let mut current = None;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let error = |message| CredentialsError::Parse {
+            path: PathBuf::new(),
+            line: index + 1,
+            message,
+        };
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| error("unclosed profile name"))?.trim();
+            profiles.entry(name.to_string()).or_default();
+            current = Some(name.to_string());
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(|| error("expected `key = value`"))?;
+        let profile = current.as_ref().ok_or_else(|| error("value outside of a profile"))?;
+
+        profiles
+            .entry(profile.clone())
+            .or_default()
+            .insert(key.trim().to_string(), value.trim().to_string());
+    }
+
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "
+        # Live account
+        [default]
+        client_id = live_id
+        client_secret = live_secret
+
+        [sandbox]
+        client_id=sandbox_id
+        client_secret = sandbox_secret
+        base_url = https://sandbox.example.com
+    ";
+
+    #[test]
+    fn it_reads_a_profile() {
+        assert_eq!(
+            Credentials::from_profile(CONTENTS, "sandbox").unwrap(),
+            Credentials {
+                client_id: String::from("sandbox_id"),
+                client_secret: String::from("sandbox_secret"),
+                base_url: Some(String::from("https://sandbox.example.com")),
+            }
+        );
+
+        assert_eq!(Credentials::from_profile(CONTENTS, "default").unwrap().base_url, None);
+    }
+
+    #[test]
+    fn it_redacts_the_secret_when_debugged() {
+        let credentials = Credentials::from_profile(CONTENTS, "sandbox").unwrap();
+        let debugged = format!("{:?}", credentials);
+
+        assert!(debugged.contains("sandbox_id"));
+        assert!(!debugged.contains("sandbox_secret"));
+    }
+
+    #[test]
+    fn it_reports_unknown_profiles_and_missing_keys() {
+        assert!(matches!(
+            Credentials::from_profile(CONTENTS, "staging"),
+            Err(CredentialsError::UnknownProfile { .. })
+        ));
+
+        assert!(matches!(
+            Credentials::from_profile("[default]\nclient_id = id\n", "default"),
+            Err(CredentialsError::MissingKey { key: "client_secret", .. })
+        ));
+    }
+
+    #[test]
+    fn it_reports_the_line_of_syntax_errors() {
+        assert!(matches!(
+            parse_profiles("[default]\nclient_id\n"),
+            Err(CredentialsError::Parse { line: 2, .. })
+        ));
+
+        assert!(matches!(
+            parse_profiles("client_id = id\n"),
+            Err(CredentialsError::Parse { line: 1, .. })
+        ));
+    }
+}
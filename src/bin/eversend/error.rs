@@ -0,0 +1,35 @@
+use std::{fmt::Display, io};
+
+use eversend_rust_sdk::EversendError;
+use thiserror::Error;
+
+use crate::credentials::CredentialsError;
+
+/// An error that ends a command.
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Credentials(#[from] CredentialsError),
+
+    /// An SDK error, described with its operational error if any.
+    #[error("{0}")]
+    Eversend(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl<E: Display> From<EversendError<E>> for CliError {
+    fn from(err: EversendError<E>) -> Self {
+        let message = match err {
+            EversendError::Operation(err) => err.to_string(),
+            EversendError::RequestError(err) => format!("request error: {}", err),
+            err => err.to_string(),
+        };
+
+        Self::Eversend(message)
+    }
+}
@@ -0,0 +1,85 @@
+//! `eversend`, a command-line tool for the Eversend API built on the SDK.
+//!
+//! Install it with `cargo install eversend_rust_sdk --features cli`, then run `eversend --help`.
+
+mod args;
+mod commands;
+mod credentials;
+mod error;
+mod output;
+
+use std::process::ExitCode;
+
+use clap::Parser;
+use eversend_rust_sdk::{auth::GenerateApiToken, ApiToken, ClientId, ClientSecret, Eversend};
+
+use crate::{
+    args::{Args, Command},
+    credentials::Credentials,
+    error::CliError,
+    output::Output,
+};
+
+/// What every command runs with.
+pub struct Context {
+    pub eversend: Eversend,
+    pub output: Output,
+
+    /// Mutating commands only print what they would do.
+    pub dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+async fn run(args: Args) -> Result<(), CliError> {
+    let credentials = Credentials::resolve(args.profile.as_deref())?;
+
+    let context = Context {
+        eversend: connect(&credentials).await?,
+        output: Output::new(args.output),
+        dry_run: args.dry_run,
+    };
+
+    match args.command {
+        Command::Wallets(command) => commands::wallets::run(&context, command).await,
+        Command::Transactions(command) => commands::transactions::run(&context, command).await,
+        Command::Payout(command) => commands::payout::run(&context, command).await,
+        Command::Beneficiaries(command) => commands::beneficiaries::run(&context, command).await,
+        Command::Crypto(command) => commands::crypto::run(&context, command).await,
+    }
+}
+
+/// Returns a client authenticated with a freshly generated API token.
+async fn connect(credentials: &Credentials) -> Result<Eversend, CliError> {
+    let client_id = ClientId::from(credentials.client_id.as_str());
+    let client_secret = ClientSecret::from(credentials.client_secret.as_str());
+
+    let client = |api_token: Option<&ApiToken>| {
+        let mut builder = Eversend::builder(&client_id, &client_secret);
+
+        if let Some(base_url) = &credentials.base_url {
+            builder = builder.set_base_url(base_url);
+        }
+
+        if let Some(api_token) = api_token {
+            builder = builder.set_api_token(api_token);
+        }
+
+        builder.build()
+    };
+
+    let api_token = client(None).auth().generate_api_token().await?;
+
+    Ok(client(Some(&api_token)))
+}
@@ -0,0 +1,176 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::error::CliError;
+
+/// How results are printed.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns, for people.
+    Table,
+
+    /// The API representation, for scripts.
+    Json,
+}
+
+/// Rows of text printed as aligned columns.
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// A table of a single value, with one line per field.
+    pub fn record(fields: Vec<(&'static str, String)>) -> Self {
+        let mut table = Self::new(&["FIELD", "VALUE"]);
+
+        for (name, value) in fields {
+            table.push(vec![name.to_string(), value]);
+        }
+
+        table
+    }
+
+    /// Adds a row, with one cell per header.
+    pub fn push(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    pub fn render(&self) -> String {
+        let mut widths = self.headers.iter().map(|header| header.chars().count()).collect::<Vec<_>>();
+
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let headers = self.headers.iter().map(|header| header.to_string()).collect::<Vec<_>>();
+        let mut rendered = String::new();
+
+        for row in std::iter::once(&headers).chain(&self.rows) {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+
+            rendered.push_str(line.trim_end());
+            rendered.push('\n');
+        }
+
+        rendered
+    }
+}
+
+/// A value that can be printed as a [`Table`].
+pub trait Tabular {
+    fn table(&self) -> Table;
+}
+
+/// Prints results to stdout in the selected [`OutputFormat`].
+pub struct Output {
+    format: OutputFormat,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn print<T: Serialize + Tabular + ?Sized>(&self, value: &T) -> Result<(), CliError> {
+        match self.format {
+            OutputFormat::Table => write(&value.table().render()),
+            OutputFormat::Json => self.json(value),
+        }
+    }
+
+    /// Prints a short confirmation, e.g. after a deletion.
+    pub fn notice(&self, message: &str) -> Result<(), CliError> {
+        match self.format {
+            OutputFormat::Table => write(&format!("{}\n", message)),
+            OutputFormat::Json => self.json(&json!({ "message": message })),
+        }
+    }
+
+    /// Prints what a mutating command would have sent, instead of sending it.
+    pub fn dry_run<T: Serialize + ?Sized>(&self, action: &str, request: &T) -> Result<(), CliError> {
+        match self.format {
+            OutputFormat::Table => write(&format!(
+                "dry run, would {}:\n{}\n",
+                action,
+                serde_json::to_string_pretty(request)?
+            )),
+            OutputFormat::Json => self.json(&json!({
+                "dry_run": true,
+                "action": action,
+                "request": request,
+            })),
+        }
+    }
+
+    fn json<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), CliError> {
+        write(&format!("{}\n", serde_json::to_string_pretty(value)?))
+    }
+}
+
+fn write(text: &str) -> Result<(), CliError> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Formats an optional value, leaving the cell empty when unset.
+pub fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
+
+/// The name an enum is sent as by the API, e.g. `UGX` or `payout`.
+pub fn api_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_aligns_columns() {
+        let mut table = Table::new(&["CURRENCY", "BALANCE", "NAME"]);
+        table.push(vec![String::from("UGX"), String::from("1000000"), String::from("Uganda Shilling")]);
+        table.push(vec![String::from("KES"), String::from("5"), String::new()]);
+
+        assert_eq!(
+            table.render(),
+            "CURRENCY  BALANCE  NAME\n\
+             UGX       1000000  Uganda Shilling\n\
+             KES       5\n"
+        );
+    }
+
+    #[test]
+    fn it_renders_records_as_field_value_pairs() {
+        let table = Table::record(vec![
+            ("id", String::from("206")),
+            ("country", String::from("UG")),
+        ]);
+
+        assert_eq!(table.render(), "FIELD    VALUE\nid       206\ncountry  UG\n");
+    }
+}
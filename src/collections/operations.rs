@@ -1,7 +1,9 @@
+mod collection_session;
 mod get_collection_fees;
 mod get_collection_otp;
 mod get_mobile_money_collection;
 
+pub use collection_session::*;
 pub use get_collection_fees::*;
 pub use get_collection_otp::*;
 pub use get_mobile_money_collection::*;
@@ -0,0 +1,562 @@
+use std::time::{Duration, SystemTime};
+
+use thiserror::Error;
+
+use crate::{collections::{GetCollectionOtp, GetCollectionOtpParams, GetMobileMoneyCollection, GetMobileMoneyCollectionError, GetMobileMoneyCollectionParams, MobileMoneyCollection, Otp, is_otp_rejection}, Eversend, EversendError, EversendResult};
+
+/// Limits of a [`CollectionSession`].
+#[derive(Debug, Clone)]
+pub struct CollectionSessionOptions {
+    /// How long a PIN is accepted after being sent. Defaults to 5 minutes.
+    pub otp_lifetime: Duration,
+
+    /// How long to wait before sending another PIN. Defaults to 1 minute.
+    pub resend_interval: Duration,
+
+    /// How many times the PIN may be sent again after the first one. Defaults to 3.
+    pub max_resends: u32,
+
+    /// How many PINs may be rejected before the session is locked. Defaults to 3.
+    pub max_attempts: u32,
+
+    /// Tells from the status and message of a [`GetMobileMoneyCollectionError::Rejected`]
+    /// collection whether the PIN was rejected, which uses up an attempt. Defaults to
+    /// [`is_otp_rejection`], a best-effort guess from the wording of the message.
+    pub is_otp_rejection: fn(u16, &str) -> bool,
+}
+
+impl Default for CollectionSessionOptions {
+    fn default() -> Self {
+        Self {
+            otp_lifetime: Duration::from_secs(5 * 60),
+            resend_interval: Duration::from_secs(60),
+            max_resends: 3,
+            max_attempts: 3,
+            is_otp_rejection,
+        }
+    }
+}
+
+/// Where a [`CollectionSession`] stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectionSessionState {
+    /// No PIN was sent yet.
+    New,
+
+    /// A PIN was sent to the customer, and is waiting to be submitted.
+    AwaitingPin {
+        pin_id: String,
+        expires_at: SystemTime,
+    },
+
+    /// The collection was submitted with an accepted PIN.
+    Submitted,
+
+    /// Too many PINs were rejected. Start a new session to try again.
+    Locked,
+}
+
+/// An error returned from [`CollectionSession`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CollectionSessionError {
+    /// A PIN was submitted before one was sent.
+    #[error("no OTP was requested yet")]
+    OtpNotRequested,
+
+    /// The PIN was submitted after it expired. Request a new one.
+    #[error("the OTP expired")]
+    OtpExpired,
+
+    /// The API rejected the PIN as invalid or expired, with its message, as told by
+    /// [`CollectionSessionOptions::is_otp_rejection`]. Uses up an attempt.
+    #[error("the OTP was rejected: {0}")]
+    OtpRejected(String),
+
+    /// The API refused the collection for another reason than the PIN, with its status and
+    /// message. Does not use up an attempt.
+    #[error("the collection was rejected with status {status}: {message}")]
+    Rejected {
+        status: u16,
+        message: String,
+    },
+
+    /// A new PIN was requested before [`CollectionSessionOptions::resend_interval`] elapsed.
+    #[error("an OTP can be sent again in {}s", retry_in.as_secs())]
+    ResendTooSoon {
+        retry_in: Duration,
+    },
+
+    /// The PIN was sent again [`CollectionSessionOptions::max_resends`] times already.
+    #[error("the OTP was sent too many times")]
+    TooManyResends,
+
+    /// Too many PINs were rejected.
+    #[error("too many invalid OTPs were submitted")]
+    Locked,
+
+    /// The collection was submitted already.
+    #[error("the collection was submitted already")]
+    AlreadySubmitted,
+}
+
+impl From<CollectionSessionError> for EversendError<CollectionSessionError> {
+    fn from(err: CollectionSessionError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Verifies the phone number of a collection through a one-time PIN, for accounts that are not
+/// whitelisted, then submits the collection.
+///
+/// The session requests the PIN with [`GetCollectionOtp`], keeps its `pinId` and expiry, and passes
+/// both to [`GetMobileMoneyCollection`] once the customer gave the PIN. It throttles resending the
+/// PIN, and locks after [`CollectionSessionOptions::max_attempts`] rejected PINs, over every PIN
+/// sent during the session.
+///
+/// # Examples
+/// ```
+/// # use eversend_rust_sdk::EversendResult;
+/// # use eversend_rust_sdk::collections::*;
+/// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
+///
+/// # async fn run(pin: &str) -> EversendResult<(), CollectionSessionError> {
+///     let eversend = Eversend::new(
+///         &ClientId::from("sk_example_123456789"),
+///         &ClientSecret::from("sk_example_123456780")
+///     );
+///
+///     let mut session = CollectionSession::new(
+///         &eversend,
+///         GetMobileMoneyCollectionParams {
+///             amount: 1000,
+///             country: String::from("UG"),
+///             currency: String::from("UGX"),
+///             phone_number: String::from("+256712345678"),
+///             transaction_ref: Some(TransactionRef::generate()),
+///             redirect_url: None,
+///             customer: None,
+///             otp: None,
+///         },
+///         CollectionSessionOptions::default()
+///     );
+///
+///     session.request_otp().await?;
+///
+///     // Ask the customer for the PIN they received.
+///
+///     let collection = session.submit_pin(pin).await?;
+///
+///     Ok(())
+/// # }
+/// ```
+pub struct CollectionSession {
+    eversend: Eversend,
+    params: GetMobileMoneyCollectionParams,
+    options: CollectionSessionOptions,
+    state: CollectionSessionState,
+    resends: u32,
+    attempts: u32,
+    last_sent_at: Option<SystemTime>,
+}
+
+impl CollectionSession {
+    /// Returns a new session for a collection. Any `otp` set on `params` is replaced.
+    pub fn new(eversend: &Eversend, params: GetMobileMoneyCollectionParams, options: CollectionSessionOptions) -> Self {
+        Self {
+            eversend: eversend.clone(),
+            params,
+            options,
+            state: CollectionSessionState::New,
+            resends: 0,
+            attempts: 0,
+            last_sent_at: None,
+        }
+    }
+
+    /// Where the session stands.
+    pub fn state(&self) -> &CollectionSessionState {
+        &self.state
+    }
+
+    /// How many more PINs may be rejected before the session is locked.
+    pub fn attempts_left(&self) -> u32 {
+        self.options.max_attempts.saturating_sub(self.attempts)
+    }
+
+    /// When a new PIN may be requested, or `None` if no more may be sent.
+    pub fn resend_available_at(&self) -> Option<SystemTime> {
+        match self.last_sent_at {
+            None => Some(SystemTime::now()),
+            Some(_) if self.resends >= self.options.max_resends => None,
+            Some(sent_at) => Some(sent_at + self.options.resend_interval),
+        }
+    }
+
+    /// Sends a PIN to the customer's phone, or a new one if one was sent already.
+    ///
+    /// A new PIN replaces the previous one, which cannot be submitted anymore.
+    pub async fn request_otp(&mut self) -> EversendResult<(), CollectionSessionError> {
+        match self.state {
+            CollectionSessionState::Submitted => return Err(CollectionSessionError::AlreadySubmitted.into()),
+            CollectionSessionState::Locked => return Err(CollectionSessionError::Locked.into()),
+            CollectionSessionState::New | CollectionSessionState::AwaitingPin { .. } => {},
+        }
+
+        if let Some(sent_at) = self.last_sent_at {
+            if self.resends >= self.options.max_resends {
+                return Err(CollectionSessionError::TooManyResends.into());
+            }
+
+            let elapsed = sent_at.elapsed().unwrap_or_default();
+
+            if elapsed < self.options.resend_interval {
+                return Err(CollectionSessionError::ResendTooSoon {
+                    retry_in: self.options.resend_interval - elapsed,
+                }.into());
+            }
+        }
+
+        let pin_id = self
+            .eversend
+            .collections()
+            .get_collection_otp(&GetCollectionOtpParams {
+                phone_number: self.params.phone_number.clone(),
+            })
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))?;
+
+        let sent_at = SystemTime::now();
+
+        if self.last_sent_at.is_some() {
+            self.resends += 1;
+        }
+
+        self.last_sent_at = Some(sent_at);
+        self.state = CollectionSessionState::AwaitingPin {
+            pin_id,
+            expires_at: sent_at + self.options.otp_lifetime,
+        };
+
+        Ok(())
+    }
+
+    /// Submits the collection with the PIN the customer received.
+    ///
+    /// A PIN rejected by the API as invalid or expired, as told by
+    /// [`CollectionSessionOptions::is_otp_rejection`], uses up an attempt, and can be corrected by
+    /// submitting again. Other errors, e.g. a rate limit, do not. Network errors leave it unknown
+    /// whether the collection was created, so set a `transaction_ref` on the collection to look it
+    /// up before submitting again.
+    pub async fn submit_pin(&mut self, pin: &str) -> EversendResult<MobileMoneyCollection, CollectionSessionError> {
+        let pin_id = match &self.state {
+            CollectionSessionState::New => return Err(CollectionSessionError::OtpNotRequested.into()),
+            CollectionSessionState::Submitted => return Err(CollectionSessionError::AlreadySubmitted.into()),
+            CollectionSessionState::Locked => return Err(CollectionSessionError::Locked.into()),
+            CollectionSessionState::AwaitingPin { expires_at, .. } if SystemTime::now() >= *expires_at => {
+                return Err(CollectionSessionError::OtpExpired.into());
+            },
+            CollectionSessionState::AwaitingPin { pin_id, .. } => pin_id.clone(),
+        };
+
+        self.params.otp = Some(Otp {
+            pin: pin.to_string(),
+            pin_id,
+        });
+
+        let result = self
+            .eversend
+            .collections()
+            .get_mobile_money_collection(&self.params)
+            .await
+            .map_err(|err| err.map_operation(|err| match err {
+                GetMobileMoneyCollectionError::Rejected { status, message } => {
+                    match (self.options.is_otp_rejection)(status, &message) {
+                        true => CollectionSessionError::OtpRejected(message),
+                        false => CollectionSessionError::Rejected { status, message },
+                    }
+                },
+            }));
+
+        match &result {
+            Ok(_) => self.state = CollectionSessionState::Submitted,
+            Err(EversendError::Operation(CollectionSessionError::OtpRejected(_))) => {
+                self.attempts += 1;
+
+                if self.attempts >= self.options.max_attempts {
+                    self.state = CollectionSessionState::Locked;
+                }
+            },
+            Err(_) => {},
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher, Mock};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend(prefix: &str) -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), prefix))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn params() -> GetMobileMoneyCollectionParams {
+        GetMobileMoneyCollectionParams {
+            amount: 1000,
+            country: String::from("UG"),
+            currency: String::from("UGX"),
+            phone_number: String::from("+256712345678"),
+            transaction_ref: Some("ADR234526534".parse().unwrap()),
+            redirect_url: None,
+            customer: None,
+            otp: None,
+        }
+    }
+
+    fn mock_otp(prefix: &str, pin_id: &str, hits: usize) -> Mock {
+        mock("POST", format!("/{}/collections/otp", prefix).as_str())
+            .match_body(Matcher::Json(json!({ "phone": "+256712345678" })))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": { "pinId": pin_id },
+                "success": true
+            }).to_string())
+            .expect(hits)
+            .create()
+    }
+
+    #[tokio::test]
+    async fn it_submits_the_collection_with_the_pin() {
+        let eversend = eversend("collection-session-submit");
+        let otp = mock_otp("collection-session-submit", "132466gdfsfsrey1535", 1);
+
+        let collection = mock("POST", "/collection-session-submit/collections/momo")
+            .match_body(Matcher::PartialJson(json!({
+                "otp": { "pin": "123456", "pinId": "132466gdfsfsrey1535" }
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "transactionId": "BE31650891443685",
+                    "transactionRef": "ADR234526534",
+                    "type": "collection",
+                    "currency": "UGX",
+                    "amount": "1000",
+                    "customer": null,
+                    "balanceBefore": null,
+                    "balanceAfter": null,
+                    "status": "pending",
+                    "createdAt": "2022-04-25T12:57:41.444Z",
+                    "updatedAt": "2022-04-25T12:57:41.445Z"
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions::default());
+
+        assert!(matches!(
+            session.submit_pin("123456").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpNotRequested))
+        ));
+
+        session.request_otp().await.unwrap();
+
+        assert!(matches!(
+            session.state(),
+            CollectionSessionState::AwaitingPin { pin_id, .. } if pin_id == "132466gdfsfsrey1535"
+        ));
+        assert!(matches!(
+            session.request_otp().await,
+            Err(EversendError::Operation(CollectionSessionError::ResendTooSoon { .. }))
+        ));
+
+        let result = session.submit_pin("123456").await.unwrap();
+
+        assert_eq!(result.transaction_id, "BE31650891443685");
+        assert_eq!(session.state(), &CollectionSessionState::Submitted);
+        assert!(matches!(
+            session.submit_pin("123456").await,
+            Err(EversendError::Operation(CollectionSessionError::AlreadySubmitted))
+        ));
+
+        otp.assert();
+        collection.assert();
+    }
+
+    #[tokio::test]
+    async fn it_limits_resends_and_rejected_pins() {
+        let eversend = eversend("collection-session-limits");
+        let otp = mock_otp("collection-session-limits", "132466gdfsfsrey1535", 2);
+
+        let collection = mock("POST", "/collection-session-limits/collections/momo")
+            .with_status(400)
+            .with_body(json!({ "code": 400, "message": "Invalid OTP", "success": false }).to_string())
+            .expect(2)
+            .create();
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions {
+            resend_interval: Duration::ZERO,
+            max_resends: 1,
+            max_attempts: 2,
+            ..CollectionSessionOptions::default()
+        });
+
+        session.request_otp().await.unwrap();
+        session.request_otp().await.unwrap();
+
+        assert_eq!(session.resend_available_at(), None);
+        assert!(matches!(
+            session.request_otp().await,
+            Err(EversendError::Operation(CollectionSessionError::TooManyResends))
+        ));
+
+        assert!(matches!(
+            session.submit_pin("000000").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpRejected(_)))
+        ));
+        assert_eq!(session.attempts_left(), 1);
+        assert!(matches!(
+            session.submit_pin("111111").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpRejected(message))) if message == "Invalid OTP"
+        ));
+        assert_eq!(session.state(), &CollectionSessionState::Locked);
+        assert!(matches!(
+            session.submit_pin("123456").await,
+            Err(EversendError::Operation(CollectionSessionError::Locked))
+        ));
+
+        otp.assert();
+        collection.assert();
+    }
+
+    #[tokio::test]
+    async fn it_only_counts_rejected_pins_as_attempts() {
+        let eversend = eversend("collection-session-other-errors");
+        let otp = mock_otp("collection-session-other-errors", "132466gdfsfsrey1535", 1);
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions::default());
+        session.request_otp().await.unwrap();
+
+        for (status, message) in [
+            (400_u16, "Insufficient balance"),
+            (409, "Duplicate transaction reference"),
+            (429, "Too many invalid OTP attempts, try again later"),
+        ] {
+            let collection = mock("POST", "/collection-session-other-errors/collections/momo")
+                .with_status(status.into())
+                .with_body(json!({ "code": status, "message": message, "success": false }).to_string())
+                .expect(1)
+                .create();
+
+            let result = session.submit_pin("123456").await;
+
+            assert_eq!(
+                result.err().and_then(|err| match err {
+                    EversendError::Operation(err) => Some(err),
+                    _ => None,
+                }),
+                Some(CollectionSessionError::Rejected { status, message: message.to_string() }),
+                "{} was not surfaced as is", status
+            );
+            assert_eq!(session.attempts_left(), 3);
+
+            collection.assert();
+        }
+
+        let collection = mock("POST", "/collection-session-other-errors/collections/momo")
+            .with_status(400)
+            .with_body(json!({ "code": 400, "message": "OTP has expired", "success": false }).to_string())
+            .expect(1)
+            .create();
+
+        assert!(matches!(
+            session.submit_pin("123456").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpRejected(_)))
+        ));
+        assert_eq!(session.attempts_left(), 2);
+
+        otp.assert();
+        collection.assert();
+    }
+
+    #[tokio::test]
+    async fn it_classifies_rejected_pins_with_the_given_check() {
+        let eversend = eversend("collection-session-classifier");
+        let otp = mock_otp("collection-session-classifier", "132466gdfsfsrey1535", 1);
+
+        let collection = mock("POST", "/collection-session-classifier/collections/momo")
+            .with_status(422)
+            .with_body(json!({ "code": 422, "message": "Verification code mismatch", "success": false }).to_string())
+            .expect(2)
+            .create();
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions::default());
+        session.request_otp().await.unwrap();
+
+        // Not worded like a rejected PIN, so taken for another error by default.
+        assert!(matches!(
+            session.submit_pin("000000").await,
+            Err(EversendError::Operation(CollectionSessionError::Rejected { status: 422, .. }))
+        ));
+        assert_eq!(session.attempts_left(), 3);
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions {
+            is_otp_rejection: |status, message| status == 422 && message.contains("code mismatch"),
+            ..CollectionSessionOptions::default()
+        });
+        session.state = CollectionSessionState::AwaitingPin {
+            pin_id: String::from("132466gdfsfsrey1535"),
+            expires_at: SystemTime::now() + Duration::from_secs(60),
+        };
+
+        assert!(matches!(
+            session.submit_pin("000000").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpRejected(_)))
+        ));
+        assert_eq!(session.attempts_left(), 2);
+
+        otp.assert();
+        collection.assert();
+    }
+
+    #[tokio::test]
+    async fn it_refuses_expired_pins() {
+        let eversend = eversend("collection-session-expired");
+        let _otp = mock_otp("collection-session-expired", "132466gdfsfsrey1535", 1);
+
+        let collection = mock("POST", "/collection-session-expired/collections/momo")
+            .expect(0)
+            .create();
+
+        let mut session = CollectionSession::new(&eversend, params(), CollectionSessionOptions {
+            otp_lifetime: Duration::ZERO,
+            ..CollectionSessionOptions::default()
+        });
+
+        session.request_otp().await.unwrap();
+
+        assert!(matches!(
+            session.submit_pin("123456").await,
+            Err(EversendError::Operation(CollectionSessionError::OtpExpired))
+        ));
+        assert_eq!(session.attempts_left(), 3);
+
+        collection.assert();
+    }
+}
@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{collections::{CollectionFees, Collections}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CollectionMethod {
     #[serde(rename = "momo")]
     MOMO,
@@ -14,6 +15,7 @@ pub enum CollectionMethod {
 }
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetCollectionFeesParams {
     pub amount: u32,
 
@@ -90,7 +92,7 @@ impl<'a> GetCollectionFees for Collections<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CollectionFees>>()
+            .decode::<ApiResponseBody<CollectionFees>, _>()
             .await?;
 
         Ok(result.data)
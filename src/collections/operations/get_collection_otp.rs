@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{collections::Collections, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetCollectionOtpParams {
     /// Phone number in international format
     #[serde(rename = "phone")]
@@ -22,9 +23,14 @@ impl From<GetCollectionOtpError> for EversendError<GetCollectionOtpError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetCollectionOtpResponse {
     #[serde(rename = "pinId")]
     pub pin_id: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Get Collection OTP](https://eversend.readme.io/reference/get-collection-otp)
@@ -86,7 +92,7 @@ impl<'a> GetCollectionOtp for Collections<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<GetCollectionOtpResponse>>()
+            .decode::<ApiResponseBody<GetCollectionOtpResponse>, _>()
             .await?;
 
         Ok(result.data.pin_id)
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{collections::{Collections, MobileMoneyCollection}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{collections::{Collections, MobileMoneyCollection}, ApiResponseBody, EversendError, EversendResult, ResponseExtension, TransactionRef};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Otp {
     pub pin: String,
 
@@ -13,6 +14,7 @@ pub struct Otp {
 }
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetMobileMoneyCollectionParams {
     /// Amount as a number
     pub amount: u32,
@@ -39,12 +41,21 @@ pub struct GetMobileMoneyCollectionParams {
 
     /// Optional unique alphanumeric string set by the client
     #[serde(rename = "transactionRef")]
-    pub transaction_ref: Option<String>,
+    pub transaction_ref: Option<TransactionRef>,
 }
 
 /// An error returned from [`GetMobileMoneyCollection`].
 #[derive(Debug, Error)]
-pub enum GetMobileMoneyCollectionError {}
+pub enum GetMobileMoneyCollectionError {
+    /// A collection submitted with an [`Otp`] was refused with a client error, with the status and
+    /// message of the API. The API does not say with a code whether the PIN was at fault, see
+    /// [`is_otp_rejection`].
+    #[error("the collection was rejected with status {status}: {message}")]
+    Rejected {
+        status: u16,
+        message: String,
+    },
+}
 
 impl From<GetMobileMoneyCollectionError> for EversendError<GetMobileMoneyCollectionError> {
     fn from(err: GetMobileMoneyCollectionError) -> Self {
@@ -69,7 +80,7 @@ pub trait GetMobileMoneyCollection {
     /// ```
     /// # use eversend_rust_sdk::EversendResult;
     /// # use eversend_rust_sdk::collections::*;
-    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
     ///
     /// # async fn run() -> EversendResult<(), GetMobileMoneyCollectionError> {
     ///     let eversend = Eversend::new(
@@ -85,7 +96,7 @@ pub trait GetMobileMoneyCollection {
     ///                 country: String::from("UG"),
     ///                 currency: String::from("UGX"),
     ///                 phone_number: String::from("+256712345678"),
-    ///                 transaction_ref: Some(String::from("ADR234526534")),
+    ///                 transaction_ref: Some(TransactionRef::generate()),
     ///                 redirect_url: Some(String::from("https://eversend.co")),
     ///                 customer: None,
     ///                 otp: None,
@@ -111,7 +122,7 @@ impl<'a> GetMobileMoneyCollection for Collections<'a> {
     ) -> EversendResult<MobileMoneyCollection, GetMobileMoneyCollectionError> {
         let url = format!("{}/collections/momo", self.eversend.base_url());
 
-        let result = self
+        let response = self
             .eversend
             .client()
             .post(url)
@@ -119,14 +130,68 @@ impl<'a> GetMobileMoneyCollection for Collections<'a> {
             .bearer_auth(self.eversend.api_token().unwrap())
             .send()
             .await?
-            .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<MobileMoneyCollection>>()
+            .handle_unauthorized_error()?;
+
+        if let Err(err) = response.error_for_status_ref().map(|_| ()) {
+            let status = response.status();
+
+            if params.otp.is_some() && status.is_client_error() {
+                let body = response.bytes().await.unwrap_or_default();
+
+                if let Some(message) = error_message(&body) {
+                    return Err(GetMobileMoneyCollectionError::Rejected {
+                        status: status.as_u16(),
+                        message,
+                    }.into());
+                }
+            }
+
+            return Err(err.into());
+        }
+
+        let result = response
+            .decode::<ApiResponseBody<MobileMoneyCollection>, _>()
             .await?;
 
         Ok(result.data)
     }
 }
 
+/// The message of an error body.
+fn error_message(body: &[u8]) -> Option<String> {
+    #[derive(Deserialize)]
+    struct ErrorBody {
+        message: String,
+    }
+
+    serde_json::from_slice::<ErrorBody>(body).ok().map(|body| body.message)
+}
+
+/// Returns `true` if a [`GetMobileMoneyCollectionError::Rejected`] collection was refused because
+/// the PIN was invalid or expired, e.g. `Invalid OTP` or `OTP has expired`.
+///
+/// This is a best-effort guess from the wording of the message, as the API has no error code for
+/// it: it looks for a word like `OTP` or `PIN` and one like `invalid` or `expired` anywhere in the
+/// message. Conflicts and rate limits are never taken for a rejected PIN, whatever their message
+/// says. Pass your own check as
+/// [`CollectionSessionOptions::is_otp_rejection`](crate::collections::CollectionSessionOptions)
+/// if the API words it otherwise for your account.
+pub fn is_otp_rejection(status: u16, message: &str) -> bool {
+    if matches!(status, 409 | 429) {
+        return false;
+    }
+
+    let lowercase = message.to_lowercase();
+    let words: Vec<_> = lowercase.split(|c: char| !c.is_alphanumeric()).collect();
+
+    let is_otp = words.iter().any(|word| matches!(*word, "otp" | "pin"));
+    let is_rejected = words
+        .iter()
+        .any(|word| matches!(*word, "invalid" | "incorrect" | "wrong" | "expired"));
+
+    is_otp && is_rejected
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
@@ -179,7 +244,7 @@ mod tests {
                     country: String::from("UG"),
                     currency: String::from("UGX"),
                     phone_number: String::from("+256712345678"),
-                    transaction_ref: Some(String::from("ADR234526534")),
+                    transaction_ref: Some("ADR234526534".parse().unwrap()),
                     redirect_url: Some(String::from("https://eversend.co")),
                     customer: None,
                     otp: None,
@@ -193,4 +258,13 @@ mod tests {
         mock.assert();
 
     }
+
+    #[test]
+    fn it_guesses_rejected_pins_from_the_message() {
+        assert!(is_otp_rejection(400, "Invalid OTP"));
+        assert!(is_otp_rejection(400, "The PIN has expired."));
+        assert!(!is_otp_rejection(400, "Insufficient balance"));
+        assert!(!is_otp_rejection(400, "Invalid phone number"));
+        assert!(!is_otp_rejection(429, "Too many invalid OTP attempts, try again later"));
+    }
 }
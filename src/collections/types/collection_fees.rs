@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CollectionFees {
     pub amount: String,
     pub amount_available_to_load: String,
@@ -12,4 +13,8 @@ pub struct CollectionFees {
     pub new_balance: String,
     pub payment_method: String,
     pub total_to_pay: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MobileMoneyCollection {
     pub amount: String,
 
@@ -30,4 +31,8 @@ pub struct MobileMoneyCollection {
 
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
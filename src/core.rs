@@ -1,9 +1,21 @@
+mod api_client;
 mod constants;
+mod decode_error;
 mod error;
+mod jwt;
+mod open_enum_schema;
+mod rate_limiter;
 mod response;
 mod types;
+mod write_atomically;
 
+pub(crate) use api_client::*;
 pub use constants::*;
+pub use decode_error::*;
 pub use error::*;
+pub(crate) use jwt::*;
+pub(crate) use open_enum_schema::*;
+pub(crate) use rate_limiter::*;
 pub use response::*;
 pub use types::*;
+pub(crate) use write_atomically::*;
@@ -0,0 +1,83 @@
+use std::{fmt::Display, sync::Arc};
+
+use reqwest::{IntoUrl, RequestBuilder, Response};
+use serde::Serialize;
+
+use crate::core::RateLimiter;
+
+/// The HTTP client of an [`Eversend`](crate::Eversend) client, shared by its clones.
+///
+/// Waits for the request rate limit, if any, before sending each request.
+#[derive(Clone)]
+pub(crate) struct ApiClient {
+    client: reqwest::Client,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl ApiClient {
+    pub(crate) fn new(client: reqwest::Client, max_requests_per_second: Option<u32>) -> Self {
+        Self {
+            client,
+            limiter: max_requests_per_second.and_then(RateLimiter::new).map(Arc::new),
+        }
+    }
+
+    pub(crate) fn get(&self, url: impl IntoUrl) -> ApiRequest {
+        self.request(self.client.get(url))
+    }
+
+    pub(crate) fn post(&self, url: impl IntoUrl) -> ApiRequest {
+        self.request(self.client.post(url))
+    }
+
+    pub(crate) fn put(&self, url: impl IntoUrl) -> ApiRequest {
+        self.request(self.client.put(url))
+    }
+
+    pub(crate) fn delete(&self, url: impl IntoUrl) -> ApiRequest {
+        self.request(self.client.delete(url))
+    }
+
+    fn request(&self, builder: RequestBuilder) -> ApiRequest {
+        ApiRequest {
+            builder,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// A request being built with an [`ApiClient`].
+pub(crate) struct ApiRequest {
+    builder: RequestBuilder,
+    limiter: Option<Arc<RateLimiter>>,
+}
+
+impl ApiRequest {
+    pub(crate) fn bearer_auth(self, token: impl Display) -> Self {
+        self.map(|builder| builder.bearer_auth(token))
+    }
+
+    pub(crate) fn header(self, key: &'static str, value: String) -> Self {
+        self.map(|builder| builder.header(key, value))
+    }
+
+    pub(crate) fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+        self.map(|builder| builder.json(json))
+    }
+
+    /// Sends the request once the rate limit allows it.
+    pub(crate) async fn send(self) -> reqwest::Result<Response> {
+        if let Some(limiter) = &self.limiter {
+            limiter.wait().await;
+        }
+
+        self.builder.send().await
+    }
+
+    fn map(self, op: impl FnOnce(RequestBuilder) -> RequestBuilder) -> Self {
+        Self {
+            builder: op(self.builder),
+            limiter: self.limiter,
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use serde_json::Value;
+use thiserror::Error;
+
+/// Keys whose values are replaced in [`DecodeError::body`], matched ignoring case and underscores.
+const SENSITIVE_KEYS: &[&str] = &[
+    "account",
+    "address",
+    "birth",
+    "bvn",
+    "email",
+    "name",
+    "otp",
+    "phone",
+    "pin",
+    "secret",
+    "token",
+];
+
+const REDACTED: &str = "[redacted]";
+
+/// A response body that did not match the expected model.
+#[derive(Debug, Error)]
+#[error("could not decode response at `{path}`: {message}")]
+pub struct DecodeError {
+    /// Where decoding failed, e.g. `data.transactions[3].meta.source.amount`.
+    pub path: String,
+
+    /// The type that was expected there, e.g. `a string`, if the value had another type.
+    pub expected: Option<String>,
+
+    /// What went wrong, without the offending value when it may be personal data.
+    pub message: String,
+
+    /// The body, with personal data redacted and truncated to [`DecodeError::MAX_BODY_LEN`]
+    /// characters.
+    pub body: String,
+}
+
+impl DecodeError {
+    /// The longest body kept, in characters.
+    pub const MAX_BODY_LEN: usize = 2048;
+
+    pub(crate) fn new(err: serde_path_to_error::Error<serde_json::Error>, body: &[u8]) -> Self {
+        let path = err.path().to_string();
+        let inner = err.into_inner();
+
+        let mut message = inner.to_string();
+        let position = format!(" at line {} column {}", inner.line(), inner.column());
+        if let Some(stripped) = message.strip_suffix(&position) {
+            message = stripped.to_string();
+        }
+
+        let expected = message
+            .split_once(", expected ")
+            .map(|(_, expected)| expected.to_string());
+
+        // Messages quote the offending value, e.g. `invalid type: string "Jane"`, which is
+        // personal data when any field on the way to it is, e.g. `phoneNumber.number`.
+        if path.split(['.', '[']).any(is_sensitive) {
+            message = match &expected {
+                Some(expected) => format!("invalid value, expected {}", expected),
+                None => String::from("invalid value"),
+            };
+        }
+
+        Self {
+            path,
+            expected,
+            message,
+            body: truncate(redact(body)),
+        }
+    }
+}
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.replace('_', "").to_lowercase();
+
+    SENSITIVE_KEYS.iter().any(|sensitive| key.contains(sensitive))
+}
+
+/// Redacts the values of sensitive keys of a JSON body, or long runs of digits of any other body.
+fn redact(body: &[u8]) -> String {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value.to_string()
+        },
+        Err(_) => {
+            let text = String::from_utf8_lossy(body);
+            let mut redacted = String::with_capacity(text.len());
+            let mut digits = String::new();
+
+            for c in text.chars().chain(std::iter::once('\0')) {
+                if c.is_ascii_digit() {
+                    digits.push(c);
+                    continue;
+                }
+
+                // Phone and account numbers are at least 7 digits long.
+                redacted.push_str(if digits.len() >= 7 { REDACTED } else { &digits });
+                digits.clear();
+
+                if c != '\0' {
+                    redacted.push(c);
+                }
+            }
+
+            redacted
+        },
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive(key) && !value.is_null() {
+                    *value = Value::String(String::from(REDACTED));
+                } else {
+                    redact_value(value);
+                }
+            }
+        },
+        Value::Array(values) => values.iter_mut().for_each(redact_value),
+        _ => {},
+    }
+}
+
+fn truncate(body: String) -> String {
+    match body.char_indices().nth(DecodeError::MAX_BODY_LEN) {
+        Some((end, _)) => format!("{}…", &body[..end]),
+        None => body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Recipient {
+        #[serde(rename = "firstName")]
+        first_name: u32,
+        amount: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Merchant {
+        #[serde(rename = "phoneNumber")]
+        phone_number: PhoneNumber,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct PhoneNumber {
+        number: u64,
+    }
+
+    fn decode(body: &str) -> DecodeError {
+        decode_as::<Vec<Recipient>>(body)
+    }
+
+    fn decode_as<T: for<'de> Deserialize<'de> + std::fmt::Debug>(body: &str) -> DecodeError {
+        let deserializer = &mut serde_json::Deserializer::from_str(body);
+        let err = serde_path_to_error::deserialize::<_, T>(deserializer).unwrap_err();
+
+        DecodeError::new(err, body.as_bytes())
+    }
+
+    #[test]
+    fn it_reports_the_path_and_expected_type() {
+        let err = decode(&json!([
+            { "firstName": 1, "amount": "10" },
+            { "firstName": 2, "amount": 10 },
+        ]).to_string());
+
+        assert_eq!(err.path, "[1].amount");
+        assert_eq!(err.expected.as_deref(), Some("a string"));
+        assert_eq!(err.message, "invalid type: integer `10`, expected a string");
+        assert_eq!(err.body, r#"[{"amount":"10","firstName":"[redacted]"},{"amount":10,"firstName":"[redacted]"}]"#);
+    }
+
+    #[test]
+    fn it_keeps_personal_data_out_of_the_message() {
+        let err = decode(&json!([{ "firstName": "Jane", "amount": "10" }]).to_string());
+
+        assert_eq!(err.path, "[0].firstName");
+        assert_eq!(err.message, "invalid value, expected u32");
+        assert!(!err.to_string().contains("Jane"));
+    }
+
+    #[test]
+    fn it_keeps_personal_data_nested_under_a_sensitive_field_out_of_the_message() {
+        let err = decode_as::<Merchant>(&json!({ "phoneNumber": { "number": "+256712345678" } }).to_string());
+
+        assert_eq!(err.path, "phoneNumber.number");
+        assert_eq!(err.message, "invalid value, expected u64");
+        assert!(!err.to_string().contains("712345678"));
+        assert!(!err.body.contains("712345678"));
+    }
+
+    #[test]
+    fn it_redacts_and_truncates_other_bodies() {
+        assert_eq!(redact(b"call +256712345678 ref 42"), "call +[redacted] ref 42");
+        assert_eq!(truncate("a".repeat(DecodeError::MAX_BODY_LEN + 1)).chars().count(), DecodeError::MAX_BODY_LEN + 1);
+        assert_eq!(truncate(String::from("short")), "short");
+    }
+}
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::DecodeError;
+
 /// An Eversend SDK error.
 #[derive(Debug, Error)]
 pub enum EversendError<E> {
@@ -17,7 +19,26 @@ pub enum EversendError<E> {
     /// An unhandled error occurred with the API request.
     #[error("request error")]
     RequestError(#[from] reqwest::Error),
+
+    /// A response was received, but its body did not match the expected model.
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
 }
 
 /// A Eversend SDK result.
 pub type EversendResult<T, E> = Result<T, EversendError<E>>;
+
+impl<E> EversendError<E> {
+    /// Maps the operational error of an [`EversendError`], leaving the other variants untouched.
+    ///
+    /// Useful when composing operations whose error types differ.
+    pub fn map_operation<F>(self, op: impl FnOnce(E) -> F) -> EversendError<F> {
+        match self {
+            Self::ApiTokenMissing => EversendError::ApiTokenMissing,
+            Self::Unauthorized => EversendError::Unauthorized,
+            Self::Operation(err) => EversendError::Operation(op(err)),
+            Self::RequestError(err) => EversendError::RequestError(err),
+            Self::Decode(err) => EversendError::Decode(err),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::Deserialize;
+
+/// Reads the expiry of a JWT from its `exp` claim, without verifying its signature.
+pub(crate) fn jwt_expiry(token: &str) -> Result<SystemTime, String> {
+    #[derive(Deserialize)]
+    struct Claims {
+        exp: u64,
+    }
+
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| String::from("token is not a JWT"))?;
+
+    let json = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|err| err.to_string())?;
+
+    let claims = serde_json::from_slice::<Claims>(&json).map_err(|err| err.to_string())?;
+
+    UNIX_EPOCH
+        .checked_add(Duration::from_secs(claims.exp))
+        .ok_or_else(|| format!("expiry {} is out of range", claims.exp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(exp: u64) -> String {
+        let payload = URL_SAFE_NO_PAD.encode(format!(r#"{{"exp":{}}}"#, exp));
+
+        format!("eyJhbGciOiJIUzI1NiJ9.{}.signature", payload)
+    }
+
+    #[test]
+    fn it_reads_the_expiry_claim() {
+        assert_eq!(jwt_expiry(&token(1700000000)), Ok(UNIX_EPOCH + Duration::from_secs(1700000000)));
+    }
+
+    #[test]
+    fn it_rejects_expiries_out_of_range() {
+        assert!(jwt_expiry(&token(u64::MAX)).is_err());
+        assert!(jwt_expiry("some-quotation-token").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+/// Implements [`JsonSchema`](schemars::JsonSchema) for an enum with an untagged `Unknown(String)`
+/// variant, given the values of its other variants as sent by the API.
+///
+/// The derived schema describes `Unknown` as an object, while it is sent as a plain string. The
+/// schema implemented here accepts any of the known values, or any other string.
+macro_rules! open_enum_schema {
+    ($type:ty, [$($value:literal),+ $(,)?]) => {
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $type {
+            fn schema_name() -> String {
+                String::from(stringify!($type))
+            }
+
+            fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                $crate::core::open_enum(&[$($value),+])
+            }
+        }
+    };
+}
+
+pub(crate) use open_enum_schema;
+
+/// A schema matching one of `known` or any other string.
+///
+/// Uses `anyOf` rather than `oneOf`, as the known values are strings too.
+#[cfg(feature = "schemars")]
+pub(crate) fn open_enum(known: &[&str]) -> schemars::schema::Schema {
+    use schemars::schema::{InstanceType, Metadata, SchemaObject, SubschemaValidation};
+
+    let known = SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(known.iter().map(|value| serde_json::Value::from(*value)).collect()),
+        ..Default::default()
+    };
+
+    let other = SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some(String::from("A value not known to this version of the SDK.")),
+            ..Default::default()
+        })),
+        instance_type: Some(InstanceType::String.into()),
+        ..Default::default()
+    };
+
+    SchemaObject {
+        subschemas: Some(Box::new(SubschemaValidation {
+            any_of: Some(vec![known.into(), other.into()]),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[cfg(all(test, feature = "schemars"))]
+mod tests {
+    use std::fmt::Debug;
+
+    use serde::de::DeserializeOwned;
+
+    use crate::{
+        crypto::{Chain, CryptoTransactionStatus, CryptoTransactionSubStatus},
+        payouts::CountryPaymentType,
+        transactions::{TransactionCurrencyOption, TransactionStatusOption, TransactionTypeOption},
+    };
+
+    /// Checks that every known value in the schema of `T` is a known value to serde as well.
+    fn assert_known_values<T>(is_unknown: fn(&T) -> bool)
+    where
+        T: schemars::JsonSchema + DeserializeOwned + Debug,
+    {
+        let schema = serde_json::to_value(schemars::schema_for!(T)).unwrap();
+
+        assert_eq!(schema["anyOf"][1]["type"], "string");
+
+        let known = schema["anyOf"][0]["enum"].as_array().unwrap();
+
+        assert!(!known.is_empty());
+
+        for value in known {
+            let parsed = serde_json::from_value::<T>(value.clone()).unwrap();
+
+            assert!(!is_unknown(&parsed), "{} is not a known value", value);
+        }
+    }
+
+    #[test]
+    fn it_describes_open_enums_as_known_values_or_any_string() {
+        assert_known_values::<TransactionCurrencyOption>(|value| matches!(value, TransactionCurrencyOption::Unknown(_)));
+        assert_known_values::<TransactionTypeOption>(|value| matches!(value, TransactionTypeOption::Unknown(_)));
+        assert_known_values::<TransactionStatusOption>(|value| matches!(value, TransactionStatusOption::Unknown(_)));
+        assert_known_values::<CountryPaymentType>(|value| matches!(value, CountryPaymentType::Unknown(_)));
+        assert_known_values::<Chain>(|value| matches!(value, Chain::Unknown(_)));
+        assert_known_values::<CryptoTransactionStatus>(|value| matches!(value, CryptoTransactionStatus::Unknown(_)));
+        assert_known_values::<CryptoTransactionSubStatus>(|value| matches!(value, CryptoTransactionSubStatus::Unknown(_)));
+    }
+}
@@ -1,9 +1,12 @@
+use async_trait::async_trait;
 use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
 
-use crate::EversendError;
+use crate::{DecodeError, EversendError};
 
 use super::EversendResult;
 
+#[async_trait]
 pub trait ResponseExtension
 where
     Self: Sized,
@@ -18,8 +21,13 @@ where
 
     /// Handles an unauthorized or generic error from the Eversend API.
     fn handle_unauthorized_or_generic_error<E>(self) -> EversendResult<Self, E>;
+
+    /// Deserializes a JSON body, reporting where it did not match `T` as an
+    /// [`EversendError::Decode`] response.
+    async fn decode<T: DeserializeOwned, E>(self) -> EversendResult<T, E>;
 }
 
+#[async_trait]
 impl ResponseExtension for Response {
     fn handle_unauthorized_error<E>(self) -> EversendResult<Self, E> {
         if self.status() == StatusCode::UNAUTHORIZED {
@@ -39,4 +47,12 @@ impl ResponseExtension for Response {
     fn handle_unauthorized_or_generic_error<E>(self) -> EversendResult<Self, E> {
         self.handle_unauthorized_error()?.handle_generic_error()
     }
+
+    async fn decode<T: DeserializeOwned, E>(self) -> EversendResult<T, E> {
+        let body = self.bytes().await?;
+        let deserializer = &mut serde_json::Deserializer::from_slice(&body);
+
+        serde_path_to_error::deserialize(deserializer)
+            .map_err(|err| EversendError::Decode(DecodeError::new(err, &body)))
+    }
 }
@@ -2,8 +2,12 @@ mod api_token;
 mod api_response_body;
 mod client_id;
 mod client_secret;
+mod quote_handle;
+mod transaction_ref;
 
 pub use client_id::*;
 pub use client_secret::*;
 pub use api_token::*;
 pub use api_response_body::*;
+pub use quote_handle::*;
+pub use transaction_ref::*;
@@ -1,11 +1,21 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::SystemTime};
 
 use serde::Deserialize;
 
+use crate::core::jwt_expiry;
+
 /// An API token to authenticate with the Eversend API.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ApiToken(String);
 
+impl ApiToken {
+    /// When the token expires, if it is a JWT with an `exp` claim.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        jwt_expiry(&self.0).ok()
+    }
+}
+
 impl Display for ApiToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
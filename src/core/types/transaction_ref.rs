@@ -0,0 +1,272 @@
+use std::{fmt::Display, str::FromStr, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ulid::Ulid;
+
+const ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const ULID_LEN: usize = 26;
+
+/// An error returned when a [`TransactionRef`] is not acceptable to Eversend.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TransactionRefError {
+    /// The reference is empty.
+    #[error("transaction reference is empty")]
+    Empty,
+
+    /// The reference is longer than [`TransactionRef::MAX_LEN`].
+    #[error("transaction reference is {0} characters long, at most {max} are allowed", max = TransactionRef::MAX_LEN)]
+    TooLong(usize),
+
+    /// The reference contains a character that is not an ASCII letter or digit.
+    #[error("transaction reference contains invalid character {0:?}")]
+    InvalidCharacter(char),
+}
+
+/// A unique alphanumeric reference set by the client on payouts and collections.
+///
+/// References can either be parsed from an existing string or generated, in which case they
+/// consist of an optional namespace prefix, a [ULID](https://github.com/ulid/spec) and a check
+/// character, e.g. `PAYROLL01J9Z3K4W5X6Y7Z8A9B0C1D2EK`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct TransactionRef(String);
+
+impl TransactionRef {
+    /// The longest reference accepted.
+    pub const MAX_LEN: usize = 64;
+
+    /// Generates a new reference without a namespace prefix.
+    pub fn generate() -> Self {
+        TransactionRefGenerator::default().generate()
+    }
+
+    /// The reference as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The namespace prefix of a generated reference.
+    ///
+    /// Returns `None` for references that were not generated, and for generated ones without a prefix.
+    pub fn prefix(&self) -> Option<&str> {
+        self.generated_parts()
+            .map(|(prefix, _)| prefix)
+            .filter(|prefix| !prefix.is_empty())
+    }
+
+    /// The moment a generated reference was created.
+    ///
+    /// Returns `None` if the reference was not generated, i.e. its check character does not match.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.generated_parts()
+            .map(|(_, ulid)| UNIX_EPOCH + Duration::from_millis(ulid.timestamp_ms()))
+    }
+
+    fn generated_parts(&self) -> Option<(&str, Ulid)> {
+        let len = self.0.len();
+
+        if len <= ULID_LEN {
+            return None;
+        }
+
+        let (body, check) = self.0.split_at(len - 1);
+
+        if check.as_bytes()[0] != check_character(body) {
+            return None;
+        }
+
+        let (prefix, ulid) = body.split_at(body.len() - ULID_LEN);
+
+        Ulid::from_string(ulid).ok().map(|ulid| (prefix, ulid))
+    }
+}
+
+impl From<TransactionRef> for String {
+    fn from(value: TransactionRef) -> Self {
+        value.0
+    }
+}
+
+impl Display for TransactionRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for TransactionRef {
+    type Err = TransactionRefError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        validate(value)?;
+
+        Ok(Self(value.to_string()))
+    }
+}
+
+impl TryFrom<String> for TransactionRef {
+    type Error = TransactionRefError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        validate(&value)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl TryFrom<&str> for TransactionRef {
+    type Error = TransactionRefError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for TransactionRef {
+    fn schema_name() -> String {
+        String::from("TransactionRef")
+    }
+
+    /// A string of 1 to [`TransactionRef::MAX_LEN`] ASCII letters and digits.
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            string: Some(Box::new(schemars::schema::StringValidation {
+                min_length: Some(1),
+                max_length: Some(Self::MAX_LEN as u32),
+                pattern: Some(String::from("^[A-Za-z0-9]+$")),
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// Generates [`TransactionRef`]s within a namespace.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionRefGenerator {
+    prefix: String,
+}
+
+impl TransactionRefGenerator {
+    /// Returns a new [`TransactionRefGenerator`] prefixing every reference with `prefix`.
+    ///
+    /// The prefix is upper-cased and must be alphanumeric.
+    pub fn new(prefix: &str) -> Result<Self, TransactionRefError> {
+        let prefix = prefix.to_ascii_uppercase();
+
+        if let Some(c) = prefix.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(TransactionRefError::InvalidCharacter(c));
+        }
+
+        if prefix.len() + ULID_LEN + 1 > TransactionRef::MAX_LEN {
+            return Err(TransactionRefError::TooLong(prefix.len() + ULID_LEN + 1));
+        }
+
+        Ok(Self { prefix })
+    }
+
+    /// Generates a new reference.
+    pub fn generate(&self) -> TransactionRef {
+        let mut value = format!("{}{}", self.prefix, Ulid::new());
+        value.push(check_character(&value) as char);
+
+        TransactionRef(value)
+    }
+}
+
+fn validate(value: &str) -> Result<(), TransactionRefError> {
+    if value.is_empty() {
+        return Err(TransactionRefError::Empty);
+    }
+
+    if let Some(c) = value.chars().find(|c| !c.is_ascii_alphanumeric()) {
+        return Err(TransactionRefError::InvalidCharacter(c));
+    }
+
+    if value.len() > TransactionRef::MAX_LEN {
+        return Err(TransactionRefError::TooLong(value.len()));
+    }
+
+    Ok(())
+}
+
+/// Computes a Luhn mod 36 check character, which catches any single mistyped character and most
+/// swaps of adjacent characters.
+fn check_character(value: &str) -> u8 {
+    let n = ALPHABET.len() as u32;
+
+    let sum = value
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let code = ALPHABET
+                .iter()
+                .position(|a| *a == b.to_ascii_uppercase())
+                .unwrap_or(0) as u32;
+            let addend = if i % 2 == 0 { code * 2 } else { code };
+
+            addend / n + addend % n
+        })
+        .sum::<u32>();
+
+    ALPHABET[((n - sum % n) % n) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_generates_valid_unique_references() {
+        let first = TransactionRef::generate();
+        let second = TransactionRef::generate();
+
+        assert_ne!(first, second);
+        assert_eq!(first.as_str().len(), ULID_LEN + 1);
+        assert_eq!(first.as_str().parse::<TransactionRef>(), Ok(first.clone()));
+        assert_eq!(first.prefix(), None);
+    }
+
+    #[test]
+    fn it_extracts_the_prefix_and_timestamp_of_generated_references() {
+        let before = SystemTime::now() - Duration::from_millis(1);
+        let reference = TransactionRefGenerator::new("payroll").unwrap().generate();
+
+        assert!(reference.as_str().starts_with("PAYROLL"));
+        assert_eq!(reference.prefix(), Some("PAYROLL"));
+
+        let timestamp = reference.timestamp().unwrap();
+        assert!(timestamp >= before && timestamp <= SystemTime::now());
+    }
+
+    #[test]
+    fn it_detects_mistyped_generated_references() {
+        let reference = TransactionRefGenerator::new("ES").unwrap().generate();
+
+        let mut mistyped = reference.as_str().to_string().into_bytes();
+        mistyped[5] = if mistyped[5] == b'0' { b'1' } else { b'0' };
+        let mistyped = TransactionRef::try_from(String::from_utf8(mistyped).unwrap()).unwrap();
+
+        assert_eq!(mistyped.timestamp(), None);
+        assert_eq!(mistyped.prefix(), None);
+    }
+
+    #[test]
+    fn it_accepts_existing_alphanumeric_references() {
+        let reference = TransactionRef::try_from("ADR234526534").unwrap();
+
+        assert_eq!(reference.to_string(), "ADR234526534");
+        assert_eq!(reference.timestamp(), None);
+    }
+
+    #[test]
+    fn it_rejects_invalid_references() {
+        assert_eq!(TransactionRef::try_from(""), Err(TransactionRefError::Empty));
+        assert_eq!(TransactionRef::try_from("some-reference"), Err(TransactionRefError::InvalidCharacter('-')));
+        assert_eq!(TransactionRef::try_from("A".repeat(65).as_str()), Err(TransactionRefError::TooLong(65)));
+        assert!(TransactionRefGenerator::new("pay roll").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::{ffi::OsString, fs::{self, File}, io::{self, Write}, path::{Path, PathBuf}};
+
+/// Replaces the contents of `path` with `bytes`, leaving either the old or the new contents behind
+/// if the process crashes or the machine loses power halfway.
+///
+/// The bytes are written to `<path>.tmp` and flushed to disk before being renamed over `path`, and
+/// the directory holding it is flushed afterwards where the platform allows it.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let temporary = temporary_path(path);
+
+    let mut file = File::create(&temporary)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&temporary, path)?;
+
+    // Makes the rename itself durable. Directories cannot be opened as files on every platform.
+    if let Some(directory) = path.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+        if let Ok(directory) = File::open(directory) {
+            let _ = directory.sync_all();
+        }
+    }
+
+    Ok(())
+}
+
+/// `path` with `.tmp` appended, so files differing only by extension do not share one.
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut temporary = OsString::from(path.as_os_str());
+    temporary.push(".tmp");
+
+    PathBuf::from(temporary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_replaces_the_file_without_leaving_the_temporary_one() {
+        let path = std::env::temp_dir().join(format!("eversend-write-atomically-{}.json", std::process::id()));
+
+        write_atomically(&path, b"first").unwrap();
+        write_atomically(&path, b"second").unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        assert!(!temporary_path(&path).exists());
+        assert_eq!(temporary_path(&path).extension().unwrap(), "tmp");
+
+        let _ = fs::remove_file(&path);
+    }
+}
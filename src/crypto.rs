@@ -1,6 +1,10 @@
+pub mod address;
+
+mod deposit_addresses;
 mod operations;
 mod types;
 
+pub(crate) use deposit_addresses::*;
 pub use operations::*;
 pub use types::*;
 
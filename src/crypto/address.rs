@@ -0,0 +1,230 @@
+//! Validation of crypto addresses by chain.
+//!
+//! Addresses of EVM chains (Ethereum, BNB Smart Chain, Polygon) are checked against their
+//! [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksum when written in mixed case, TRON
+//! addresses against their base58check checksum, and Solana addresses for being 32-byte base58
+//! public keys. Valid addresses are normalized, so they can be displayed and compared as is.
+//!
+//! # Examples
+//! ```
+//! use eversend_rust_sdk::crypto::{address, Chain};
+//!
+//! let address = address::validate(&Chain::Ethereum, " 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed ").unwrap();
+//! assert_eq!(address.as_str(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+//!
+//! assert!(address::validate(&Chain::Tron, "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzz").is_err());
+//! ```
+
+use std::fmt::Display;
+
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+use crate::crypto::Chain;
+
+/// The version byte TRON addresses start with.
+const TRON_PREFIX: u8 = 0x41;
+
+/// An error returned when an address is not valid on a chain.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AddressError {
+    /// The address is empty.
+    #[error("address is empty")]
+    Empty,
+
+    /// The address is not written the way addresses of the chain are.
+    #[error("not a valid {0} address")]
+    InvalidFormat(Chain),
+
+    /// The address is well formed, but its checksum does not match, e.g. because of a typo.
+    #[error("{0} address checksum does not match")]
+    InvalidChecksum(Chain),
+
+    /// Addresses of the chain are not known to this version of the SDK.
+    #[error("addresses on {0} cannot be validated")]
+    UnsupportedChain(Chain),
+}
+
+/// An address validated for a chain, in its normalized form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    chain: Chain,
+    value: String,
+}
+
+impl Address {
+    /// The chain the address is valid on.
+    pub fn chain(&self) -> &Chain {
+        &self.chain
+    }
+
+    /// The normalized address.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl From<Address> for String {
+    fn from(value: Address) -> Self {
+        value.value
+    }
+}
+
+/// Validates an address for a chain, ignoring surrounding whitespace.
+///
+/// EVM addresses written in a single case are accepted and returned with their EIP-55 checksum
+/// casing. Mixed-case ones must match it.
+pub fn validate(chain: &Chain, address: &str) -> Result<Address, AddressError> {
+    let address = address.trim();
+
+    if address.is_empty() {
+        return Err(AddressError::Empty);
+    }
+
+    let value = match chain {
+        Chain::BinanceSmartChain | Chain::Ethereum | Chain::Polygon => validate_evm(chain, address)?,
+        Chain::Tron => validate_tron(address)?,
+        Chain::Solana => validate_solana(address)?,
+        Chain::Unknown(_) => return Err(AddressError::UnsupportedChain(chain.clone())),
+    };
+
+    Ok(Address {
+        chain: chain.clone(),
+        value,
+    })
+}
+
+fn validate_evm(chain: &Chain, address: &str) -> Result<String, AddressError> {
+    let hex = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .filter(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| AddressError::InvalidFormat(chain.clone()))?;
+
+    let checksummed = format!("0x{}", eip55(hex));
+
+    let single_case = hex == hex.to_lowercase() || hex == hex.to_uppercase();
+    if !single_case && checksummed[2..] != *hex {
+        return Err(AddressError::InvalidChecksum(chain.clone()));
+    }
+
+    Ok(checksummed)
+}
+
+/// Cases the letters of a hex address by the nibbles of the Keccak-256 hash of its lowercase form.
+fn eip55(hex: &str) -> String {
+    let lowercase = hex.to_lowercase();
+    let hash = Keccak256::digest(lowercase.as_bytes());
+
+    lowercase
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect()
+}
+
+fn validate_tron(address: &str) -> Result<String, AddressError> {
+    let invalid_format = || AddressError::InvalidFormat(Chain::Tron);
+
+    if !address.starts_with('T') || address.len() != 34 {
+        return Err(invalid_format());
+    }
+
+    let payload = bs58::decode(address)
+        .with_check(None)
+        .into_vec()
+        .map_err(|err| match err {
+            bs58::decode::Error::InvalidChecksum { .. } => AddressError::InvalidChecksum(Chain::Tron),
+            _ => invalid_format(),
+        })?;
+
+    if payload.len() != 21 || payload[0] != TRON_PREFIX {
+        return Err(invalid_format());
+    }
+
+    Ok(address.to_string())
+}
+
+fn validate_solana(address: &str) -> Result<String, AddressError> {
+    match bs58::decode(address).into_vec() {
+        Ok(key) if key.len() == 32 => Ok(address.to_string()),
+        _ => Err(AddressError::InvalidFormat(Chain::Solana)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_validates_and_checksums_evm_addresses() {
+        // Test vectors from EIP-55.
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(validate(&Chain::Ethereum, address).unwrap().as_str(), address);
+            assert_eq!(validate(&Chain::BinanceSmartChain, &address.to_lowercase()).unwrap().as_str(), address);
+        }
+
+        assert_eq!(
+            validate(&Chain::Ethereum, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(AddressError::InvalidChecksum(Chain::Ethereum))
+        );
+        assert_eq!(
+            validate(&Chain::Polygon, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+            Err(AddressError::InvalidFormat(Chain::Polygon))
+        );
+        assert_eq!(
+            validate(&Chain::Ethereum, "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzy"),
+            Err(AddressError::InvalidFormat(Chain::Ethereum))
+        );
+    }
+
+    #[test]
+    fn it_validates_tron_addresses() {
+        let address = validate(&Chain::Tron, "\tTR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t\n").unwrap();
+        assert_eq!(address.as_str(), "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+        assert_eq!(address.chain(), &Chain::Tron);
+
+        assert!(validate(&Chain::Tron, "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzy").is_ok());
+        assert_eq!(
+            validate(&Chain::Tron, "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzz"),
+            Err(AddressError::InvalidChecksum(Chain::Tron))
+        );
+        assert_eq!(
+            validate(&Chain::Tron, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(AddressError::InvalidFormat(Chain::Tron))
+        );
+    }
+
+    #[test]
+    fn it_validates_solana_addresses() {
+        assert!(validate(&Chain::Solana, "So11111111111111111111111111111111111111112").is_ok());
+        assert_eq!(
+            validate(&Chain::Solana, "So1111111111111111111111111111111111111111O"),
+            Err(AddressError::InvalidFormat(Chain::Solana))
+        );
+    }
+
+    #[test]
+    fn it_refuses_empty_addresses_and_unknown_chains() {
+        assert_eq!(validate(&Chain::Tron, "  "), Err(AddressError::Empty));
+        assert_eq!(
+            validate(&Chain::Unknown(String::from("Avalanche")), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            Err(AddressError::UnsupportedChain(Chain::Unknown(String::from("Avalanche"))))
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+use crate::crypto::{AssetId, CryptoAddress};
+
+/// The address of a customer and asset, once known.
+type Entry = Arc<AsyncMutex<Option<CryptoAddress>>>;
+
+/// The deposit address of each customer and asset looked up by a client.
+///
+/// Each entry is locked while its address is looked up or created through
+/// [`AddressFor`](crate::crypto::AddressFor), so concurrent requests for the same customer and
+/// asset wait for the first one instead of creating a second address.
+#[derive(Default)]
+pub(crate) struct DepositAddresses {
+    entries: Mutex<HashMap<(String, AssetId), Entry>>,
+}
+
+impl DepositAddresses {
+    /// Locks the entry of a customer and asset, holding its address if it is known already.
+    pub(crate) async fn lock(&self, customer_id: &str, asset_id: &AssetId) -> OwnedMutexGuard<Option<CryptoAddress>> {
+        let entry = self
+            .entries()
+            .entry((customer_id.to_string(), asset_id.clone()))
+            .or_default()
+            .clone();
+
+        entry.lock_owned().await
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<(String, AssetId), Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -1,9 +1,15 @@
+mod address_for;
 mod create_crypo_address;
 mod fetch_asset_chains;
 mod fetch_crypto_addresses;
 mod fetch_crypto_transactions;
+mod sweep_deposit;
+mod watch_deposits;
 
+pub use address_for::*;
 pub use create_crypo_address::*;
 pub use fetch_asset_chains::*;
 pub use fetch_crypto_addresses::*;
 pub use fetch_crypto_transactions::*;
+pub use sweep_deposit::*;
+pub use watch_deposits::*;
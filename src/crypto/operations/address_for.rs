@@ -0,0 +1,294 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{crypto::{AssetId, CreateCryptoAddress, CreateCryptoAddressParams, Crypto, CryptoAddress, FetchCryptoAddresses}, EversendError, EversendResult};
+
+/// An error returned from [`AddressFor`].
+#[derive(Debug, Error)]
+pub enum AddressForError {}
+
+impl From<AddressForError> for EversendError<AddressForError> {
+    fn from(err: AddressForError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Keeps one deposit address per customer and asset, using [`FetchCryptoAddresses`] and
+/// [`CreateCryptoAddress`].
+#[async_trait]
+pub trait AddressFor {
+    /// Get the deposit address of a customer for an asset, creating it if the customer has none.
+    ///
+    /// Customers are identified by the `destinationAddressDescription` of their addresses. An
+    /// address is only created when none of the existing ones, on any page, has that description
+    /// and asset, in which case `owner_name` is set on it. Concurrent calls for the same customer
+    /// and asset through clones of the same client wait for each other, so at most one address is
+    /// created.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::crypto::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), AddressForError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let address = eversend
+    ///         .crypto()
+    ///         .address_for("customer-1042", &AssetId::from("TRX_USDT_S2UZ"), "Jane Doe")
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    async fn address_for(
+        &self,
+        customer_id: &str,
+        asset_id: &AssetId,
+        owner_name: &str,
+    ) -> EversendResult<CryptoAddress, AddressForError>;
+}
+
+#[async_trait]
+impl<'a> AddressFor for Crypto<'a> {
+    async fn address_for(
+        &self,
+        customer_id: &str,
+        asset_id: &AssetId,
+        owner_name: &str,
+    ) -> EversendResult<CryptoAddress, AddressForError> {
+        let mut known = self.eversend.deposit_addresses().lock(customer_id, asset_id).await;
+
+        if let Some(address) = known.as_ref() {
+            return Ok(address.clone());
+        }
+
+        let existing = self.find_address(customer_id, asset_id).await?;
+
+        let address = match existing {
+            Some(address) => address,
+            None => self
+                .create_crypto_address(&CreateCryptoAddressParams {
+                    asset_id: asset_id.clone(),
+                    destination_address_description: customer_id.to_string(),
+                    owner_name: owner_name.to_string(),
+                    purpose: None,
+                })
+                .await
+                .map_err(|err| err.map_operation(|err| match err {}))?,
+        };
+
+        *known = Some(address.clone());
+
+        Ok(address)
+    }
+}
+
+impl<'a> Crypto<'a> {
+    /// Looks through every page of addresses for the one of the customer for the asset.
+    async fn find_address(
+        &self,
+        customer_id: &str,
+        asset_id: &AssetId,
+    ) -> EversendResult<Option<CryptoAddress>, AddressForError> {
+        let mut page_number = 1;
+
+        loop {
+            let page = self
+                .fetch_crypto_addresses_page(page_number)
+                .await
+                .map_err(|err| err.map_operation(|err| match err {}))?;
+
+            let has_next_page = page.has_next_page() && !page.addresses.is_empty();
+
+            let found = page.addresses.into_iter().find(|address| {
+                address.destination_address_description == customer_id && address.coin == asset_id.as_str()
+            });
+
+            if found.is_some() || !has_next_page {
+                return Ok(found);
+            }
+
+            page_number += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher, Mock};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend(prefix: &str) -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), prefix))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn address(customer_id: &str, coin: &str, address: &str) -> serde_json::Value {
+        json!({
+            "address": address,
+            "coin": coin,
+            "purpose": "Deposits",
+            "ownerName": "Jane Doe",
+            "destinationAddressDescription": customer_id,
+            "createdAt": "2022-10-18T12:58:51.093Z",
+            "updatedAt": "2022-10-18T12:58:51.093Z"
+        })
+    }
+
+    #[tokio::test]
+    async fn it_returns_the_existing_address_of_the_customer() {
+        let eversend = eversend("address-for-existing");
+
+        let fetch = mock("GET", "/address-for-existing/crypto/addresses")
+            .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "addresses": [
+                        address("customer-1", "TRX_USDT_S2UZ", "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"),
+                        address("customer-2", "USDT_ERC20", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+                        address("customer-2", "TRX_USDT_S2UZ", "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzy"),
+                    ]
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let create = mock("POST", "/address-for-existing/crypto/addresses")
+            .expect(0)
+            .create();
+
+        let address = eversend
+            .crypto()
+            .address_for("customer-2", &AssetId::from("TRX_USDT_S2UZ"), "Jane Doe")
+            .await
+            .unwrap();
+
+        assert_eq!(address.address, "TLsV52sRDL79HXGGm9yzwKibb6BeruhUzy");
+
+        eversend
+            .crypto()
+            .address_for("customer-2", &AssetId::from("TRX_USDT_S2UZ"), "Jane Doe")
+            .await
+            .unwrap();
+
+        fetch.assert();
+        create.assert();
+    }
+
+    #[tokio::test]
+    async fn it_looks_through_every_page_of_addresses() {
+        let eversend = eversend("address-for-pages");
+
+        let pages = [
+            address("customer-4", "USDT_ERC20", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            address("customer-4", "TRX_USDT_S2UZ", "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"),
+        ];
+
+        let fetches: Vec<_> = pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, address)| {
+                mock("GET", "/address-for-pages/crypto/addresses")
+                    .match_query(Matcher::UrlEncoded("page".into(), (index + 1).to_string()))
+                    .with_status(200)
+                    .with_body(json!({
+                        "code": 200,
+                        "data": {
+                            "addresses": [address],
+                            "total": 2,
+                            "limit": 1,
+                            "page": index + 1
+                        },
+                        "success": true
+                    }).to_string())
+                    .expect(1)
+                    .create()
+            })
+            .collect();
+
+        let create = mock("POST", "/address-for-pages/crypto/addresses")
+            .expect(0)
+            .create();
+
+        let address = eversend
+            .crypto()
+            .address_for("customer-4", &AssetId::from("TRX_USDT_S2UZ"), "Jane Doe")
+            .await
+            .unwrap();
+
+        assert_eq!(address.address, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+
+        fetches.iter().for_each(Mock::assert);
+        create.assert();
+    }
+
+    #[tokio::test]
+    async fn it_creates_a_single_address_under_concurrent_requests() {
+        let eversend = eversend("address-for-concurrent");
+
+        let fetch = mock("GET", "/address-for-concurrent/crypto/addresses")
+            .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "addresses": [
+                        address("customer-3", "USDT_ERC20", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+                    ]
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let create = mock("POST", "/address-for-concurrent/crypto/addresses")
+            .match_body(Matcher::PartialJson(json!({
+                "assetId": "TRX_USDT_S2UZ",
+                "destinationAddressDescription": "customer-3",
+                "ownerName": "Jane Doe"
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "address": address("customer-3", "TRX_USDT_S2UZ", "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t")
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let asset_id = AssetId::from("TRX_USDT_S2UZ");
+        let other = eversend.clone();
+        let (crypto, other_crypto) = (eversend.crypto(), other.crypto());
+
+        let (first, second) = tokio::join!(
+            crypto.address_for("customer-3", &asset_id, "Jane Doe"),
+            other_crypto.address_for("customer-3", &asset_id, "Jane Doe"),
+        );
+
+        assert_eq!(first.unwrap().address, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+        assert_eq!(second.unwrap().address, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+
+        fetch.assert();
+        create.assert();
+    }
+}
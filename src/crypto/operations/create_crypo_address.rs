@@ -2,13 +2,14 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{crypto::{Crypto, CryptoAddress}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{crypto::{AssetId, Crypto, CryptoAddress}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateCryptoAddressParams {
     /// Valid asset from Fetch Asset Chains
     #[serde(rename = "assetId")]
-    pub asset_id: String,
+    pub asset_id: AssetId,
 
     /// Client email or unique identifier
     #[serde(rename = "destinationAddressDescription")]
@@ -33,8 +34,13 @@ impl From<CreateCryptoAddressError> for EversendError<CreateCryptoAddressError>
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateCryptoAddressResponse {
     pub address: CryptoAddress,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Create Crypto Address](https://eversend.readme.io/reference/create-crypto-address)
@@ -60,7 +66,7 @@ pub trait CreateCryptoAddress {
     ///         .crypto()
     ///         .create_crypto_address(
     ///             &CreateCryptoAddressParams {
-    ///                 asset_id: String::from("TRX_USDT_S2UZ"),
+    ///                 asset_id: AssetId::from("TRX_USDT_S2UZ"),
     ///                 destination_address_description: String::from("emmanuelchilaka779@gmail.com"),
     ///                 owner_name: String::from("Emmanuel Chilaka"),
     ///                 purpose: Some(String::from("payment for coffee")),
@@ -95,7 +101,7 @@ impl<'a> CreateCryptoAddress for Crypto<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateCryptoAddressResponse>>()
+            .decode::<ApiResponseBody<CreateCryptoAddressResponse>, _>()
             .await?;
 
         Ok(result.data.address)
@@ -146,7 +152,7 @@ mod tests {
             .crypto()
             .create_crypto_address(
                 &CreateCryptoAddressParams {
-                    asset_id: String::from("TRX_USDT_S2UZ"),
+                    asset_id: AssetId::from("TRX_USDT_S2UZ"),
                     destination_address_description: String::from("emmanuelchilaka779@gmail.com"),
                     owner_name: String::from("Emmanuel Chilaka"),
                     purpose: Some(String::from("payment for coffee")),
@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 use thiserror::Error;
 
-use crate::{crypto::{AssetChains, Crypto}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{crypto::{AssetChain, AssetId, Crypto}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 pub struct FetchAssetChainsParams {
     /// This should be any of the available crypto asset you have access to.
@@ -21,8 +23,14 @@ impl From<FetchAssetChainsError> for EversendError<FetchAssetChainsError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FetchAssetChainsResponse {
-    pub chains: AssetChains,
+    /// Asset IDs keyed by chain display name, e.g. `TRON (TRC20)`.
+    pub chains: BTreeMap<String, AssetId>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Fetch Asset Chains](https://eversend.readme.io/reference/fetch-asset-chains)
@@ -53,6 +61,8 @@ pub trait FetchAssetChains {
     ///         )
     ///         .await?;
     ///
+    ///     let tron = chains.iter().find(|chain| chain.network == Chain::Tron);
+    ///
     ///     Ok(())
     /// # }
     /// ```
@@ -60,7 +70,7 @@ pub trait FetchAssetChains {
     async fn fetch_asset_chains(
         &self,
         params: &FetchAssetChainsParams,
-    ) -> EversendResult<AssetChains, FetchAssetChainsError>;
+    ) -> EversendResult<Vec<AssetChain>, FetchAssetChainsError>;
 }
 
 #[async_trait]
@@ -68,7 +78,7 @@ impl<'a> FetchAssetChains for Crypto<'a> {
     async fn fetch_asset_chains(
         &self,
         params: &FetchAssetChainsParams,
-    ) -> EversendResult<AssetChains, FetchAssetChainsError> {
+    ) -> EversendResult<Vec<AssetChain>, FetchAssetChainsError> {
         let url = format!("{}/crypto/assets/{}", self.eversend.base_url(), params.coin);
 
         let result = self
@@ -79,16 +89,21 @@ impl<'a> FetchAssetChains for Crypto<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<FetchAssetChainsResponse>>()
+            .decode::<ApiResponseBody<FetchAssetChainsResponse>, _>()
             .await?;
 
-        Ok(result.data.chains)
+        Ok(
+            result.data.chains
+                .into_iter()
+                .map(|(display_name, asset_id)| AssetChain::new(display_name, asset_id))
+                .collect()
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+    use crate::{crypto::Chain, ClientId, eversend::Eversend, ApiToken, ClientSecret};
 
     use super::*;
     use mockito::{self, mock};
@@ -116,7 +131,8 @@ mod tests {
                         "chains": {
                             "Binance Smart Chain (BEP20)": "USDT_BSC",
                             "Ethereum (ERC20)": "USDT_ERC20",
-                            "TRON (TRC20)": "TRX_USDT_S2UZ"
+                            "TRON (TRC20)": "TRX_USDT_S2UZ",
+                            "Polygon": "USDT_POLYGON"
                         }
                     },
                     "success": true
@@ -134,7 +150,14 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(chains.binance_smart_chain, "USDT_BSC");
+        assert_eq!(chains.len(), 4);
+        assert_eq!(chains[0], AssetChain {
+            network: Chain::BinanceSmartChain,
+            asset_id: AssetId::from("USDT_BSC"),
+            display_name: String::from("Binance Smart Chain (BEP20)"),
+        });
+        assert_eq!(chains[2].network, Chain::Polygon);
+        assert_eq!(chains[3].asset_id.as_str(), "TRX_USDT_S2UZ");
 
         mock.assert();
 
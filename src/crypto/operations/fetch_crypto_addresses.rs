@@ -15,8 +15,32 @@ impl From<FetchCryptoAddressesError> for EversendError<FetchCryptoAddressesError
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FetchCryptoAddressesResponse {
     pub addresses: Vec<CryptoAddress>,
+
+    /// Total number of addresses, across all pages.
+    #[serde(default)]
+    pub total: u32,
+
+    /// The page size used for this listing.
+    #[serde(default)]
+    pub limit: u32,
+
+    /// The current page, starting from 1.
+    #[serde(default)]
+    pub page: u32,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl FetchCryptoAddressesResponse {
+    /// Returns `true` if there are more pages after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.limit > 0 && self.page < self.total.div_ceil(self.limit)
+    }
 }
 
 /// [Eversend Docs: Fetch Addresses](https://eversend.readme.io/reference/fetch-address)
@@ -50,6 +74,14 @@ pub trait FetchCryptoAddresses {
     async fn fetch_crypto_addresses(
         &self
     ) -> EversendResult<Vec<CryptoAddress>, FetchCryptoAddressesError>;
+
+    /// Fetch a page of Addresses, starting from 1.
+    ///
+    /// [Eversend Docs: Fetch Addresses](https://eversend.readme.io/reference/fetch-address)
+    async fn fetch_crypto_addresses_page(
+        &self,
+        page: u32
+    ) -> EversendResult<FetchCryptoAddressesResponse, FetchCryptoAddressesError>;
 }
 
 #[async_trait]
@@ -67,11 +99,31 @@ impl<'a> FetchCryptoAddresses for Crypto<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<FetchCryptoAddressesResponse>>()
+            .decode::<ApiResponseBody<FetchCryptoAddressesResponse>, _>()
             .await?;
 
         Ok(result.data.addresses)
     }
+
+    async fn fetch_crypto_addresses_page(
+        &self,
+        page: u32
+    ) -> EversendResult<FetchCryptoAddressesResponse, FetchCryptoAddressesError> {
+        let url = format!("{}/crypto/addresses?page={}", self.eversend.base_url(), page);
+
+        let result = self
+            .eversend
+            .client()
+            .get(url)
+            .bearer_auth(self.eversend.api_token().unwrap())
+            .send()
+            .await?
+            .handle_unauthorized_or_generic_error()?
+            .decode::<ApiResponseBody<FetchCryptoAddressesResponse>, _>()
+            .await?;
+
+        Ok(result.data)
+    }
 }
 
 #[cfg(test)]
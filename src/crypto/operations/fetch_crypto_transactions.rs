@@ -15,8 +15,32 @@ impl From<FetchCryptoTransactionsError> for EversendError<FetchCryptoTransaction
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct FetchCryptoTransactionsResponse {
     pub transactions: Vec<CryptoTransaction>,
+
+    /// Total number of transactions, across all pages.
+    #[serde(default)]
+    pub total: u32,
+
+    /// The page size used for this listing.
+    #[serde(default)]
+    pub limit: u32,
+
+    /// The current page, starting from 1.
+    #[serde(default)]
+    pub page: u32,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl FetchCryptoTransactionsResponse {
+    /// Returns `true` if there are more pages after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.limit > 0 && self.page < self.total.div_ceil(self.limit)
+    }
 }
 
 /// [Eversend Docs: Fetch Transactions](https://eversend.readme.io/reference/fetch-transactions)
@@ -50,6 +74,14 @@ pub trait FetchCryptoTransactions {
     async fn fetch_crypto_transactions(
         &self
     ) -> EversendResult<Vec<CryptoTransaction>, FetchCryptoTransactionsError>;
+
+    /// Fetch a page of Transactions, starting from 1.
+    ///
+    /// [Eversend Docs: Fetch Transactions](https://eversend.readme.io/reference/fetch-transactions)
+    async fn fetch_crypto_transactions_page(
+        &self,
+        page: u32
+    ) -> EversendResult<FetchCryptoTransactionsResponse, FetchCryptoTransactionsError>;
 }
 
 #[async_trait]
@@ -67,16 +99,36 @@ impl<'a> FetchCryptoTransactions for Crypto<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<FetchCryptoTransactionsResponse>>()
+            .decode::<ApiResponseBody<FetchCryptoTransactionsResponse>, _>()
             .await?;
 
         Ok(result.data.transactions)
     }
+
+    async fn fetch_crypto_transactions_page(
+        &self,
+        page: u32
+    ) -> EversendResult<FetchCryptoTransactionsResponse, FetchCryptoTransactionsError> {
+        let url = format!("{}/crypto/transactions?page={}", self.eversend.base_url(), page);
+
+        let result = self
+            .eversend
+            .client()
+            .get(url)
+            .bearer_auth(self.eversend.api_token().unwrap())
+            .send()
+            .await?
+            .handle_unauthorized_or_generic_error()?
+            .decode::<ApiResponseBody<FetchCryptoTransactionsResponse>, _>()
+            .await?;
+
+        Ok(result.data)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret, Decimal, crypto::{CryptoTransactionStatus, CryptoTransactionSubStatus}};
 
     use super::*;
     use mockito::{self, mock};
@@ -112,14 +164,14 @@ mod tests {
                                     "type": "Blockchain",
                                     "amount": 10,
                                     "source": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
-                                    "charges": 0,
+                                    "charges": 0.25,
                                     "country": "NG",
                                     "currency": "XDC",
                                     "toppedUp": true,
                                     "username": "eversendb2btest@gmail.com",
                                     "processor": "Fireblocks",
                                     "actualCoin": "TRX_USDC_6NU3",
-                                    "totalToPay": 10,
+                                    "totalToPay": 10.25,
                                     "eversendRef": "3557276",
                                     "creationDate": "2022-10-18T13:13:47+00:00",
                                     "fireblocksId": "6f014008-47bd-422a-9a26-25f32f79dc60",
@@ -159,8 +211,102 @@ mod tests {
             .unwrap();
 
         assert_eq!(transactions[0].transaction_id, "BP11666178904722");
+        assert_eq!(transactions[0].amount, Decimal::from(10));
+        assert_eq!(transactions[0].meta.total_to_pay, Decimal::new(1025, 2));
+        assert_eq!(transactions[0].status, CryptoTransactionStatus::Confirming);
+        assert_eq!(transactions[0].sub_status, CryptoTransactionSubStatus::PendingBlockchainConfirmations);
+        assert!(!transactions[0].is_final());
+        assert!(transactions[0].is_credited());
 
         mock.assert();
 
     }
+
+    #[tokio::test]
+    async fn it_keeps_every_digit_of_amounts() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/crypto-transactions-precision", mockito::server_url()))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let body = json!({
+            "code": 200,
+            "data": {
+                "transactions": [
+                    {
+                        "id": 2,
+                        "transactionId": "BP11666178904722",
+                        "accountId": 1,
+                        "addressId": 11,
+                        "amount": "PRECISE",
+                        "meta": {
+                            "date": "2022-10-18",
+                            "fees": "0.00",
+                            "type": "Blockchain",
+                            "amount": "PRECISE",
+                            "source": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                            "charges": "PRECISE",
+                            "country": "NG",
+                            "currency": "XDC",
+                            "toppedUp": true,
+                            "username": "eversendb2btest@gmail.com",
+                            "processor": "Fireblocks",
+                            "actualCoin": "TRX_USDC_6NU3",
+                            "totalToPay": "PRECISE",
+                            "eversendRef": "3557276",
+                            "creationDate": "2022-10-18T13:13:47+00:00",
+                            "fireblocksId": "6f014008-47bd-422a-9a26-25f32f79dc60",
+                            "toppedUpDate": "2022-10-18T13:13:48+00:00",
+                            "blockchainHash": "972cca5c28496d8f82c3a5d0fdc02110a762a25c1823d76275901554f8752726",
+                            "blockchainStatus": "COMPLETED",
+                            "blockchainSubStatus": "CONFIRMED",
+                            "networkFee": "PRECISE"
+                        },
+                        "status": "COMPLETED",
+                        "subStatus": "CONFIRMED",
+                        "createdAt": "2022-10-19T11:28:26.480Z",
+                        "updatedAt": "2022-10-19T11:28:26.481Z",
+                        "address": {
+                            "address": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                            "coin": "TRX_USDC_6NU3",
+                            "purpose": "Payment for coffee",
+                            "ownerName": "Emmanuel Chilaka",
+                            "destinationAddressDescription": "emmanuel@eversend.co",
+                            "createdAt": "2022-10-18T12:58:51.093Z",
+                            "updatedAt": "2022-10-18T12:58:51.093Z",
+                        }
+                    }
+                ],
+                "total": 1,
+                "limit": 10,
+                "page": 1
+            },
+            "success": true
+        })
+            .to_string()
+            // More digits than an f64 holds, sent as JSON numbers.
+            .replace("\"PRECISE\"", "0.123456789012345678");
+
+        let _mock = mock("GET", "/crypto-transactions-precision/crypto/transactions")
+            .with_status(200)
+            .with_body(body)
+            .create();
+
+        let transactions = eversend
+            .crypto()
+            .fetch_crypto_transactions()
+            .await
+            .unwrap();
+
+        let precise = Decimal::from_str_exact("0.123456789012345678").unwrap();
+
+        assert_eq!(transactions[0].amount, precise);
+        assert_eq!(transactions[0].meta.amount, precise);
+        assert_eq!(transactions[0].meta.charges, precise);
+        assert_eq!(transactions[0].meta.total_to_pay, precise);
+        assert_eq!(transactions[0].meta.extra["networkFee"].to_string(), "0.123456789012345678");
+    }
 }
@@ -0,0 +1,570 @@
+use std::io;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{crypto::{Crypto, CryptoTransaction, CryptoTransactionStatus, SweptDeposits}, exchange::{CreateQuotationParams, ExchangeQuote, ExchangeQuoteError, ExchangeResult, Quotation}, wallets::WalletId, Decimal, EversendError, EversendResult, RateDelta};
+
+/// How [`SweepDeposit`] converts crypto deposits into a fiat wallet.
+#[derive(Debug, Clone)]
+pub struct SweepPolicy {
+    /// The crypto wallet deposits are credited to, e.g. `USDT`. Deposits in other currencies are
+    /// left alone.
+    pub from: WalletId,
+
+    /// The fiat wallet to convert into, e.g. `UGX`.
+    pub to: WalletId,
+
+    /// Deposits crediting less than this are left in the crypto wallet. Defaults to 0.
+    pub min_amount: Decimal,
+
+    /// How far the quoted rate may be from the reference rate, in percent. Defaults to 2.
+    pub max_rate_deviation: f64,
+
+    /// The rate quotations are checked against. Defaults to the last rate quoted for the pair, if
+    /// it is still fresh, see [`RateCache`](crate::exchange::RateCache). Without either, deposits
+    /// are skipped with [`SweepSkipReason::NoReferenceRate`].
+    pub reference_rate: Option<f64>,
+}
+
+impl SweepPolicy {
+    /// Returns a policy converting every deposit credited to `from` into `to`.
+    pub fn new(from: WalletId, to: WalletId) -> Self {
+        Self {
+            from,
+            to,
+            min_amount: Decimal::ZERO,
+            max_rate_deviation: 2.0,
+            reference_rate: None,
+        }
+    }
+}
+
+/// Why a deposit was left in the crypto wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepSkipReason {
+    /// The deposit is not completed and credited to the wallet yet.
+    NotCredited,
+
+    /// The deposit is in the [`SweptDeposits`] record already.
+    AlreadySwept,
+
+    /// The deposit was credited to another wallet than [`SweepPolicy::from`].
+    OtherCurrency(String),
+
+    /// The deposit credited less than [`SweepPolicy::min_amount`].
+    BelowMinimum,
+
+    /// Neither [`SweepPolicy::reference_rate`] nor a fresh cached rate was available to check the
+    /// quoted rate against. No quotation was requested.
+    NoReferenceRate,
+
+    /// The quoted rate moved more than [`SweepPolicy::max_rate_deviation`] from the reference rate.
+    /// The quotation was not used.
+    RateDeviation(RateDelta),
+}
+
+/// What [`SweepDeposit`] did with a deposit.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)] // Made once per deposit, boxing would only add noise.
+pub enum SweepOutcome {
+    /// The deposit was converted.
+    Swept {
+        quotation: Quotation,
+        exchange: ExchangeResult,
+    },
+
+    /// The deposit was left in the crypto wallet.
+    Skipped(SweepSkipReason),
+}
+
+/// The result of [`SweepDeposit`].
+#[derive(Debug, Clone)]
+pub struct DepositSweep {
+    /// The crypto transaction of the deposit.
+    pub transaction_id: String,
+
+    /// The amount received by the deposit, in its crypto currency.
+    pub amount: Decimal,
+
+    pub outcome: SweepOutcome,
+}
+
+impl DepositSweep {
+    /// Returns `true` if the deposit was converted.
+    pub fn is_swept(&self) -> bool {
+        matches!(self.outcome, SweepOutcome::Swept { .. })
+    }
+}
+
+/// An error returned from [`SweepDeposit`].
+#[derive(Debug, Error)]
+pub enum SweepDepositError {
+    /// The quotation could not be used, e.g. because it expired before the exchange.
+    #[error(transparent)]
+    Exchange(#[from] ExchangeQuoteError),
+
+    /// The deposit could not be recorded in [`SweptDeposits`], so it was not exchanged. The record
+    /// is unchanged.
+    #[error("could not record the sweep: {0}")]
+    Record(#[from] io::Error),
+
+    /// The quotation could not be used, so the deposit was not exchanged, but it could not be
+    /// removed from [`SweptDeposits`] either. It stays recorded, and is skipped until it is removed.
+    #[error("{exchange}, and the deposit could not be removed from the record: {record}")]
+    ExchangeRecorded {
+        #[source]
+        exchange: ExchangeQuoteError,
+        record: io::Error,
+    },
+}
+
+impl From<SweepDepositError> for EversendError<SweepDepositError> {
+    fn from(err: SweepDepositError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Converts credited crypto deposits into a fiat wallet, using [`ExchangeQuote`].
+#[async_trait]
+pub trait SweepDeposit {
+    /// Sweep a deposit into the fiat wallet of a policy.
+    ///
+    /// Only deposits that are `COMPLETED` and credited to the wallet are swept. The amount
+    /// received ([`CryptoTransaction::amount`]) is quoted from [`SweepPolicy::from`] to
+    /// [`SweepPolicy::to`], and exchanged unless the rate moved too far from the reference rate.
+    /// `totalToPay` is not used, as it may include charges that were not credited.
+    ///
+    /// Deposits are added to `swept` before they are exchanged, and deposits found in it are
+    /// skipped, so with the same record each deposit is exchanged at most once, even when
+    /// [`WatchDeposits`](crate::crypto::WatchDeposits) emits it again after a restart. A deposit
+    /// stays recorded after a request error while exchanging, as it does not tell whether the
+    /// exchange went through: check the wallet balances before removing it from the record.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::crypto::*;
+    /// use std::time::Duration;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Decimal,Eversend};
+    /// use eversend_rust_sdk::wallets::WalletId;
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let policy = SweepPolicy {
+    ///         min_amount: Decimal::from(5),
+    ///         ..SweepPolicy::new(WalletId::from("USDT"), WalletId::from("UGX"))
+    ///     };
+    ///
+    ///     let mut swept = SweptDeposits::load("swept.json")?;
+    ///     let mut deposits = eversend.crypto().watch_deposits(Duration::from_secs(30));
+    ///
+    ///     while let Some(deposit) = deposits.next().await {
+    ///         let sweep = eversend.crypto().sweep_deposit(&deposit?, &policy, &mut swept).await?;
+    ///
+    ///         println!("{} {} swept: {}", sweep.transaction_id, sweep.amount, sweep.is_swept());
+    ///     }
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    async fn sweep_deposit(
+        &self,
+        transaction: &CryptoTransaction,
+        policy: &SweepPolicy,
+        swept: &mut SweptDeposits,
+    ) -> EversendResult<DepositSweep, SweepDepositError>;
+}
+
+#[async_trait]
+impl<'a> SweepDeposit for Crypto<'a> {
+    async fn sweep_deposit(
+        &self,
+        transaction: &CryptoTransaction,
+        policy: &SweepPolicy,
+        swept: &mut SweptDeposits,
+    ) -> EversendResult<DepositSweep, SweepDepositError> {
+        let amount = transaction.amount;
+
+        let skipped = |reason| DepositSweep {
+            transaction_id: transaction.transaction_id.clone(),
+            amount,
+            outcome: SweepOutcome::Skipped(reason),
+        };
+
+        if transaction.status != CryptoTransactionStatus::Completed || !transaction.is_credited() {
+            return Ok(skipped(SweepSkipReason::NotCredited));
+        }
+
+        if !transaction.meta.currency.eq_ignore_ascii_case(&policy.from.to_string()) {
+            return Ok(skipped(SweepSkipReason::OtherCurrency(transaction.meta.currency.clone())));
+        }
+
+        if amount < policy.min_amount {
+            return Ok(skipped(SweepSkipReason::BelowMinimum));
+        }
+
+        if swept.contains(&transaction.transaction_id) {
+            return Ok(skipped(SweepSkipReason::AlreadySwept));
+        }
+
+        let exchange = self.eversend.exchange();
+
+        // Read before quoting, as the quotation replaces the cached rate.
+        let reference_rate = policy.reference_rate.or_else(|| {
+            exchange
+                .rates()
+                .get(&policy.from, &policy.to)
+                .filter(|rate| rate.age() <= exchange.rates().freshness())
+                .map(|rate| rate.rate)
+        });
+
+        let Some(reference_rate) = reference_rate else {
+            return Ok(skipped(SweepSkipReason::NoReferenceRate));
+        };
+
+        let handle = exchange
+            .quote(&CreateQuotationParams {
+                amount: amount.to_string(),
+                from: &policy.from,
+                to: &policy.to,
+            })
+            .await
+            .map_err(|err| err.map_operation(SweepDepositError::from))?;
+
+        let delta = RateDelta {
+            previous: reference_rate,
+            current: handle.quotation().rate,
+        };
+
+        if delta.percentage().abs() > policy.max_rate_deviation {
+            return Ok(skipped(SweepSkipReason::RateDeviation(delta)));
+        }
+
+        swept.insert(&transaction.transaction_id).map_err(SweepDepositError::Record)?;
+
+        let result = match exchange.exchange_quote(&handle).await {
+            Ok(result) => result,
+            // Operation errors, e.g. an expired quotation, are raised before anything is sent.
+            Err(EversendError::Operation(exchange)) => {
+                let err = match swept.remove(&transaction.transaction_id) {
+                    Ok(()) => SweepDepositError::Exchange(exchange),
+                    Err(record) => SweepDepositError::ExchangeRecorded { exchange, record },
+                };
+
+                return Err(err.into());
+            },
+            Err(err) => return Err(err.map_operation(SweepDepositError::from)),
+        };
+
+        Ok(DepositSweep {
+            transaction_id: transaction.transaction_id.clone(),
+            amount,
+            outcome: SweepOutcome::Swept {
+                quotation: handle.quotation().clone(),
+                exchange: result,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret, crypto::{DepositWatch, WatchDeposits}};
+
+    use super::*;
+    use futures::StreamExt;
+    use mockito::{self, mock, Matcher};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend(prefix: &str) -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), prefix))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn deposit(status: &str, topped_up: bool, currency: &str, amount: f64) -> CryptoTransaction {
+        serde_json::from_value(deposit_json(status, topped_up, currency, amount)).unwrap()
+    }
+
+    /// A deposit of `amount`, with charges on top in `totalToPay`.
+    fn deposit_json(status: &str, topped_up: bool, currency: &str, amount: f64) -> serde_json::Value {
+        json!({
+            "id": 2,
+            "transactionId": "BP11666178904722",
+            "accountId": 1,
+            "addressId": 11,
+            "amount": amount.to_string(),
+            "meta": {
+                "date": "2022-10-18",
+                "fees": "0.00",
+                "type": "Blockchain",
+                "amount": amount,
+                "source": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                "charges": 0.5,
+                "country": "UG",
+                "currency": currency,
+                "toppedUp": topped_up,
+                "username": "eversendb2btest@gmail.com",
+                "processor": "Fireblocks",
+                "actualCoin": "TRX_USDT_S2UZ",
+                "totalToPay": amount + 0.5,
+                "eversendRef": "3557276",
+                "creationDate": "2022-10-18T13:13:47+00:00",
+                "fireblocksId": "6f014008-47bd-422a-9a26-25f32f79dc60",
+                "toppedUpDate": "2022-10-18T13:13:48+00:00",
+                "blockchainHash": "972cca5c28496d8f82c3a5d0fdc02110a762a25c1823d76275901554f8752726",
+                "blockchainStatus": status,
+                "blockchainSubStatus": "CONFIRMED"
+            },
+            "status": status,
+            "subStatus": "CONFIRMED",
+            "createdAt": "2022-10-19T11:28:26.480Z",
+            "updatedAt": "2022-10-19T11:28:26.481Z",
+            "address": {
+                "address": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                "coin": "TRX_USDT_S2UZ",
+                "purpose": "Deposits",
+                "ownerName": "Jane Doe",
+                "destinationAddressDescription": "customer-1042",
+                "createdAt": "2022-10-18T12:58:51.093Z",
+                "updatedAt": "2022-10-18T12:58:51.093Z"
+            }
+        })
+    }
+
+    async fn next_deposit(watch: &mut DepositWatch) -> CryptoTransaction {
+        tokio::time::timeout(Duration::from_millis(1000), watch.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn mock_quotation(prefix: &str, amount: &str, rate: f64) -> mockito::Mock {
+        mock("POST", format!("/{}/exchanges/quotation", prefix).as_str())
+            .match_body(Matcher::Json(json!({ "amount": amount, "from": "USDT", "to": "UGX" })))
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "expires": "2099-01-01T00:00:00+00:00",
+                    "token": "some-quotation-token",
+                    "quotation": {
+                        "baseCurrency": "USDT",
+                        "baseAmount": 10.5,
+                        "baseWalletBefore": 10.5,
+                        "baseWalletAfter": 0,
+                        "destCurrency": "UGX",
+                        "destAmount": 10.5 * rate,
+                        "destWalletBefore": 0,
+                        "destWalletAfter": null,
+                        "rate": rate
+                    }
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create()
+    }
+
+    #[tokio::test]
+    async fn it_sweeps_credited_deposits_into_the_fiat_wallet() {
+        let eversend = eversend("sweep-deposit-swept");
+
+        let quotation = mock_quotation("sweep-deposit-swept", "10.5", 3700.0);
+
+        let exchange = mock("POST", "/sweep-deposit-swept/exchanges")
+            .match_body(Matcher::PartialJson(json!({ "quotation_token": "some-quotation-token" })))
+            .with_status(200)
+            .with_body(json!({
+                "code": 201,
+                "data": {
+                    "source": {
+                        "currency": "USDT",
+                        "amount": 10.5,
+                        "balance": { "before": "10.5", "after": "0" }
+                    },
+                    "destination": {
+                        "currency": "UGX",
+                        "amount": 38850,
+                        "balance": { "before": "0", "after": "38850" }
+                    }
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let policy = SweepPolicy {
+            reference_rate: Some(3700.0),
+            ..SweepPolicy::new(WalletId::from("USDT"), WalletId::from("UGX"))
+        };
+        let mut swept = SweptDeposits::default();
+
+        let sweep = eversend
+            .crypto()
+            .sweep_deposit(&deposit("COMPLETED", true, "USDT", 10.5), &policy, &mut swept)
+            .await
+            .unwrap();
+
+        // The amount received, not `totalToPay`, which includes the charges.
+        assert_eq!(sweep.amount, Decimal::new(105, 1));
+        assert_eq!(sweep.amount, deposit("COMPLETED", true, "USDT", 10.5).amount);
+        assert_ne!(sweep.amount, deposit("COMPLETED", true, "USDT", 10.5).meta.total_to_pay);
+        assert!(matches!(
+            sweep.outcome,
+            SweepOutcome::Swept { ref exchange, .. } if exchange.destination.amount == 38850.0
+        ));
+        assert!(swept.contains("BP11666178904722"));
+
+        let again = eversend
+            .crypto()
+            .sweep_deposit(&deposit("COMPLETED", true, "USDT", 10.5), &policy, &mut swept)
+            .await
+            .unwrap();
+
+        assert!(matches!(again.outcome, SweepOutcome::Skipped(SweepSkipReason::AlreadySwept)));
+
+        quotation.assert();
+        exchange.assert();
+    }
+
+    #[tokio::test]
+    async fn it_leaves_deposits_outside_the_policy_in_the_crypto_wallet() {
+        let eversend = eversend("sweep-deposit-skipped");
+
+        let quotation = mock_quotation("sweep-deposit-skipped", "10.5", 3500.0);
+
+        let exchange = mock("POST", "/sweep-deposit-skipped/exchanges")
+            .expect(0)
+            .create();
+
+        let policy = SweepPolicy {
+            min_amount: Decimal::from(5),
+            reference_rate: Some(3700.0),
+            ..SweepPolicy::new(WalletId::from("USDT"), WalletId::from("UGX"))
+        };
+
+        let skip_reason = |transaction: CryptoTransaction| {
+            let eversend = eversend.clone();
+            let policy = policy.clone();
+
+            async move {
+                let mut swept = SweptDeposits::default();
+
+                match eversend.crypto().sweep_deposit(&transaction, &policy, &mut swept).await.unwrap().outcome {
+                    SweepOutcome::Skipped(reason) => reason,
+                    SweepOutcome::Swept { .. } => panic!("deposit was swept"),
+                }
+            }
+        };
+
+        assert_eq!(skip_reason(deposit("CONFIRMING", true, "USDT", 10.5)).await, SweepSkipReason::NotCredited);
+        assert_eq!(skip_reason(deposit("COMPLETED", false, "USDT", 10.5)).await, SweepSkipReason::NotCredited);
+        assert_eq!(
+            skip_reason(deposit("COMPLETED", true, "USDC", 10.5)).await,
+            SweepSkipReason::OtherCurrency(String::from("USDC"))
+        );
+        assert_eq!(skip_reason(deposit("COMPLETED", true, "USDT", 4.99)).await, SweepSkipReason::BelowMinimum);
+        assert_eq!(
+            skip_reason(deposit("COMPLETED", true, "USDT", 10.5)).await,
+            SweepSkipReason::RateDeviation(RateDelta { previous: 3700.0, current: 3500.0 })
+        );
+
+        // Without a cached rate from an earlier quotation either.
+        let unreferenced = SweepPolicy::new(WalletId::from("USDT"), WalletId::from("UGX"));
+        let sweep = self::eversend("sweep-deposit-unreferenced")
+            .crypto()
+            .sweep_deposit(&deposit("COMPLETED", true, "USDT", 10.5), &unreferenced, &mut SweptDeposits::default())
+            .await
+            .unwrap();
+
+        assert!(matches!(sweep.outcome, SweepOutcome::Skipped(SweepSkipReason::NoReferenceRate)));
+
+        quotation.assert();
+        exchange.assert();
+    }
+
+    #[tokio::test]
+    async fn it_sweeps_watched_deposits_once_they_are_credited() {
+        let eversend = eversend("sweep-deposit-watched");
+
+        let mock_deposit = |topped_up: bool| {
+            mock("GET", "/sweep-deposit-watched/crypto/transactions")
+                .match_query(Matcher::UrlEncoded("page".into(), "1".into()))
+                .with_status(200)
+                .with_body(json!({
+                    "code": 200,
+                    "data": {
+                        "transactions": [deposit_json("COMPLETED", topped_up, "USDT", 10.5)],
+                        "total": 1,
+                        "limit": 10,
+                        "page": 1
+                    },
+                    "success": true
+                }).to_string())
+                .create()
+        };
+
+        let quotation = mock_quotation("sweep-deposit-watched", "10.5", 3700.0);
+
+        let exchange = mock("POST", "/sweep-deposit-watched/exchanges")
+            .with_status(200)
+            .with_body(json!({
+                "code": 201,
+                "data": {
+                    "source": {
+                        "currency": "USDT",
+                        "amount": 10.5,
+                        "balance": { "before": "10.5", "after": "0" }
+                    },
+                    "destination": {
+                        "currency": "UGX",
+                        "amount": 38850,
+                        "balance": { "before": "0", "after": "38850" }
+                    }
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        let policy = SweepPolicy {
+            reference_rate: Some(3700.0),
+            ..SweepPolicy::new(WalletId::from("USDT"), WalletId::from("UGX"))
+        };
+        let mut swept = SweptDeposits::default();
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10));
+
+        let uncredited = mock_deposit(false);
+        let deposit = next_deposit(&mut watch).await;
+        let sweep = eversend.crypto().sweep_deposit(&deposit, &policy, &mut swept).await.unwrap();
+
+        assert!(matches!(sweep.outcome, SweepOutcome::Skipped(SweepSkipReason::NotCredited)));
+
+        // Crediting a deposit does not always move its `updatedAt`.
+        drop(uncredited);
+        let _credited = mock_deposit(true);
+        let deposit = next_deposit(&mut watch).await;
+        let sweep = eversend.crypto().sweep_deposit(&deposit, &policy, &mut swept).await.unwrap();
+
+        assert!(sweep.is_swept());
+
+        quotation.assert();
+        exchange.assert();
+    }
+}
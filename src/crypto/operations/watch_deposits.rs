@@ -0,0 +1,532 @@
+use std::{cmp::Ordering, collections::{HashMap, VecDeque}, future::Future, io, path::PathBuf, pin::Pin, task::{Context, Poll}, time::Duration};
+
+use futures::{future::BoxFuture, ready, Stream};
+use thiserror::Error;
+use tokio::time::Sleep;
+
+use crate::{crypto::{compare_timestamps, status_of, Crypto, CryptoTransaction, CryptoTransactionStatus, DepositWatermark, FetchCryptoTransactions, FetchCryptoTransactionsError}, Eversend, EversendError, EversendResult};
+
+/// An error returned from [`DepositWatch`].
+#[derive(Debug, Error)]
+pub enum WatchDepositsError {
+    /// The watermark could not be written. The watch keeps its previous watermark and writes again
+    /// after the next fetch. Deposits emitted since the last successful write may be emitted again
+    /// after a restart.
+    #[error("could not persist the deposit watermark: {0}")]
+    Persist(#[from] io::Error),
+}
+
+impl From<WatchDepositsError> for EversendError<WatchDepositsError> {
+    fn from(err: WatchDepositsError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Polls [`FetchCryptoTransactions`] for new deposits.
+pub trait WatchDeposits {
+    /// Watch for new crypto transactions, and for transactions whose status changed.
+    ///
+    /// Transactions are fetched straight away and then every `interval`, page by page until every
+    /// transaction of a page was updated before the watermark. Each transaction is
+    /// emitted once per status, and again once it is credited, oldest update first. Failed
+    /// fetches are emitted as errors and retried on the next tick, so the stream only ends when
+    /// it is dropped.
+    ///
+    /// By default the stream starts from scratch and emits every transaction it finds. Use
+    /// [`DepositWatch::persist_to`] to keep a [`DepositWatermark`] on disk, so a restarted
+    /// watch picks up where the previous one stopped. The watermark is only written once every
+    /// transaction of a fetch was taken from the stream, so deposits are emitted at least once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::crypto::*;
+    /// use std::time::Duration;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> EversendResult<(), WatchDepositsError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let mut deposits = eversend
+    ///         .crypto()
+    ///         .watch_deposits(Duration::from_secs(30))
+    ///         .persist_to("deposits.json")?;
+    ///
+    ///     while let Some(deposit) = deposits.next().await {
+    ///         let deposit = deposit?;
+    ///
+    ///         println!("{} is {}", deposit.transaction_id, deposit.status);
+    ///     }
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    fn watch_deposits(&self, interval: Duration) -> DepositWatch;
+}
+
+impl<'a> WatchDeposits for Crypto<'a> {
+    fn watch_deposits(&self, interval: Duration) -> DepositWatch {
+        DepositWatch {
+            eversend: self.eversend.clone(),
+            interval,
+            path: None,
+            watermark: DepositWatermark::default(),
+            statuses: HashMap::new(),
+            pending: VecDeque::new(),
+            uncommitted: None,
+            fetch: None,
+            sleep: None,
+        }
+    }
+}
+
+/// A stream of new and updated [`CryptoTransaction`]s, returned by [`WatchDeposits`].
+pub struct DepositWatch {
+    eversend: Eversend,
+    interval: Duration,
+    path: Option<PathBuf>,
+    watermark: DepositWatermark,
+
+    /// The last status emitted or skipped per transaction since the watch started, less settled
+    /// transactions the watermark has moved past.
+    statuses: HashMap<String, SeenStatus>,
+
+    /// Transactions of the last fetch not taken from the stream yet.
+    pending: VecDeque<CryptoTransaction>,
+
+    /// The watermark to commit once `pending` is drained.
+    uncommitted: Option<DepositWatermark>,
+
+    fetch: Option<BoxFuture<'static, EversendResult<Vec<CryptoTransaction>, FetchCryptoTransactionsError>>>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+/// The status a transaction was last seen in.
+struct SeenStatus {
+    status: String,
+    updated_at: String,
+    is_settled: bool,
+}
+
+impl DepositWatch {
+    /// Resumes from the watermark stored at `path`, if there is one, and stores it there as the
+    /// watch progresses.
+    pub fn persist_to(mut self, path: impl Into<PathBuf>) -> Result<Self, WatchDepositsError> {
+        let path = path.into();
+
+        self.watermark = DepositWatermark::load(&path)?;
+        self.path = Some(path);
+
+        Ok(self)
+    }
+
+    /// Resumes from a watermark kept elsewhere, e.g. in a database.
+    pub fn starting_from(mut self, watermark: DepositWatermark) -> Self {
+        self.watermark = watermark;
+        self
+    }
+
+    /// How far the watch has got, counting only transactions of fully drained fetches.
+    pub fn watermark(&self) -> &DepositWatermark {
+        &self.watermark
+    }
+
+    fn enqueue(&mut self, mut transactions: Vec<CryptoTransaction>) {
+        transactions.sort_by(|a, b| compare_timestamps(&a.updated_at, &b.updated_at));
+
+        // The watermark alone keeps settled transactions at or before it from being emitted again.
+        if let Some(updated_at) = self.watermark.updated_at() {
+            self.statuses.retain(|_, seen| {
+                !seen.is_settled || compare_timestamps(&seen.updated_at, updated_at) == Ordering::Greater
+            });
+        }
+
+        let mut watermark = self.watermark.clone();
+
+        for transaction in transactions {
+            let status = status_of(&transaction);
+
+            let is_new = match self.statuses.get(&transaction.transaction_id) {
+                Some(previous) => previous.status != status,
+                None => !self.watermark.has_seen(&transaction),
+            };
+
+            // Completed deposits still change once they are credited.
+            let is_settled = transaction.is_final()
+                && (transaction.is_credited() || transaction.status != CryptoTransactionStatus::Completed);
+
+            watermark.advance(&transaction);
+
+            if is_settled && self.watermark.has_seen(&transaction) {
+                self.statuses.remove(&transaction.transaction_id);
+            } else {
+                self.statuses.insert(transaction.transaction_id.clone(), SeenStatus {
+                    status,
+                    updated_at: transaction.updated_at.clone(),
+                    is_settled,
+                });
+            }
+
+            if is_new {
+                self.pending.push_back(transaction);
+            }
+        }
+
+        self.uncommitted = Some(watermark);
+    }
+}
+
+impl Stream for DepositWatch {
+    type Item = EversendResult<CryptoTransaction, WatchDepositsError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(transaction) = this.pending.pop_front() {
+                return Poll::Ready(Some(Ok(transaction)));
+            }
+
+            if let Some(watermark) = this.uncommitted.take() {
+                if watermark != this.watermark {
+                    // On failure the next fetch starts from the previous watermark, and its
+                    // watermark, which covers this one, is written instead.
+                    if let Some(path) = &this.path {
+                        if let Err(err) = watermark.save(path) {
+                            return Poll::Ready(Some(Err(WatchDepositsError::Persist(err).into())));
+                        }
+                    }
+
+                    this.watermark = watermark;
+                }
+            }
+
+            if let Some(sleep) = &mut this.sleep {
+                ready!(sleep.as_mut().poll(cx));
+                this.sleep = None;
+            }
+
+            let fetch = this.fetch.get_or_insert_with(|| {
+                let eversend = this.eversend.clone();
+                let updated_at = this.watermark.updated_at().map(str::to_string);
+
+                Box::pin(fetch_since(eversend, updated_at))
+            });
+
+            let result = ready!(fetch.as_mut().poll(cx));
+            this.fetch = None;
+            this.sleep = Some(Box::pin(tokio::time::sleep(this.interval)));
+
+            match result {
+                Ok(transactions) => this.enqueue(transactions),
+                Err(err) => return Poll::Ready(Some(Err(err.map_operation(|err| match err {})))),
+            }
+        }
+    }
+}
+
+/// Fetches pages of transactions until every transaction of one was updated before `updated_at`, or
+/// there are no more.
+///
+/// The API does not document the order transactions are listed in, so a page reaching back to
+/// `updated_at` does not mean later pages were all seen: a deposit created long ago may have been
+/// updated since.
+async fn fetch_since(
+    eversend: Eversend,
+    updated_at: Option<String>
+) -> EversendResult<Vec<CryptoTransaction>, FetchCryptoTransactionsError> {
+    let crypto = eversend.crypto();
+    let mut transactions = Vec::new();
+    let mut page_number = 1;
+
+    loop {
+        let page = crypto.fetch_crypto_transactions_page(page_number).await?;
+        let has_next_page = page.has_next_page() && !page.transactions.is_empty();
+
+        let is_behind_watermark = updated_at.as_deref().is_some_and(|updated_at| {
+            page.transactions
+                .iter()
+                .all(|transaction| compare_timestamps(&transaction.updated_at, updated_at) == Ordering::Less)
+        });
+
+        transactions.extend(page.transactions);
+
+        if is_behind_watermark || !has_next_page {
+            return Ok(transactions);
+        }
+
+        page_number += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, ApiToken, ClientSecret, crypto::CryptoTransactionStatus};
+
+    use super::*;
+    use futures::StreamExt;
+    use mockito::{self, mock, Matcher, Mock};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend(prefix: &str) -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), prefix))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn transaction(transaction_id: &str, status: &str, updated_at: &str) -> serde_json::Value {
+        json!({
+            "id": 2,
+            "transactionId": transaction_id,
+            "accountId": 1,
+            "addressId": 11,
+            "amount": "10",
+            "meta": {
+                "date": "2022-10-18",
+                "fees": "0.00",
+                "type": "Blockchain",
+                "amount": 10,
+                "source": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                "charges": 0,
+                "country": "NG",
+                "currency": "XDC",
+                "toppedUp": true,
+                "username": "eversendb2btest@gmail.com",
+                "processor": "Fireblocks",
+                "actualCoin": "TRX_USDC_6NU3",
+                "totalToPay": 10,
+                "eversendRef": "3557276",
+                "creationDate": "2022-10-18T13:13:47+00:00",
+                "fireblocksId": "6f014008-47bd-422a-9a26-25f32f79dc60",
+                "toppedUpDate": "2022-10-18T13:13:48+00:00",
+                "blockchainHash": "972cca5c28496d8f82c3a5d0fdc02110a762a25c1823d76275901554f8752726",
+                "blockchainStatus": status,
+                "blockchainSubStatus": "PENDING_BLOCKCHAIN_CONFIRMATIONS"
+            },
+            "status": status,
+            "subStatus": "PENDING_BLOCKCHAIN_CONFIRMATIONS",
+            "createdAt": "2022-10-19T11:28:26.480Z",
+            "updatedAt": updated_at,
+            "address": {
+                "address": "TEduPZAcEA3GB8UGP3xuEE7i4ZvytK49PQ",
+                "coin": "TRX_USDC_6NU3",
+                "purpose": "Payment for coffee",
+                "ownerName": "Emmanuel Chilaka",
+                "destinationAddressDescription": "emmanuel@eversend.co",
+                "createdAt": "2022-10-18T12:58:51.093Z",
+                "updatedAt": "2022-10-18T12:58:51.093Z",
+            }
+        })
+    }
+
+    fn mock_transactions(prefix: &str, transactions: Vec<serde_json::Value>) -> Mock {
+        let total = transactions.len();
+
+        mock_page(prefix, 1, 10, total, transactions).create()
+    }
+
+    fn mock_page(prefix: &str, page: usize, limit: usize, total: usize, transactions: Vec<serde_json::Value>) -> Mock {
+        mock("GET", format!("/{}/crypto/transactions", prefix).as_str())
+            .match_query(Matcher::UrlEncoded("page".into(), page.to_string()))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "transactions": transactions,
+                        "total": total,
+                        "limit": limit,
+                        "page": page
+                    },
+                    "success": true
+                }).to_string(),
+            )
+    }
+
+    async fn next_within(watch: &mut DepositWatch, millis: u64) -> Option<CryptoTransaction> {
+        tokio::time::timeout(Duration::from_millis(millis), watch.next())
+            .await
+            .ok()
+            .flatten()
+            .map(Result::unwrap)
+    }
+
+    #[tokio::test]
+    async fn it_emits_each_transaction_once_per_status() {
+        let eversend = eversend("watch-deposits-status");
+
+        let confirming = mock_transactions("watch-deposits-status", vec![
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+            transaction("BP1", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+        ]);
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10));
+
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP1");
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP2");
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        drop(confirming);
+        let _completed = mock_transactions("watch-deposits-status", vec![
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+            transaction("BP1", "COMPLETED", "2022-10-19T11:28:26.481Z"),
+        ]);
+
+        let completed = next_within(&mut watch, 1000).await.unwrap();
+        assert_eq!(completed.transaction_id, "BP1");
+        assert_eq!(completed.status, CryptoTransactionStatus::Completed);
+        assert!(next_within(&mut watch, 50).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_pages_back_to_the_watermark() {
+        let eversend = eversend("watch-deposits-pages");
+
+        let first = mock_page("watch-deposits-pages", 1, 1, 2, vec![
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+        ]).create();
+        let second = mock_page("watch-deposits-pages", 2, 1, 2, vec![
+            transaction("BP1", "COMPLETED", "2022-10-19T11:28:26.481Z"),
+        ]).create();
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10));
+
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP1");
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP2");
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        // BP1 is final and behind the watermark, so it no longer needs tracking.
+        assert!(!watch.statuses.contains_key("BP1"));
+        assert!(watch.statuses.contains_key("BP2"));
+
+        drop(first);
+        drop(second);
+        let _first = mock_page("watch-deposits-pages", 1, 2, 3, vec![
+            transaction("BP3", "CONFIRMING", "2022-10-19T11:31:00.000Z"),
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+        ]).create();
+        let _second = mock_page("watch-deposits-pages", 2, 2, 5, vec![
+            transaction("BP1", "COMPLETED", "2022-10-19T11:28:26.481Z"),
+            transaction("BP0", "COMPLETED", "2022-10-19T11:20:00.000Z"),
+        ]).create();
+        let third = mock_page("watch-deposits-pages", 3, 2, 5, vec![
+            transaction("BP-1", "COMPLETED", "2022-10-19T11:10:00.000Z"),
+        ]).expect(0).create();
+
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP3");
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        third.assert();
+    }
+
+    #[tokio::test]
+    async fn it_pages_past_the_watermark_to_old_deposits_that_changed() {
+        let eversend = eversend("watch-deposits-changed");
+
+        let first = mock_transactions("watch-deposits-changed", vec![
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+            transaction("BP1", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+            transaction("BP0", "COMPLETED", "2022-10-19T11:20:00.000Z"),
+        ]);
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10));
+
+        for _ in 0..3 {
+            next_within(&mut watch, 1000).await.unwrap();
+        }
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        // Listed by creation, so BP1, updated after the watermark, is on the second page.
+        drop(first);
+        let _first = mock_page("watch-deposits-changed", 1, 1, 3, vec![
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:30:00.000Z"),
+        ]).create();
+        let _second = mock_page("watch-deposits-changed", 2, 1, 3, vec![
+            transaction("BP1", "COMPLETED", "2022-10-19T11:35:00.000Z"),
+        ]).create();
+        let third = mock_page("watch-deposits-changed", 3, 1, 3, vec![
+            transaction("BP0", "COMPLETED", "2022-10-19T11:20:00.000Z"),
+        ]).expect_at_least(1).create();
+
+        let changed = next_within(&mut watch, 1000).await.unwrap();
+        assert_eq!(changed.transaction_id, "BP1");
+        assert_eq!(changed.status, CryptoTransactionStatus::Completed);
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        third.assert();
+    }
+
+    #[tokio::test]
+    async fn it_keeps_the_watermark_until_it_is_written() {
+        let directory = std::env::temp_dir().join(format!("eversend-deposit-watermark-dir-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        let path = directory.join("deposits.json");
+
+        let eversend = eversend("watch-deposits-unwritten");
+
+        let _transactions = mock_transactions("watch-deposits-unwritten", vec![
+            transaction("BP1", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+        ]);
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10)).persist_to(&path).unwrap();
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP1");
+
+        // The directory is missing, so the watermark cannot be written.
+        let failed = tokio::time::timeout(Duration::from_millis(1000), watch.next()).await.unwrap().unwrap();
+        assert!(matches!(failed, Err(EversendError::Operation(WatchDepositsError::Persist(_)))));
+        assert_eq!(watch.watermark(), &DepositWatermark::default());
+
+        std::fs::create_dir_all(&directory).unwrap();
+        assert!(next_within(&mut watch, 100).await.is_none());
+
+        let watermark = DepositWatermark::load(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&directory);
+
+        assert_eq!(watermark.updated_at(), Some("2022-10-19T11:28:26.481Z"));
+        assert_eq!(&watermark, watch.watermark());
+    }
+
+    #[tokio::test]
+    async fn it_resumes_from_the_persisted_watermark() {
+        let path = std::env::temp_dir().join(format!("eversend-deposit-watermark-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let eversend = eversend("watch-deposits-persist");
+
+        let first = mock_transactions("watch-deposits-persist", vec![
+            transaction("BP1", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+        ]);
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10)).persist_to(&path).unwrap();
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP1");
+        assert!(next_within(&mut watch, 50).await.is_none());
+        drop(watch);
+
+        drop(first);
+        let _second = mock_transactions("watch-deposits-persist", vec![
+            transaction("BP1", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+            transaction("BP3", "CONFIRMING", "2022-10-19T11:28:26.481Z"),
+            transaction("BP2", "CONFIRMING", "2022-10-19T11:28:26+01:00"),
+        ]);
+
+        let mut watch = eversend.crypto().watch_deposits(Duration::from_millis(10)).persist_to(&path).unwrap();
+        assert_eq!(next_within(&mut watch, 1000).await.unwrap().transaction_id, "BP3");
+        assert!(next_within(&mut watch, 50).await.is_none());
+
+        let watermark = DepositWatermark::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(watermark.updated_at(), Some("2022-10-19T11:28:26.481Z"));
+        assert_eq!(&watermark, watch.watermark());
+    }
+}
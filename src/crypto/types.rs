@@ -1,7 +1,15 @@
-mod asset_chains;
+mod asset_chain;
+mod chain;
 mod crypto_address;
 mod crypto_transaction;
+mod crypto_transaction_status;
+mod deposit_watermark;
+mod swept_deposits;
 
-pub use asset_chains::*;
+pub use asset_chain::*;
+pub use chain::*;
 pub use crypto_address::*;
 pub use crypto_transaction::*;
+pub use crypto_transaction_status::*;
+pub use deposit_watermark::*;
+pub use swept_deposits::*;
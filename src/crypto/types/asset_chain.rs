@@ -0,0 +1,58 @@
+use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::Chain;
+
+/// A chain a coin can be held on, from [`FetchAssetChains`](crate::crypto::FetchAssetChains).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AssetChain {
+    /// The network of the chain.
+    pub network: Chain,
+
+    /// The asset to create addresses for, e.g. `TRX_USDT_S2UZ`.
+    pub asset_id: AssetId,
+
+    /// The name of the chain as sent by the API, e.g. `TRON (TRC20)`.
+    pub display_name: String,
+}
+
+impl AssetChain {
+    pub(crate) fn new(display_name: String, asset_id: AssetId) -> Self {
+        Self {
+            network: Chain::from_display_name(&display_name),
+            asset_id,
+            display_name,
+        }
+    }
+}
+
+/// The ID of a crypto asset on a chain, e.g. `TRX_USDT_S2UZ`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AssetId(String);
+
+impl AssetId {
+    /// The ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for AssetId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for AssetId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for AssetId {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
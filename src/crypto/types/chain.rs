@@ -0,0 +1,103 @@
+use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::core::open_enum_schema;
+
+/// A blockchain network crypto assets are held on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Chain {
+    BinanceSmartChain,
+    Ethereum,
+    Polygon,
+    Solana,
+    Tron,
+
+    /// A network not known to this version of the SDK, by the name the API gave it.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+open_enum_schema!(Chain, ["binance_smart_chain", "ethereum", "polygon", "solana", "tron"]);
+
+impl Chain {
+    /// The network of a chain named by the API, e.g. `Binance Smart Chain (BEP20)`.
+    ///
+    /// Matches whole words only, so e.g. `Electroneum` is not taken for TRON.
+    pub fn from_display_name(name: &str) -> Self {
+        let lowercase = name.to_lowercase();
+        let words: Vec<&str> = lowercase
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let mentions = |names: &[&str]| {
+            names.iter().any(|candidate| {
+                let candidate: Vec<&str> = candidate.split(' ').collect();
+                words.windows(candidate.len()).any(|window| window == candidate.as_slice())
+            })
+        };
+
+        if mentions(&["binance", "bnb smart chain", "bsc"]) {
+            Self::BinanceSmartChain
+        } else if mentions(&["ethereum"]) {
+            Self::Ethereum
+        } else if mentions(&["polygon", "matic"]) {
+            Self::Polygon
+        } else if mentions(&["solana"]) {
+            Self::Solana
+        } else if mentions(&["tron"]) {
+            Self::Tron
+        } else {
+            Self::Unknown(name.to_string())
+        }
+    }
+}
+
+impl Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::BinanceSmartChain => "BNB Smart Chain",
+            Self::Ethereum => "Ethereum",
+            Self::Polygon => "Polygon",
+            Self::Solana => "Solana",
+            Self::Tron => "TRON",
+            Self::Unknown(name) => name,
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_recognizes_networks_by_display_name() {
+        assert_eq!(Chain::from_display_name("Binance Smart Chain (BEP20)"), Chain::BinanceSmartChain);
+        assert_eq!(Chain::from_display_name("Ethereum (ERC20)"), Chain::Ethereum);
+        assert_eq!(Chain::from_display_name("TRON (TRC20)"), Chain::Tron);
+        assert_eq!(Chain::from_display_name("Polygon"), Chain::Polygon);
+        assert_eq!(Chain::from_display_name("Solana (SPL)"), Chain::Solana);
+        assert_eq!(Chain::from_display_name("Avalanche C-Chain"), Chain::Unknown(String::from("Avalanche C-Chain")));
+        assert_eq!(Chain::from_display_name("BNB Smart Chain"), Chain::BinanceSmartChain);
+        assert_eq!(Chain::from_display_name("Polygon/MATIC"), Chain::Polygon);
+    }
+
+    #[test]
+    fn it_does_not_recognize_networks_within_other_words() {
+        assert_eq!(Chain::from_display_name("Electroneum"), Chain::Unknown(String::from("Electroneum")));
+        assert_eq!(Chain::from_display_name("Patron Chain"), Chain::Unknown(String::from("Patron Chain")));
+        assert_eq!(Chain::from_display_name("BSCX"), Chain::Unknown(String::from("BSCX")));
+        assert_eq!(Chain::from_display_name("BNB Smart"), Chain::Unknown(String::from("BNB Smart")));
+    }
+
+    #[test]
+    fn it_serializes_unknown_networks_by_name() {
+        assert_eq!(serde_json::to_value(Chain::Tron).unwrap(), "tron");
+        assert_eq!(serde_json::to_value(Chain::Unknown(String::from("Avalanche"))).unwrap(), "Avalanche");
+        assert_eq!(serde_json::from_value::<Chain>("binance_smart_chain".into()).unwrap(), Chain::BinanceSmartChain);
+        assert_eq!(serde_json::from_value::<Chain>("Avalanche".into()).unwrap(), Chain::Unknown(String::from("Avalanche")));
+    }
+}
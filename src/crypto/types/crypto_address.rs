@@ -1,6 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+use crate::crypto::{address::{self, Address, AddressError}, Chain};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CryptoAddress {
     pub address: String,
 
@@ -19,4 +22,15 @@ pub struct CryptoAddress {
 
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CryptoAddress {
+    /// Validates the address for the chain it was created on, see [`address::validate`].
+    pub fn validate(&self, chain: &Chain) -> Result<Address, AddressError> {
+        address::validate(chain, &self.address)
+    }
 }
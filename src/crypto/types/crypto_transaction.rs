@@ -1,8 +1,12 @@
 use serde::Deserialize;
 
-use super::CryptoAddress;
+use crate::Decimal;
 
-#[derive(Deserialize)]
+use super::{CryptoAddress, CryptoTransactionStatus, CryptoTransactionSubStatus};
+
+/// A crypto deposit to one of the merchant's addresses.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CryptoTransaction {
     #[serde(rename = "accountId")]
     pub account_id: u32,
@@ -12,7 +16,8 @@ pub struct CryptoTransaction {
     #[serde(rename = "addressId")]
     pub address_id: u32,
 
-    pub amount: String,
+    /// The amount received, in [`TransactionMetaData::currency`].
+    pub amount: Decimal,
 
     pub id: u32,
 
@@ -21,35 +26,52 @@ pub struct CryptoTransaction {
 
     pub meta: TransactionMetaData,
 
-    pub status: String,
+    pub status: CryptoTransactionStatus,
 
     #[serde(rename = "subStatus")]
-    pub sub_status: String,
+    pub sub_status: CryptoTransactionSubStatus,
 
     #[serde(rename = "createdAt")]
     pub created_at: String,
 
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CryptoTransaction {
+    /// Returns `true` if the status will not change anymore.
+    pub fn is_final(&self) -> bool {
+        self.status.is_final()
+    }
+
+    /// Returns `true` once the deposit was credited to the merchant's wallet.
+    pub fn is_credited(&self) -> bool {
+        self.meta.topped_up
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransactionMetaData {
     #[serde(rename = "actualCoin")]
     pub actual_coin: String,
 
-    pub amount: u32,
+    pub amount: Decimal,
 
     #[serde(rename = "blockchainHash")]
     pub blockchain_hash: String,
 
     #[serde(rename = "blockchainStatus")]
-    pub blockchain_status: String,
+    pub blockchain_status: CryptoTransactionStatus,
 
     #[serde(rename = "blockchainSubStatus")]
-    pub blockchain_sub_status: String,
+    pub blockchain_sub_status: CryptoTransactionSubStatus,
 
-    pub charges: u32,
+    pub charges: Decimal,
 
     pub country: String,
 
@@ -63,7 +85,7 @@ pub struct TransactionMetaData {
     #[serde(rename = "eversendRef")]
     pub eversend_ref: String,
 
-    pub fees: String,
+    pub fees: Decimal,
 
     #[serde(rename = "fireblocksId")]
     pub fireblocks_id: String,
@@ -72,6 +94,7 @@ pub struct TransactionMetaData {
 
     pub source: String,
 
+    /// Whether the deposit was credited to the merchant's wallet.
     #[serde(rename = "toppedUp")]
     pub topped_up: bool,
 
@@ -79,10 +102,14 @@ pub struct TransactionMetaData {
     pub topped_up_date: String,
 
     #[serde(rename = "totalToPay")]
-    pub total_to_pay: u32,
+    pub total_to_pay: Decimal,
 
     #[serde(rename = "type")]
     pub transaction_type: String,
 
     pub username: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::core::open_enum_schema;
+
+/// Where a [`CryptoTransaction`](crate::crypto::CryptoTransaction) is in its lifecycle.
+///
+/// Deposits are `SUBMITTED` when detected, `CONFIRMING` while the chain adds confirmations, and end
+/// either `COMPLETED` or, if they never make it, `FAILED`, `CANCELLED`, `REJECTED` or `BLOCKED`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CryptoTransactionStatus {
+    /// The deposit was detected and is waiting to be processed.
+    Submitted,
+
+    /// The deposit is on chain and waiting for enough confirmations.
+    Confirming,
+
+    /// The deposit is confirmed.
+    Completed,
+
+    /// The deposit could not be processed.
+    Failed,
+
+    /// The deposit was cancelled before completing.
+    Cancelled,
+
+    /// The deposit was rejected, e.g. by a policy rule.
+    Rejected,
+
+    /// The deposit was blocked, e.g. by AML screening.
+    Blocked,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+open_enum_schema!(CryptoTransactionStatus, ["SUBMITTED", "CONFIRMING", "COMPLETED", "FAILED", "CANCELLED", "REJECTED", "BLOCKED"]);
+
+impl CryptoTransactionStatus {
+    /// Returns `true` if the status will not change anymore.
+    ///
+    /// [`Unknown`](Self::Unknown) statuses are not considered final.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled | Self::Rejected | Self::Blocked)
+    }
+
+    /// The status as sent by the API, e.g. `CONFIRMING`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Submitted => "SUBMITTED",
+            Self::Confirming => "CONFIRMING",
+            Self::Completed => "COMPLETED",
+            Self::Failed => "FAILED",
+            Self::Cancelled => "CANCELLED",
+            Self::Rejected => "REJECTED",
+            Self::Blocked => "BLOCKED",
+            Self::Unknown(status) => status,
+        }
+    }
+}
+
+impl Display for CryptoTransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Details on a [`CryptoTransactionStatus`], e.g. why a deposit failed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CryptoTransactionSubStatus {
+    /// The deposit is waiting for more blocks to be added on top of it.
+    PendingBlockchainConfirmations,
+
+    /// The deposit has enough confirmations.
+    Confirmed,
+
+    /// The deposit was rejected by the blockchain.
+    RejectedByBlockchain,
+
+    /// The deposit was blocked by AML screening.
+    BlockedByPolicy,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+open_enum_schema!(CryptoTransactionSubStatus, ["PENDING_BLOCKCHAIN_CONFIRMATIONS", "CONFIRMED", "REJECTED_BY_BLOCKCHAIN", "BLOCKED_BY_POLICY"]);
+
+impl CryptoTransactionSubStatus {
+    /// The sub-status as sent by the API, e.g. `PENDING_BLOCKCHAIN_CONFIRMATIONS`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::PendingBlockchainConfirmations => "PENDING_BLOCKCHAIN_CONFIRMATIONS",
+            Self::Confirmed => "CONFIRMED",
+            Self::RejectedByBlockchain => "REJECTED_BY_BLOCKCHAIN",
+            Self::BlockedByPolicy => "BLOCKED_BY_POLICY",
+            Self::Unknown(sub_status) => sub_status,
+        }
+    }
+}
+
+impl Display for CryptoTransactionSubStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_statuses_as_sent_by_the_api() {
+        for status in ["SUBMITTED", "CONFIRMING", "COMPLETED", "FAILED", "CANCELLED", "REJECTED", "BLOCKED", "QUEUED"] {
+            let parsed = serde_json::from_value::<CryptoTransactionStatus>(status.into()).unwrap();
+
+            assert_eq!(parsed.as_str(), status);
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), status);
+        }
+
+        assert_eq!(
+            serde_json::from_value::<CryptoTransactionSubStatus>("PENDING_BLOCKCHAIN_CONFIRMATIONS".into()).unwrap(),
+            CryptoTransactionSubStatus::PendingBlockchainConfirmations
+        );
+        assert!(CryptoTransactionStatus::Completed.is_final());
+        assert!(!CryptoTransactionStatus::Confirming.is_final());
+        assert!(!CryptoTransactionStatus::Unknown(String::from("QUEUED")).is_final());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{cmp::Ordering, collections::BTreeMap, fs, io, path::Path};
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{core::write_atomically, crypto::CryptoTransaction};
+
+/// How far a [`DepositWatch`](crate::crypto::DepositWatch) has got, so it can resume after a
+/// restart without emitting the same deposits again.
+///
+/// Made of the latest `updatedAt` seen and the statuses of the transactions updated at that
+/// moment, which tells apart transactions updated within the same timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositWatermark {
+    updated_at: Option<String>,
+    statuses: BTreeMap<String, String>,
+}
+
+impl DepositWatermark {
+    /// Reads a watermark written by [`save`](Self::save). A missing file gives an empty watermark.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(json) => Ok(serde_json::from_slice(&json)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes the watermark, replacing the file atomically so a crash never leaves a truncated
+    /// one behind.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        write_atomically(path, &serde_json::to_vec(self)?)
+    }
+
+    /// The latest `updatedAt` seen, if any transaction was.
+    pub fn updated_at(&self) -> Option<&str> {
+        self.updated_at.as_deref()
+    }
+
+    /// Returns `true` if the transaction, in its current status, is at or before the watermark.
+    pub fn has_seen(&self, transaction: &CryptoTransaction) -> bool {
+        let Some(updated_at) = &self.updated_at else {
+            return false;
+        };
+
+        match compare_timestamps(&transaction.updated_at, updated_at) {
+            Ordering::Less => true,
+            Ordering::Equal => self.statuses.get(&transaction.transaction_id) == Some(&status_of(transaction)),
+            Ordering::Greater => false,
+        }
+    }
+
+    /// Moves the watermark up to the transaction, if it was updated later.
+    pub(crate) fn advance(&mut self, transaction: &CryptoTransaction) {
+        let ordering = self
+            .updated_at
+            .as_deref()
+            .map_or(Ordering::Greater, |updated_at| compare_timestamps(&transaction.updated_at, updated_at));
+
+        match ordering {
+            Ordering::Less => return,
+            Ordering::Equal => {},
+            Ordering::Greater => {
+                self.updated_at = Some(transaction.updated_at.clone());
+                self.statuses.clear();
+            },
+        }
+
+        self.statuses.insert(transaction.transaction_id.clone(), status_of(transaction));
+    }
+}
+
+/// The status and sub-status of a transaction, which change as it progresses, and whether it was
+/// credited, which may change after it is `COMPLETED`.
+pub(crate) fn status_of(transaction: &CryptoTransaction) -> String {
+    match transaction.is_credited() {
+        true => format!("{}/{}/credited", transaction.status, transaction.sub_status),
+        false => format!("{}/{}", transaction.status, transaction.sub_status),
+    }
+}
+
+/// Compares RFC 3339 timestamps by instant, or as strings if either cannot be parsed.
+pub(crate) fn compare_timestamps(a: &str, b: &str) -> Ordering {
+    match (DateTime::parse_from_rfc3339(a), DateTime::parse_from_rfc3339(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeSet, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::write_atomically;
+
+/// The deposits [`SweepDeposit`](crate::crypto::SweepDeposit) started exchanging, by transaction
+/// ID, so none is exchanged twice.
+///
+/// Kept in memory by default. Use [`load`](Self::load) to keep it in a file, or seed it from
+/// elsewhere, e.g. a database, with [`FromIterator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweptDeposits {
+    transaction_ids: BTreeSet<String>,
+
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl SweptDeposits {
+    /// Reads the deposits stored at `path`, and stores each deposit recorded from now on there
+    /// before it is exchanged. A missing file gives an empty record.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+
+        let mut swept = match fs::read(&path) {
+            Ok(json) => serde_json::from_slice(&json)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => return Err(err),
+        };
+
+        swept.path = Some(path);
+
+        Ok(swept)
+    }
+
+    /// Returns `true` if the deposit of the transaction was recorded.
+    pub fn contains(&self, transaction_id: &str) -> bool {
+        self.transaction_ids.contains(transaction_id)
+    }
+
+    /// The transaction IDs of the deposits recorded.
+    pub fn transaction_ids(&self) -> impl Iterator<Item = &str> {
+        self.transaction_ids.iter().map(String::as_str)
+    }
+
+    /// Records the deposit of a transaction, writing the record back to its file if it has one.
+    pub(crate) fn insert(&mut self, transaction_id: &str) -> io::Result<()> {
+        let mut transaction_ids = self.transaction_ids.clone();
+        transaction_ids.insert(transaction_id.to_string());
+
+        self.replace(transaction_ids)
+    }
+
+    /// Forgets the deposit of a transaction, as it was certainly not exchanged.
+    pub(crate) fn remove(&mut self, transaction_id: &str) -> io::Result<()> {
+        let mut transaction_ids = self.transaction_ids.clone();
+        transaction_ids.remove(transaction_id);
+
+        self.replace(transaction_ids)
+    }
+
+    /// Writes the transaction IDs to the file, if there is one, and only then keeps them, so the
+    /// record in memory never gets ahead of the one on disk.
+    fn replace(&mut self, transaction_ids: BTreeSet<String>) -> io::Result<()> {
+        let replacement = Self {
+            transaction_ids,
+            path: None,
+        };
+
+        if let Some(path) = &self.path {
+            write_atomically(path, &serde_json::to_vec(&replacement)?)?;
+        }
+
+        self.transaction_ids = replacement.transaction_ids;
+
+        Ok(())
+    }
+}
+
+impl FromIterator<String> for SweptDeposits {
+    fn from_iter<I: IntoIterator<Item = String>>(transaction_ids: I) -> Self {
+        Self {
+            transaction_ids: transaction_ids.into_iter().collect(),
+            path: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_keeps_the_record_unchanged_when_it_cannot_be_written() {
+        let directory = std::env::temp_dir().join(format!("eversend-swept-deposits-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let path = directory.join("swept.json");
+
+        let mut swept = SweptDeposits::load(&path).unwrap();
+
+        assert!(swept.insert("BP1").is_err());
+        assert!(!swept.contains("BP1"));
+
+        fs::create_dir_all(&directory).unwrap();
+        swept.insert("BP1").unwrap();
+        swept.insert("BP2").unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(swept.remove("BP1").is_err());
+        assert!(swept.contains("BP1"));
+        assert_eq!(swept.transaction_ids().collect::<Vec<_>>(), ["BP1", "BP2"]);
+    }
+}
@@ -1,12 +1,22 @@
-use crate::{accounts::Accounts, auth::Auth, beneficiaries::Beneficiaries, collections::Collections, core::{ApiToken, ClientId, EversendError, BASE_URL}, crypto::Crypto, exchange::Exchange, payouts::Payouts, transactions::Transactions, wallets::Wallets, ClientSecret};
+use std::{sync::Arc, time::Duration};
+
+use crate::{accounts::Accounts, auth::Auth, beneficiaries::Beneficiaries, collections::Collections, core::{ApiClient, ApiToken, ClientId, EversendError, BASE_URL}, crypto::{Crypto, DepositAddresses}, exchange::{Exchange, RateCache}, payouts::{CreateQuotationResponse, Payouts}, reference_data::{ReferenceData, ReferenceDataCache, ReferenceDataOptions}, transactions::Transactions, wallets::Wallets, ClientSecret};
 
 /// The Eversend client.
+///
+/// Clones are cheap and share the same connection pool, request rate limit, [`ReferenceData`] cache,
+/// [`RateCache`] and known crypto deposit addresses.
+#[derive(Clone)]
 pub struct Eversend {
     api_token: Option<ApiToken>,
     base_url: String,
-    client: reqwest::Client,
+    client: ApiClient,
     client_id: ClientId,
     client_secret: ClientSecret,
+    deposit_addresses: Arc<DepositAddresses>,
+    exchange_rates: Arc<RateCache>,
+    payout_quote_lifetime: Duration,
+    reference_data: Arc<ReferenceDataCache>,
 }
 
 impl Eversend {
@@ -24,7 +34,7 @@ impl Eversend {
     }
 
     pub fn base_url(&self) -> &str {
-        &self.base_url.as_str()
+        self.base_url.as_str()
     }
 
     pub fn client_secret(&self) -> &ClientSecret {
@@ -35,10 +45,34 @@ impl Eversend {
         &self.client_id
     }
 
-    pub(crate) fn client(&self) -> &reqwest::Client {
+    pub(crate) fn client(&self) -> &ApiClient {
         &self.client
     }
 
+    /// Returns a clone of the client authenticated with another API token.
+    pub(crate) fn with_api_token(&self, api_token: ApiToken) -> Eversend {
+        Eversend {
+            api_token: Some(api_token),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn deposit_addresses(&self) -> &DepositAddresses {
+        &self.deposit_addresses
+    }
+
+    pub(crate) fn exchange_rates(&self) -> &RateCache {
+        &self.exchange_rates
+    }
+
+    pub(crate) fn payout_quote_lifetime(&self) -> Duration {
+        self.payout_quote_lifetime
+    }
+
+    pub(crate) fn reference_data_cache(&self) -> &ReferenceDataCache {
+        &self.reference_data
+    }
+
     pub fn api_token(&self) -> Result<&ApiToken, EversendError<()>> {
         if let Some(token) = &self.api_token {
             return Ok(token);
@@ -48,49 +82,54 @@ impl Eversend {
     }
 
     /// Returns an [`Auth`] instance.
-    pub fn auth(&self) -> Auth {
+    pub fn auth(&self) -> Auth<'_> {
         Auth::new(self)
     }
 
     /// Returns an [`Wallets`] instance.
-    pub fn wallets(&self) -> Wallets {
+    pub fn wallets(&self) -> Wallets<'_> {
         Wallets::new(self)
     }
 
     /// Returns an [`Accounts`] instance.
-    pub fn accounts(&self) -> Accounts {
+    pub fn accounts(&self) -> Accounts<'_> {
         Accounts::new(self)
     }
 
     /// Returns an [`Exchange`] instance.
-    pub fn exchange(&self) -> Exchange {
+    pub fn exchange(&self) -> Exchange<'_> {
         Exchange::new(self)
     }
 
     /// Returns an [`Beneficiaries`] instance.
-    pub fn beneficiaries(&self) -> Beneficiaries {
+    pub fn beneficiaries(&self) -> Beneficiaries<'_> {
         Beneficiaries::new(self)
     }
 
     /// Returns an [`Collections`] instance.
-    pub fn collections(&self) -> Collections {
+    pub fn collections(&self) -> Collections<'_> {
         Collections::new(self)
     }
 
     /// Returns an [`Crypto`] instance.
-    pub fn crypto(&self) -> Crypto {
+    pub fn crypto(&self) -> Crypto<'_> {
         Crypto::new(self)
     }
 
     /// Returns an [`Payouts`] instance.
-    pub fn payouts(&self) -> Payouts {
+    pub fn payouts(&self) -> Payouts<'_> {
         Payouts::new(self)
     }
 
     /// Returns an [`Transactions`] instance.
-    pub fn transactions(&self) -> Transactions {
+    pub fn transactions(&self) -> Transactions<'_> {
         Transactions::new(self)
     }
+
+    /// Returns a [`ReferenceData`] instance.
+    pub fn reference_data(&self) -> ReferenceData<'_> {
+        ReferenceData::new(self)
+    }
 }
 
 /// A builder for an Eversend client.
@@ -99,6 +138,10 @@ pub struct EversendBuilder<'a> {
     base_url: String,
     client_id: &'a ClientId,
     client_secret: &'a ClientSecret,
+    exchange_rate_freshness: Duration,
+    max_requests_per_second: Option<u32>,
+    payout_quote_lifetime: Duration,
+    reference_data_options: ReferenceDataOptions,
 }
 
 impl<'a> EversendBuilder<'a> {
@@ -109,6 +152,10 @@ impl<'a> EversendBuilder<'a> {
             base_url: BASE_URL.to_string(),
             client_id,
             client_secret,
+            exchange_rate_freshness: RateCache::DEFAULT_FRESHNESS,
+            max_requests_per_second: None,
+            payout_quote_lifetime: CreateQuotationResponse::DEFAULT_LIFETIME,
+            reference_data_options: ReferenceDataOptions::default(),
         }
     }
 
@@ -118,6 +165,7 @@ impl<'a> EversendBuilder<'a> {
             // .user_agent(concat!("eversend-rust/", env!("CARGO_PKG_VERSION")))
             .build()
             .unwrap();
+        let client = ApiClient::new(client, self.max_requests_per_second);
 
         Eversend {
             api_token: self.api_token,
@@ -125,32 +173,63 @@ impl<'a> EversendBuilder<'a> {
             client_secret: self.client_secret.to_owned(),
             client_id: self.client_id.to_owned(),
             client,
+            deposit_addresses: Arc::default(),
+            exchange_rates: Arc::new(RateCache::new(self.exchange_rate_freshness)),
+            payout_quote_lifetime: self.payout_quote_lifetime,
+            reference_data: Arc::new(ReferenceDataCache::new(self.reference_data_options)),
         }
     }
 
     /// Sets the base URL of the Eversend API that the client should point to.
-    pub fn set_base_url(mut self, base_url: &'a str) -> EversendBuilder {
+    pub fn set_base_url(mut self, base_url: &'a str) -> EversendBuilder<'a> {
         self.base_url = base_url.to_string();
         self
     }
 
     /// Sets the client secret of the Eversend API that the client should point to.
-    pub fn set_client_secret(mut self, client_secret: &'a ClientSecret) -> EversendBuilder {
+    pub fn set_client_secret(mut self, client_secret: &'a ClientSecret) -> EversendBuilder<'a> {
         self.client_secret = client_secret;
         self
     }
 
     /// Sets the client ID of the Eversend API that the client should point to.
-    pub fn set_client_id(mut self, client_id: &'a ClientId) -> EversendBuilder {
+    pub fn set_client_id(mut self, client_id: &'a ClientId) -> EversendBuilder<'a> {
         self.client_id = client_id;
         self
     }
 
     /// Sets the base URL of the Eversend API that the client should point to.
-    pub fn set_api_token(mut self, api_token: &'a ApiToken) -> EversendBuilder {
+    pub fn set_api_token(mut self, api_token: &'a ApiToken) -> EversendBuilder<'a> {
         self.api_token = Some(api_token.to_owned());
         self
     }
+
+    /// Sets how long a quoted exchange rate is used for estimates, see [`RateCache`]. Defaults to
+    /// 5 minutes.
+    pub fn set_exchange_rate_freshness(mut self, freshness: Duration) -> EversendBuilder<'a> {
+        self.exchange_rate_freshness = freshness;
+        self
+    }
+
+    /// Sets how many requests the client, and every client cloned from it, may send per second.
+    /// Requests over the limit wait for their turn. Defaults to unlimited, as does 0.
+    pub fn set_max_requests_per_second(mut self, max: u32) -> EversendBuilder<'a> {
+        self.max_requests_per_second = Some(max);
+        self
+    }
+
+    /// Sets how long a payout quotation is assumed to be valid when the API does not say, see
+    /// [`CreateQuotationResponse::into_handle`]. Defaults to 30 minutes.
+    pub fn set_payout_quote_lifetime(mut self, lifetime: Duration) -> EversendBuilder<'a> {
+        self.payout_quote_lifetime = lifetime;
+        self
+    }
+
+    /// Sets how long [`ReferenceData`] is cached, and whether it is persisted to disk.
+    pub fn set_reference_data_options(mut self, options: ReferenceDataOptions) -> EversendBuilder<'a> {
+        self.reference_data_options = options;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -2,11 +2,16 @@
 //!
 
 mod operations;
+mod rate_cache;
 mod types;
 
 pub use operations::*;
+pub use rate_cache::*;
 pub use types::*;
 
+/// The result of [`CreateExchange`], named apart from the [`Exchange`] resource.
+pub use types::Exchange as ExchangeResult;
+
 use crate::Eversend;
 
 /// Exchange.
@@ -20,4 +25,9 @@ impl<'a> Exchange<'a> {
     pub fn new(eversend: &'a Eversend) -> Self {
         Self { eversend }
     }
+
+    /// The rates of every pair quoted by this client and its clones.
+    pub fn rates(&self) -> &'a RateCache {
+        self.eversend.exchange_rates()
+    }
 }
@@ -1,5 +1,9 @@
 mod create_exchange;
 mod create_quotation;
+mod estimate_conversion;
+mod exchange_quote;
 
 pub use create_exchange::*;
 pub use create_quotation::*;
+pub use estimate_conversion::*;
+pub use exchange_quote::*;
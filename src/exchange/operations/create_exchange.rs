@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{exchange::{types::Exchange as ExchangeResult, Exchange}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateExchangeParams {
     /// Token from Create Quotation
     pub quotation_token: String
@@ -74,7 +75,7 @@ impl<'a> CreateExchange for Exchange<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<ExchangeResult>>()
+            .decode::<ApiResponseBody<ExchangeResult>, _>()
             .await?;
         Ok(response.data)
     }
@@ -138,7 +139,7 @@ mod tests {
             .unwrap();
 
         // Source ...
-        assert_eq!(exchange.source.amount, 100 as f64);
+        assert_eq!(exchange.source.amount, 100_f64);
         assert_eq!(exchange.source.currency, WalletId::from("UGX"));
         assert_eq!(exchange.source.balance.before, String::from("398.78"));
         assert_eq!(exchange.source.balance.after, String::from("398.78"));
@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{exchange::{types::Quotation, Exchange}, wallets::WalletId, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{exchange::{types::Quotation, Exchange}, wallets::WalletId, ApiResponseBody, EversendError, EversendResult, QuoteError, QuoteHandle, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateQuotationParams<'a> {
     /// Amount of source currency
     pub amount: String,
@@ -27,10 +28,22 @@ impl From<CreateQuotationError> for EversendError<CreateQuotationError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateQuotationResponse {
     pub expires: String,
     pub token: String,
     pub quotation: Quotation,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CreateQuotationResponse {
+    /// Converts the response into a [`QuoteHandle`] that tracks when the token expires.
+    pub fn into_handle(self) -> Result<QuoteHandle<Quotation>, QuoteError> {
+        QuoteHandle::from_rfc3339(self.token, self.quotation, &self.expires)
+    }
 }
 
 #[async_trait]
@@ -89,9 +102,11 @@ impl<'a> CreateQuotation for Exchange<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateQuotationResponse>>()
+            .decode::<ApiResponseBody<CreateQuotationResponse>, _>()
             .await?;
 
+        self.rates().record(&response.data.quotation);
+
         Ok(response.data)
     }
 }
@@ -155,7 +170,7 @@ mod tests {
         assert_eq!(response.expires, String::from("2022-08-30T16:09:53+00:00"));
         assert_eq!(response.token, String::from("eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9..."));
 
-        assert_eq!(response.quotation.base_amount, 100);
+        assert_eq!(response.quotation.base_amount, 100.0);
         assert_eq!(response.quotation.base_currency, WalletId::from("UGX"));
         assert_eq!(response.quotation.dest_currency, WalletId::from("USD"));
     }
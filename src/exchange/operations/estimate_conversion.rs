@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+
+use crate::{
+    exchange::{CreateQuotation, CreateQuotationError, CreateQuotationParams, Estimate, EstimateError, Exchange, ExchangeRate},
+    wallets::WalletId,
+    EversendResult,
+};
+
+/// Estimates conversions from cached exchange rates, for price display.
+///
+/// Estimates are not binding, see [`Estimate`]. Use [`CreateQuotation`] or
+/// [`ExchangeQuote`](crate::exchange::ExchangeQuote) to exchange money.
+#[async_trait]
+pub trait EstimateConversion {
+    /// Estimate a conversion from the last quoted rate of the pair, without calling the API.
+    ///
+    /// Fails if the pair was never quoted, or was last quoted longer ago than the freshness window
+    /// of the client's [`RateCache`](crate::exchange::RateCache).
+    ///
+    /// # Examples
+    /// ```
+    /// use eversend_rust_sdk::exchange::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::wallets::WalletId;
+    ///
+    /// let eversend = Eversend::new(
+    ///     &ClientId::from("sk_example_123456789"),
+    ///     &ClientSecret::from("sk_example_123456780")
+    /// );
+    ///
+    /// match eversend.exchange().estimate(1000.0, &WalletId::from("UGX"), &WalletId::from("KES")) {
+    ///     Ok(estimate) => println!("{}", estimate),
+    ///     Err(EstimateError::Unknown { .. } | EstimateError::Stale { .. }) => println!("price unavailable"),
+    /// }
+    /// ```
+    fn estimate(
+        &self,
+        amount: f64,
+        from: &WalletId,
+        to: &WalletId
+    ) -> Result<Estimate, EstimateError>;
+
+    /// Estimate a conversion from the cache, creating a quotation to learn the rate when it is
+    /// unknown or stale.
+    ///
+    /// The quotation is not used beyond its rate, which is cached for later estimates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::exchange::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::wallets::WalletId;
+    ///
+    /// # async fn run() -> EversendResult<(), CreateQuotationError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let estimate = eversend
+    ///         .exchange()
+    ///         .estimate_or_quote(1000.0, &WalletId::from("UGX"), &WalletId::from("KES"))
+    ///         .await?;
+    ///
+    ///     println!("{}", estimate);
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn estimate_or_quote(
+        &self,
+        amount: f64,
+        from: &WalletId,
+        to: &WalletId
+    ) -> EversendResult<Estimate, CreateQuotationError>;
+}
+
+#[async_trait]
+impl<'a> EstimateConversion for Exchange<'a> {
+    fn estimate(
+        &self,
+        amount: f64,
+        from: &WalletId,
+        to: &WalletId
+    ) -> Result<Estimate, EstimateError> {
+        self.rates().estimate(amount, from, to)
+    }
+
+    async fn estimate_or_quote(
+        &self,
+        amount: f64,
+        from: &WalletId,
+        to: &WalletId
+    ) -> EversendResult<Estimate, CreateQuotationError> {
+        if let Ok(estimate) = self.estimate(amount, from, to) {
+            return Ok(estimate);
+        }
+
+        let response = self
+            .create_quotation(&CreateQuotationParams {
+                amount: amount.to_string(),
+                from,
+                to,
+            })
+            .await?;
+
+        Ok(Estimate::new(amount, &ExchangeRate::from_quotation(&response.quotation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock};
+    use serde_json::json;
+    use tokio;
+
+    #[tokio::test]
+    async fn it_quotes_unknown_rates_once() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("POST", "/exchanges/quotation")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "expires": "2022-08-30T16:09:53+00:00",
+                        "token": "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9...",
+                        "quotation": {
+                            "baseCurrency": "UGX",
+                            "baseAmount": 1000,
+                            "baseWalletBefore": 5000.0,
+                            "baseWalletAfter": 4000.0,
+                            "destCurrency": "KES",
+                            "destAmount": 35.0,
+                            "destWalletBefore": 0.0,
+                            "destWalletAfter": null,
+                            "rate": 0.035
+                        }
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .expect(1)
+            .create();
+
+        let ugx = WalletId::from("UGX");
+        let kes = WalletId::from("KES");
+
+        assert!(matches!(eversend.exchange().estimate(1000.0, &ugx, &kes), Err(EstimateError::Unknown { .. })));
+
+        let quoted = eversend.exchange().estimate_or_quote(1000.0, &ugx, &kes).await.unwrap();
+        let cached = eversend.exchange().estimate_or_quote(2000.0, &ugx, &kes).await.unwrap();
+
+        assert_eq!(quoted.converted, 35.0);
+        assert_eq!(cached.converted, 70.0);
+        assert_eq!(eversend.exchange().estimate(2000.0, &ugx, &kes).unwrap(), cached);
+
+        mock.assert();
+    }
+}
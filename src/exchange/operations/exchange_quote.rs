@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{exchange::{types::{Exchange as ExchangeResult, Quotation}, CreateExchange, CreateExchangeParams, CreateQuotation, CreateQuotationParams, Exchange}, EversendError, EversendResult, QuoteError, QuoteHandle, RateDelta};
+
+/// An error returned from [`ExchangeQuote`].
+#[derive(Debug, Error)]
+pub enum ExchangeQuoteError {
+    #[error(transparent)]
+    Quote(#[from] QuoteError),
+}
+
+impl From<ExchangeQuoteError> for EversendError<ExchangeQuoteError> {
+    fn from(err: ExchangeQuoteError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+impl From<QuoteError> for EversendError<ExchangeQuoteError> {
+    fn from(err: QuoteError) -> Self {
+        Self::Operation(ExchangeQuoteError::Quote(err))
+    }
+}
+
+/// Quotes and executes exchanges through a [`QuoteHandle`], so an expired quotation is never used.
+#[async_trait]
+pub trait ExchangeQuote {
+    /// Creates a [`Quotation`] and wraps it in a [`QuoteHandle`].
+    ///
+    /// [Eversend Docs: Create a Quotation](https://eversend.readme.io/reference/create-quotation)
+    async fn quote(
+        &self,
+        params: &CreateQuotationParams<'_>
+    ) -> EversendResult<QuoteHandle<Quotation>, ExchangeQuoteError>;
+
+    /// Creates a fresh quotation for `params` and reports how the rate moved since `previous`.
+    ///
+    /// Useful once `previous` has expired, to decide whether to go ahead at the new rate.
+    async fn requote(
+        &self,
+        previous: &QuoteHandle<Quotation>,
+        params: &CreateQuotationParams<'_>
+    ) -> EversendResult<(QuoteHandle<Quotation>, RateDelta), ExchangeQuoteError>;
+
+    /// Executes the exchange for a quotation, refusing to do so once it has expired.
+    ///
+    /// [Eversend Docs: Create Exchange](https://eversend.readme.io/reference/create-exchange)
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::exchange::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::wallets::WalletId;
+    ///
+    /// # async fn run() -> EversendResult<(), ExchangeQuoteError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let params = CreateQuotationParams {
+    ///         amount: String::from("1000"),
+    ///         from: &WalletId::from("UGX"),
+    ///         to: &WalletId::from("KES")
+    ///     };
+    ///
+    ///     let mut handle = eversend.exchange().quote(&params).await?;
+    ///
+    ///     if handle.is_expired() {
+    ///         let (fresh, delta) = eversend.exchange().requote(&handle, &params).await?;
+    ///
+    ///         if delta.percentage().abs() > 1.0 {
+    ///             return Ok(());
+    ///         }
+    ///
+    ///         handle = fresh;
+    ///     }
+    ///
+    ///     let exchange = eversend.exchange().exchange_quote(&handle).await?;
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    async fn exchange_quote(
+        &self,
+        handle: &QuoteHandle<Quotation>
+    ) -> EversendResult<ExchangeResult, ExchangeQuoteError>;
+}
+
+#[async_trait]
+impl<'a> ExchangeQuote for Exchange<'a> {
+    async fn quote(
+        &self,
+        params: &CreateQuotationParams<'_>
+    ) -> EversendResult<QuoteHandle<Quotation>, ExchangeQuoteError> {
+        let response = self
+            .create_quotation(params)
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))?;
+
+        Ok(response.into_handle()?)
+    }
+
+    async fn requote(
+        &self,
+        previous: &QuoteHandle<Quotation>,
+        params: &CreateQuotationParams<'_>
+    ) -> EversendResult<(QuoteHandle<Quotation>, RateDelta), ExchangeQuoteError> {
+        let handle = self.quote(params).await?;
+
+        let delta = RateDelta {
+            previous: previous.quotation().rate,
+            current: handle.quotation().rate,
+        };
+
+        Ok((handle, delta))
+    }
+
+    async fn exchange_quote(
+        &self,
+        handle: &QuoteHandle<Quotation>
+    ) -> EversendResult<ExchangeResult, ExchangeQuoteError> {
+        let quotation_token = handle.token()?.to_string();
+
+        self
+            .create_exchange(&CreateExchangeParams { quotation_token })
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{ClientId, eversend::Eversend, wallets::WalletId, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock};
+    use serde_json::json;
+    use tokio;
+
+    fn quotation(rate: f64) -> Quotation {
+        Quotation {
+            base_amount: 100.0,
+            base_currency: WalletId::from("UGX"),
+            base_wallet_after: 398.78,
+            base_wallet_before: 498.78,
+            dest_amount: 100.0 * rate,
+            dest_currency: WalletId::from("KES"),
+            dest_wallet_after: None,
+            dest_wallet_before: 1.52,
+            rate,
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_refuses_to_exchange_an_expired_quotation() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("POST", "/exchanges")
+            .with_status(200)
+            .expect(0)
+            .create();
+
+        let handle = QuoteHandle::new(
+            String::from("some-test-token"),
+            quotation(0.03),
+            SystemTime::now() - Duration::from_secs(1)
+        );
+
+        let result = eversend
+            .exchange()
+            .exchange_quote(&handle)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(ExchangeQuoteError::Quote(QuoteError::Expired { .. })))
+        ));
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_reports_the_rate_delta_when_requoting() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("POST", "/exchanges/quotation")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data":{
+                        "expires":"2099-08-30T16:09:53+00:00",
+                        "token":"some-fresh-token",
+                        "quotation":{
+                            "baseCurrency":"UGX",
+                            "baseAmount":100,
+                            "baseWalletBefore":498.78,
+                            "baseWalletAfter":398.78,
+                            "destCurrency":"KES",
+                            "destAmount":3.3,
+                            "destWalletBefore":1.52,
+                            "destWalletAfter":null,
+                            "rate":0.033
+                        }
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create();
+
+        let previous = QuoteHandle::new(
+            String::from("some-stale-token"),
+            quotation(0.03),
+            SystemTime::now() - Duration::from_secs(1)
+        );
+
+        let (handle, delta) = eversend
+            .exchange()
+            .requote(
+                &previous,
+                &CreateQuotationParams {
+                    amount: String::from("100"),
+                    from: &WalletId::from("UGX"),
+                    to: &WalletId::from("KES")
+                }
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(handle.token().unwrap(), "some-fresh-token");
+        assert_eq!(delta.previous, 0.03);
+        assert_eq!(delta.current, 0.033);
+
+        mock.assert();
+    }
+}
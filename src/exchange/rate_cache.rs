@@ -0,0 +1,173 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use thiserror::Error;
+
+use crate::{exchange::{Estimate, ExchangeRate, Quotation}, wallets::WalletId};
+
+/// An error returned when a conversion cannot be estimated offline.
+#[derive(Debug, Error, PartialEq)]
+pub enum EstimateError {
+    /// No quotation for the pair has been seen.
+    #[error("no rate from {from} to {to} has been quoted")]
+    Unknown {
+        from: WalletId,
+        to: WalletId,
+    },
+
+    /// The last rate seen for the pair is older than the freshness window.
+    #[error("the rate from {} to {} was quoted {}s ago", rate.from, rate.to, rate.age().as_secs())]
+    Stale {
+        rate: ExchangeRate,
+    },
+}
+
+/// The latest [`ExchangeRate`] of each currency pair quoted by a client.
+///
+/// Every quotation created through [`CreateQuotation`](crate::exchange::CreateQuotation) is
+/// recorded, so conversions can be estimated offline with
+/// [`EstimateConversion`](crate::exchange::EstimateConversion).
+pub struct RateCache {
+    freshness: Duration,
+    rates: Mutex<BTreeMap<(WalletId, WalletId), ExchangeRate>>,
+}
+
+impl RateCache {
+    /// The freshness window used unless set with
+    /// [`EversendBuilder::set_exchange_rate_freshness`](crate::EversendBuilder::set_exchange_rate_freshness).
+    pub const DEFAULT_FRESHNESS: Duration = Duration::from_secs(5 * 60);
+
+    /// Returns an empty cache whose rates are used for `freshness` after being quoted.
+    pub fn new(freshness: Duration) -> Self {
+        Self {
+            freshness,
+            rates: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// How long a rate is used for estimates after being quoted.
+    pub fn freshness(&self) -> Duration {
+        self.freshness
+    }
+
+    /// Records the rate of a quotation received just now.
+    pub fn record(&self, quotation: &Quotation) {
+        self.insert(ExchangeRate::from_quotation(quotation));
+    }
+
+    /// Records a rate, unless a more recent one is already known for the pair.
+    pub fn insert(&self, rate: ExchangeRate) {
+        let mut rates = self.rates();
+        let key = (rate.from.clone(), rate.to.clone());
+
+        match rates.get(&key) {
+            Some(known) if known.observed_at > rate.observed_at => {},
+            _ => {
+                rates.insert(key, rate);
+            },
+        }
+    }
+
+    /// The last rate seen for a pair, however old.
+    pub fn get(&self, from: &WalletId, to: &WalletId) -> Option<ExchangeRate> {
+        self.rates().get(&(from.clone(), to.clone())).cloned()
+    }
+
+    /// Estimates how much of `to` `amount` of `from` converts to, from the last rate seen.
+    ///
+    /// Converting a currency to itself always succeeds, at a rate of 1.
+    pub fn estimate(&self, amount: f64, from: &WalletId, to: &WalletId) -> Result<Estimate, EstimateError> {
+        if from == to {
+            let rate = ExchangeRate {
+                from: from.clone(),
+                to: to.clone(),
+                rate: 1.0,
+                observed_at: SystemTime::now(),
+            };
+
+            return Ok(Estimate::new(amount, &rate));
+        }
+
+        let rate = self.get(from, to).ok_or_else(|| EstimateError::Unknown {
+            from: from.clone(),
+            to: to.clone(),
+        })?;
+
+        if rate.age() > self.freshness {
+            return Err(EstimateError::Stale { rate });
+        }
+
+        Ok(Estimate::new(amount, &rate))
+    }
+
+    /// Forgets every rate.
+    pub fn clear(&self) {
+        self.rates().clear();
+    }
+
+    fn rates(&self) -> MutexGuard<'_, BTreeMap<(WalletId, WalletId), ExchangeRate>> {
+        self.rates.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for RateCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_FRESHNESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(from: &str, to: &str, rate: f64, age: Duration) -> ExchangeRate {
+        ExchangeRate {
+            from: WalletId::from(from),
+            to: WalletId::from(to),
+            rate,
+            observed_at: SystemTime::now() - age,
+        }
+    }
+
+    #[test]
+    fn it_estimates_from_fresh_rates() {
+        let cache = RateCache::default();
+        cache.insert(rate("UGX", "KES", 0.035, Duration::from_secs(60)));
+
+        let estimate = cache.estimate(1000.0, &WalletId::from("UGX"), &WalletId::from("KES")).unwrap();
+
+        assert_eq!(estimate.converted, 35.0);
+        assert!(!estimate.is_binding());
+        assert_eq!(estimate.to_string(), "≈ 35.00 KES (estimate)");
+    }
+
+    #[test]
+    fn it_refuses_unknown_and_stale_rates() {
+        let cache = RateCache::new(Duration::from_secs(60));
+        cache.insert(rate("UGX", "KES", 0.035, Duration::from_secs(120)));
+
+        assert!(matches!(
+            cache.estimate(1000.0, &WalletId::from("UGX"), &WalletId::from("KES")),
+            Err(EstimateError::Stale { .. })
+        ));
+
+        assert_eq!(
+            cache.estimate(1000.0, &WalletId::from("KES"), &WalletId::from("UGX")),
+            Err(EstimateError::Unknown { from: WalletId::from("KES"), to: WalletId::from("UGX") })
+        );
+
+        assert_eq!(cache.estimate(1000.0, &WalletId::from("UGX"), &WalletId::from("UGX")).unwrap().converted, 1000.0);
+    }
+
+    #[test]
+    fn it_keeps_the_most_recent_rate() {
+        let cache = RateCache::default();
+        cache.insert(rate("UGX", "KES", 0.035, Duration::from_secs(10)));
+        cache.insert(rate("UGX", "KES", 0.030, Duration::from_secs(20)));
+
+        assert_eq!(cache.get(&WalletId::from("UGX"), &WalletId::from("KES")).unwrap().rate, 0.035);
+    }
+}
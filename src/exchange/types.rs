@@ -1,5 +1,7 @@
 mod exchange;
+mod exchange_rate;
 mod quotation;
 
 pub use exchange::*;
+pub use exchange_rate::*;
 pub use quotation::*;
@@ -2,16 +2,22 @@ use serde::{Deserialize, Serialize};
 
 use crate::wallets::WalletId;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Exchange {
     /// Source account for the exchange.
     pub source: ExchangeAccount,
 
     /// Destination account for the exchange.
-    pub destination: ExchangeAccount
+    pub destination: ExchangeAccount,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExchangeAccount {
     /// Amount for the account.
     pub amount: f64,
@@ -21,9 +27,14 @@ pub struct ExchangeAccount {
 
     /// Effect of the account balance after the exchange.
     pub balance: Balance,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Balance {
 
     /// Balance after the exchange.
@@ -31,4 +42,8 @@ pub struct Balance {
 
     /// Balance before the exchange.
     pub before: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
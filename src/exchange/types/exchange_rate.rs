@@ -0,0 +1,80 @@
+use std::{fmt::Display, time::{Duration, SystemTime}};
+
+use crate::{exchange::Quotation, wallets::WalletId};
+
+/// The rate of a currency pair, as seen on a [`Quotation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    pub from: WalletId,
+    pub to: WalletId,
+
+    /// Units of `to` received per unit of `from`.
+    pub rate: f64,
+
+    /// When the rate was quoted.
+    pub observed_at: SystemTime,
+}
+
+impl ExchangeRate {
+    /// The rate of a quotation received just now.
+    pub fn from_quotation(quotation: &Quotation) -> Self {
+        Self {
+            from: quotation.base_currency.clone(),
+            to: quotation.dest_currency.clone(),
+            rate: quotation.rate,
+            observed_at: SystemTime::now(),
+        }
+    }
+
+    /// How long ago the rate was quoted.
+    pub fn age(&self) -> Duration {
+        self.observed_at.elapsed().unwrap_or_default()
+    }
+}
+
+/// A conversion worked out from a cached [`ExchangeRate`], for display only.
+///
+/// An estimate is not binding: rates move, and the amount actually received is only known from a
+/// [`Quotation`], whose token is what an exchange is executed with. There is no way to turn an
+/// estimate into an exchange.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    /// The amount of `from` converted.
+    pub amount: f64,
+    pub from: WalletId,
+    pub to: WalletId,
+
+    /// The estimated amount of `to`.
+    pub converted: f64,
+
+    /// The rate the estimate is based on.
+    pub rate: f64,
+
+    /// When that rate was quoted.
+    pub observed_at: SystemTime,
+}
+
+impl Estimate {
+    pub(crate) fn new(amount: f64, rate: &ExchangeRate) -> Self {
+        Self {
+            amount,
+            from: rate.from.clone(),
+            to: rate.to.clone(),
+            converted: amount * rate.rate,
+            rate: rate.rate,
+            observed_at: rate.observed_at,
+        }
+    }
+
+    /// Always `false`, see [`Estimate`].
+    pub fn is_binding(&self) -> bool {
+        false
+    }
+}
+
+impl Display for Estimate {
+    /// Formats the estimate as e.g. `≈ 25.83 KES (estimate)`, so it is never shown as a quote.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "≈ {:.2} {} (estimate)", self.converted, self.to)
+    }
+}
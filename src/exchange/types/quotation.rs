@@ -3,9 +3,11 @@ use serde::{Deserialize, Serialize};
 use crate::wallets::WalletId;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Quotation {
+    /// The amount quoted in the base currency, which may be fractional, e.g. for crypto.
     #[serde(rename = "baseAmount")]
-    pub base_amount: u32,
+    pub base_amount: f64,
 
     #[serde(rename = "baseCurrency")]
     pub base_currency: WalletId,
@@ -29,4 +31,8 @@ pub struct Quotation {
     pub dest_wallet_before: f64,
 
     pub rate: f64,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
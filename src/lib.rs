@@ -12,8 +12,19 @@ pub mod collections;
 pub mod crypto;
 pub mod exchange;
 pub mod payouts;
+pub mod pool;
+pub mod reconcile;
+pub mod reference_data;
 pub mod transactions;
 pub mod wallets;
 
 pub use crate::core::*;
 pub use crate::eversend::*;
+
+/// The `schemars` version the models derive [`JsonSchema`](schemars::JsonSchema) with.
+#[cfg(feature = "schemars")]
+pub use schemars;
+
+
+/// The decimal type crypto amounts are parsed into.
+pub use rust_decimal::Decimal;
@@ -1,3 +1,5 @@
+pub mod bulk;
+
 mod operations;
 mod types;
 
@@ -0,0 +1,13 @@
+//! Bulk momo and bank payouts, e.g. for payroll and vendor runs.
+//!
+//! Rows are read from CSV or built in code, validated, quoted and submitted a few at a time.
+//! Every change to a row is appended to a journal, from which an interrupted run can be resumed
+//! without paying anyone twice.
+
+mod bulk_payout;
+mod report;
+mod row;
+
+pub use bulk_payout::*;
+pub use report::*;
+pub use row::*;
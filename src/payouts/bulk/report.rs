@@ -0,0 +1,206 @@
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{payouts::bulk::BulkPayoutError, TransactionRef};
+
+/// Where a row of a bulk run stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkRowStatus {
+    /// The row failed validation. Nothing was sent.
+    Invalid,
+
+    /// Quoting or submitting failed and no money moved. The row is retried on resume.
+    Failed,
+
+    /// The payout was, or was about to be, submitted, but whether it went through is unknown.
+    /// On resume the row is looked up by its reference before being submitted again.
+    InFlight,
+
+    /// The payout was made.
+    Paid,
+}
+
+/// The result of a single row of a bulk run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BulkRowResult {
+    pub row_id: String,
+
+    pub status: BulkRowStatus,
+
+    /// The reference the payout was submitted with.
+    pub transaction_ref: Option<TransactionRef>,
+
+    /// The Eversend transaction ID, once paid.
+    pub transaction_id: Option<String>,
+
+    /// The status of the Eversend transaction, e.g. `pending`, once paid.
+    pub transaction_status: Option<String>,
+
+    /// Total fees of the quotation.
+    pub fees: Option<String>,
+
+    /// Why the row is invalid, failed or still in flight.
+    pub error: Option<String>,
+}
+
+impl BulkRowResult {
+    pub(crate) fn new(row_id: &str) -> Self {
+        Self {
+            row_id: row_id.to_string(),
+            status: BulkRowStatus::Invalid,
+            transaction_ref: None,
+            transaction_id: None,
+            transaction_status: None,
+            fees: None,
+            error: None,
+        }
+    }
+}
+
+/// The per-row results of a bulk run, in the order the rows were given.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BulkReport {
+    rows: Vec<BulkRowResult>,
+}
+
+impl BulkReport {
+    /// Rebuilds a report from the journal of a previous run, to resume it.
+    ///
+    /// The last entry of each row wins. A truncated last line, left behind by a crash while it was
+    /// being written, is ignored.
+    pub fn from_journal<R: BufRead>(reader: R) -> Result<Self, BulkPayoutError> {
+        let mut report = Self::default();
+        let mut lines = reader.lines().enumerate().peekable();
+
+        while let Some((index, line)) = lines.next() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str(&line) {
+                Ok(result) => report.record(result),
+                Err(_) if lines.peek().is_none() => break,
+                Err(err) => {
+                    return Err(BulkPayoutError::InvalidJournal {
+                        line: index + 1,
+                        message: err.to_string(),
+                    });
+                },
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// The results of all rows.
+    pub fn rows(&self) -> &[BulkRowResult] {
+        &self.rows
+    }
+
+    /// The result of the row with the given ID.
+    pub fn get(&self, row_id: &str) -> Option<&BulkRowResult> {
+        self.rows.iter().find(|result| result.row_id == row_id)
+    }
+
+    /// The number of rows with the given status.
+    pub fn count(&self, status: BulkRowStatus) -> usize {
+        self.rows.iter().filter(|result| result.status == status).count()
+    }
+
+    /// Returns `true` if no row is left to retry, i.e. every row was paid or is invalid.
+    pub fn is_complete(&self) -> bool {
+        self.rows
+            .iter()
+            .all(|result| matches!(result.status, BulkRowStatus::Paid | BulkRowStatus::Invalid))
+    }
+
+    /// Writes the report as CSV with a header line.
+    pub fn write_csv<W: Write>(&self, writer: W) -> Result<(), BulkPayoutError> {
+        let mut writer = csv::Writer::from_writer(writer);
+
+        for result in &self.rows {
+            writer.serialize(result)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    pub(crate) fn record(&mut self, result: BulkRowResult) {
+        match self.rows.iter_mut().find(|existing| existing.row_id == result.row_id) {
+            Some(existing) => *existing = result,
+            None => self.rows.push(result),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(row_id: &str, status: BulkRowStatus) -> BulkRowResult {
+        BulkRowResult {
+            status,
+            ..BulkRowResult::new(row_id)
+        }
+    }
+
+    #[test]
+    fn it_rebuilds_a_report_from_a_journal() {
+        let mut journal = Vec::new();
+
+        for result in [
+            result("EMP001", BulkRowStatus::InFlight),
+            result("EMP002", BulkRowStatus::Invalid),
+            result("EMP001", BulkRowStatus::Paid),
+        ] {
+            serde_json::to_writer(&mut journal, &result).unwrap();
+            journal.push(b'\n');
+        }
+
+        journal.extend_from_slice(br#"{"row_id":"EMP003","sta"#);
+
+        let report = BulkReport::from_journal(journal.as_slice()).unwrap();
+
+        assert_eq!(report.rows().len(), 2);
+        assert_eq!(report.get("EMP001").unwrap().status, BulkRowStatus::Paid);
+        assert_eq!(report.count(BulkRowStatus::Invalid), 1);
+        assert!(report.is_complete());
+    }
+
+    #[test]
+    fn it_rejects_a_corrupt_journal() {
+        let journal = "not json\n{\"row_id\":\"EMP001\"}\n";
+
+        assert!(matches!(
+            BulkReport::from_journal(journal.as_bytes()),
+            Err(BulkPayoutError::InvalidJournal { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn it_writes_the_report_as_csv() {
+        let mut report = BulkReport::default();
+        report.record(BulkRowResult {
+            transaction_id: Some(String::from("BP11678735362605")),
+            fees: Some(String::from("250")),
+            ..result("EMP001", BulkRowStatus::Paid)
+        });
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "row_id,status,transaction_ref,transaction_id,transaction_status,fees,error\n\
+             EMP001,paid,,BP11678735362605,,250,\n"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::io::Read;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{payouts::{bulk::BulkPayoutError, CountryPaymentType, PayoutRecipient, PayoutRequest}, TransactionRef};
+
+/// An error that makes a [`BulkPayoutRow`] unfit to be paid out.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BulkRowError {
+    /// A field required for the payment type is missing or blank.
+    #[error("{0} is required")]
+    MissingField(&'static str),
+
+    /// The amount is zero.
+    #[error("amount must be greater than 0")]
+    InvalidAmount,
+
+    /// The amount type is neither `SOURCE` nor `DESTINATION`.
+    #[error("invalid amount type {0:?}, expected SOURCE or DESTINATION")]
+    InvalidAmountType(String),
+
+    /// The country is not an ALPHA-2 code.
+    #[error("invalid country {0:?}, expected an ALPHA-2 code")]
+    InvalidCountry(String),
+
+    /// The phone number is not in international format.
+    #[error("invalid phone number {0:?}, expected international format e.g. +256789123456")]
+    InvalidPhoneNumber(String),
+
+    /// Only momo and bank payouts can be made in bulk.
+    #[error("{0:?} payouts are not supported in bulk")]
+    UnsupportedPaymentType(CountryPaymentType),
+}
+
+/// A single payout of a bulk run.
+///
+/// In CSV the header names are the field names, and bank fields may be left empty for momo payouts.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BulkPayoutRow {
+    /// Identifies the row within the run, e.g. an employee or invoice number. Must be unique.
+    pub row_id: String,
+
+    /// `momo` or `bank`.
+    pub payment_type: CountryPaymentType,
+
+    /// Amount to pay, interpreted according to `amount_type`.
+    pub amount: u32,
+
+    /// DESTINATION or SOURCE. Defaults to SOURCE.
+    #[serde(default)]
+    pub amount_type: Option<String>,
+
+    /// Destination country ALPHA-2 code e.g NG for Nigeria.
+    pub country: String,
+
+    /// Destination currency.
+    pub destination_currency: String,
+
+    /// Source wallet currency from Get Wallets.
+    pub source_wallet: String,
+
+    pub first_name: String,
+
+    pub last_name: String,
+
+    /// Phone number in international format.
+    pub phone_number: String,
+
+    #[serde(default)]
+    pub bank_account_name: Option<String>,
+
+    #[serde(default)]
+    pub bank_account_number: Option<String>,
+
+    /// Bank code from Get Delivery Banks.
+    #[serde(default)]
+    pub bank_code: Option<String>,
+
+    #[serde(default)]
+    pub bank_name: Option<String>,
+
+    /// Generated on first submission when not set.
+    #[serde(default)]
+    pub transaction_ref: Option<TransactionRef>,
+}
+
+impl BulkPayoutRow {
+    /// Reads rows from CSV with a header line.
+    ///
+    /// Fails on the first row that cannot be read, e.g. because of a non-numeric amount. Rows are
+    /// not validated until they are paid out, see [`BulkPayoutRow::validate`].
+    pub fn from_csv<R: Read>(reader: R) -> Result<Vec<Self>, BulkPayoutError> {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(BulkPayoutError::from)
+    }
+
+    /// Checks the row and turns it into a [`PayoutRequest`].
+    pub fn validate(&self) -> Result<PayoutRequest, BulkRowError> {
+        if self.amount == 0 {
+            return Err(BulkRowError::InvalidAmount);
+        }
+
+        let amount_type = self.amount_type.as_deref().unwrap_or("SOURCE");
+
+        if amount_type != "SOURCE" && amount_type != "DESTINATION" {
+            return Err(BulkRowError::InvalidAmountType(amount_type.to_string()));
+        }
+
+        if self.country.len() != 2 || !self.country.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(BulkRowError::InvalidCountry(self.country.clone()));
+        }
+
+        let digits = self.phone_number.strip_prefix('+').unwrap_or_default();
+
+        if !(7..=15).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(BulkRowError::InvalidPhoneNumber(self.phone_number.clone()));
+        }
+
+        let first_name = required("first_name", &self.first_name)?;
+        let last_name = required("last_name", &self.last_name)?;
+
+        let recipient = match &self.payment_type {
+            CountryPaymentType::MOMO => PayoutRecipient::Momo {
+                first_name,
+                last_name,
+                phone_number: self.phone_number.clone(),
+            },
+            CountryPaymentType::BANK => PayoutRecipient::Bank {
+                first_name,
+                last_name,
+                phone_number: self.phone_number.clone(),
+                bank_account_name: required_option("bank_account_name", &self.bank_account_name)?,
+                bank_account_number: required_option("bank_account_number", &self.bank_account_number)?,
+                bank_code: required_option("bank_code", &self.bank_code)?,
+                bank_name: required_option("bank_name", &self.bank_name)?,
+            },
+            payment_type => return Err(BulkRowError::UnsupportedPaymentType(payment_type.clone())),
+        };
+
+        Ok(PayoutRequest {
+            amount: self.amount,
+            amount_type: amount_type.to_string(),
+            country: self.country.clone(),
+            destination_currency: required("destination_currency", &self.destination_currency)?,
+            recipient,
+            source_wallet: required("source_wallet", &self.source_wallet)?,
+            transaction_ref: self.transaction_ref.clone(),
+        })
+    }
+}
+
+fn required(field: &'static str, value: &str) -> Result<String, BulkRowError> {
+    match value.trim() {
+        "" => Err(BulkRowError::MissingField(field)),
+        value => Ok(value.to_string()),
+    }
+}
+
+fn required_option(field: &'static str, value: &Option<String>) -> Result<String, BulkRowError> {
+    required(field, value.as_deref().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+row_id,payment_type,amount,amount_type,country,destination_currency,source_wallet,first_name,last_name,phone_number,bank_account_name,bank_account_number,bank_code,bank_name,transaction_ref
+EMP001,momo,1000,,UG,UGX,UGX,John,Doe,+256789123456,,,,,
+EMP002,bank,2000,DESTINATION,NG,NGN,USD,Jane,Smith,+2348012345678,Jane Smith,0012033048,14,World Bank,PAYROLL1
+";
+
+    #[test]
+    fn it_reads_rows_from_csv() {
+        let rows = BulkPayoutRow::from_csv(CSV.as_bytes()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].payment_type, CountryPaymentType::MOMO);
+        assert_eq!(rows[0].amount_type, None);
+        assert_eq!(rows[0].bank_account_name, None);
+        assert_eq!(rows[1].bank_code.as_deref(), Some("14"));
+        assert_eq!(rows[1].transaction_ref.as_ref().map(TransactionRef::as_str), Some("PAYROLL1"));
+    }
+
+    #[test]
+    fn it_rejects_unreadable_csv() {
+        let csv = CSV.replace("1000", "lots");
+
+        assert!(matches!(BulkPayoutRow::from_csv(csv.as_bytes()), Err(BulkPayoutError::Csv(_))));
+    }
+
+    #[test]
+    fn it_validates_rows() {
+        let rows = BulkPayoutRow::from_csv(CSV.as_bytes()).unwrap();
+
+        let request = rows[1].validate().unwrap();
+        assert_eq!(request.recipient.payment_type(), CountryPaymentType::BANK);
+        assert_eq!(request.amount_type, "DESTINATION");
+
+        let mut row = rows[0].clone();
+        row.amount = 0;
+        assert_eq!(row.validate().unwrap_err(), BulkRowError::InvalidAmount);
+
+        let mut row = rows[0].clone();
+        row.phone_number = String::from("0789123456");
+        assert_eq!(row.validate().unwrap_err(), BulkRowError::InvalidPhoneNumber(String::from("0789123456")));
+
+        let mut row = rows[1].clone();
+        row.bank_code = None;
+        assert_eq!(row.validate().unwrap_err(), BulkRowError::MissingField("bank_code"));
+
+        let mut row = rows[0].clone();
+        row.payment_type = CountryPaymentType::EVERSEND;
+        assert_eq!(row.validate().unwrap_err(), BulkRowError::UnsupportedPaymentType(CountryPaymentType::EVERSEND));
+    }
+}
@@ -6,6 +6,10 @@ mod create_momo_and_bank_payout_quotation;
 mod create_momo_payout_transaction;
 mod get_delivery_banks;
 mod get_delivery_countries;
+mod payout_quote;
+mod send_payout;
+mod submit_payout;
+mod verify_bank_account;
 
 pub use create_bank_payout_transaction::*;
 pub use create_beneficiary_payout_transaction::*;
@@ -15,3 +19,7 @@ pub use create_momo_and_bank_payout_quotation::*;
 pub use create_momo_payout_transaction::*;
 pub use get_delivery_banks::*;
 pub use get_delivery_countries::*;
+pub use payout_quote::*;
+pub use send_payout::*;
+pub use submit_payout::*;
+pub use verify_bank_account::*;
@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension, TransactionRef};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBankPayoutTransactionParams {
     /// Recipient bank account name
     #[serde(rename = "bankAccountName")]
@@ -41,8 +42,8 @@ pub struct CreateBankPayoutTransactionParams {
     pub token: String,
 
     /// Optional unique alphanumeric string set by the client
-    #[serde(rename = "transactionRef")]
-    pub transaction_ref: String,
+    #[serde(rename = "transactionRef", skip_serializing_if = "Option::is_none")]
+    pub transaction_ref: Option<TransactionRef>,
 }
 
 /// An error returned from [`CreateBankPayoutTransaction`].
@@ -56,8 +57,13 @@ impl From<CreateBankPayoutTransactionError> for EversendError<CreateBankPayoutTr
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBankPayoutResponse {
-    transaction: Transaction
+    transaction: Transaction,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // [Eversend Docs: Create Payout Transaction Non Beneficiary - Bank](https://eversend.readme.io/reference/create-payout-transaction-non-beneficiary-bank)
@@ -71,7 +77,7 @@ pub trait CreateBankPayoutTransaction {
     /// ```
     /// # use eversend_rust_sdk::EversendResult;
     /// # use eversend_rust_sdk::payouts::*;
-    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
     ///
     /// # async fn run() -> EversendResult<(), CreateBankPayoutTransactionError> {
     ///     let eversend = Eversend::new(
@@ -88,7 +94,7 @@ pub trait CreateBankPayoutTransaction {
     ///                 last_name: String::from("Doe"),
     ///                 phone_number: String::from("+256789123456"),
     ///                 token: String::from("some-token"),
-    ///                 transaction_ref: String::from("some-reference"),
+    ///                 transaction_ref: Some(TransactionRef::generate()),
     ///                 bank_account_name: String::from("John Doe"),
     ///                 bank_account_number: String::from("12345"),
     ///                 bank_code: String::from("1234"),
@@ -124,7 +130,7 @@ impl<'a> CreateBankPayoutTransaction for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateBankPayoutResponse>>()
+            .decode::<ApiResponseBody<CreateBankPayoutResponse>, _>()
             .await?;
 
         Ok(result.data.transaction)
@@ -202,7 +208,7 @@ mod tests {
                     last_name: String::from("Doe"),
                     phone_number: String::from("+256789123456"),
                     token: String::from("some-token"),
-                    transaction_ref: String::from("some-reference"),
+                    transaction_ref: Some("SOMEREFERENCE".parse().unwrap()),
                     bank_account_name: String::from("John Doe"),
                     bank_account_number: String::from("12345"),
                     bank_code: String::from("1234"),
@@ -217,4 +223,15 @@ mod tests {
         mock.assert();
 
     }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn it_describes_the_params_as_json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(CreateBankPayoutTransactionParams)).unwrap();
+
+        assert_eq!(schema["properties"]["bankAccountNumber"]["type"], "string");
+        assert_eq!(schema["definitions"]["TransactionRef"]["maxLength"], TransactionRef::MAX_LEN);
+        assert!(schema["required"].as_array().unwrap().contains(&json!("token")));
+        assert!(!schema["required"].as_array().unwrap().contains(&json!("transactionRef")));
+    }
 }
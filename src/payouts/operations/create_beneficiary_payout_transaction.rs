@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension, TransactionRef};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBeneficiaryPayoutTransactionParams {
     /// Id of beneficiary you are sending to, you can get the beneficiary Id from the get beneficiary endpoint
     #[serde(rename = "beneficiaryId")]
@@ -12,6 +13,10 @@ pub struct CreateBeneficiaryPayoutTransactionParams {
 
     /// JWT token from quotation
     pub token: String,
+
+    /// Optional unique alphanumeric string set by the client
+    #[serde(rename = "transactionRef", skip_serializing_if = "Option::is_none")]
+    pub transaction_ref: Option<TransactionRef>,
 }
 
 /// An error returned from [`CreateBeneficiaryPayoutTransaction`].
@@ -25,8 +30,13 @@ impl From<CreateBeneficiaryPayoutTransactionError> for EversendError<CreateBenef
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateBeneficiaryPayoutResponse {
-    transaction: Transaction
+    transaction: Transaction,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // [Eversend Docs: Create Payout Transaction Beneficiary](https://eversend.readme.io/reference/create-payout-transaction-beneficiary)
@@ -54,6 +64,7 @@ pub trait CreateBeneficiaryPayoutTransaction {
     ///             &CreateBeneficiaryPayoutTransactionParams {
     ///                 token: String::from("some-token"),
     ///                 beneficiary_id: String::from("123"),
+    ///                 transaction_ref: None,
     ///             }
     ///         )
     ///         .await?;
@@ -84,7 +95,7 @@ impl<'a> CreateBeneficiaryPayoutTransaction for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateBeneficiaryPayoutResponse>>()
+            .decode::<ApiResponseBody<CreateBeneficiaryPayoutResponse>, _>()
             .await?;
 
         Ok(result.data.transaction)
@@ -159,6 +170,7 @@ mod tests {
                 &CreateBeneficiaryPayoutTransactionParams {
                     token: String::from("some-token"),
                     beneficiary_id: String::from("123"),
+                    transaction_ref: None,
                 }
             )
             .await
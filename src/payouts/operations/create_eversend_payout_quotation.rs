@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{payouts::{Payouts, Quotation}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateEversendPayoutQuotationParams {
     /// Source amount to pay
     pub amount: u32,
@@ -41,9 +42,14 @@ impl From<CreateEversendPayoutQuotationError> for EversendError<CreateEversendPa
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateEversendPayoutResponse {
     pub quotation: Quotation,
     pub token: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Create Payout Quotation - Eversend](https://eversend.readme.io/reference/create-payout-quotation-eversend)
@@ -107,7 +113,7 @@ impl<'a> CreateEversendPayoutQuotation for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateEversendPayoutResponse>>()
+            .decode::<ApiResponseBody<CreateEversendPayoutResponse>, _>()
             .await?;
 
         Ok(result.data)
@@ -196,10 +202,10 @@ mod tests {
 
         let merchant = response.quotation.merchant.unwrap();
         assert_eq!(merchant.result, "successful");
-        assert_eq!(merchant.merchant_exists, true);
+        assert!(merchant.merchant_exists);
         assert_eq!(merchant.country, "NG");
         assert_eq!(merchant.default_wallet, "NGN");
-        assert_eq!(merchant.is_merchant, false);
+        assert!(!merchant.is_merchant);
         assert_eq!(merchant.phone_number.number, "8038385263");
         assert_eq!(merchant.phone_number.prefix, "+234");
 
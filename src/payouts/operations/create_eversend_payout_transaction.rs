@@ -2,16 +2,17 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension, TransactionRef};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateEversendPayoutTransactionParams {
     /// JWT token from quotation
     pub token: String,
 
     /// Optional unique alphanumeric string set by the client
-    #[serde(rename = "transactionRef")]
-    pub transaction_ref: String,
+    #[serde(rename = "transactionRef", skip_serializing_if = "Option::is_none")]
+    pub transaction_ref: Option<TransactionRef>,
 }
 
 /// An error returned from [`CreateEversendPayoutTransaction`].
@@ -25,8 +26,13 @@ impl From<CreateEversendPayoutTransactionError> for EversendError<CreateEversend
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateEversendPayoutTransactionResponse {
-    transaction: Transaction
+    transaction: Transaction,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // [Eversend Docs: Create Payout Transaction Eversend](https://eversend.readme.io/reference/create-payout-transaction-eversend)
@@ -40,7 +46,7 @@ pub trait CreateEversendPayoutTransaction {
     /// ```
     /// # use eversend_rust_sdk::EversendResult;
     /// # use eversend_rust_sdk::payouts::*;
-    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
     ///
     /// # async fn run() -> EversendResult<(), CreateEversendPayoutTransactionError> {
     ///     let eversend = Eversend::new(
@@ -53,7 +59,7 @@ pub trait CreateEversendPayoutTransaction {
     ///         .create_eversend_payout_transaction(
     ///             &CreateEversendPayoutTransactionParams {
     ///                 token: String::from("some-token"),
-    ///                 transaction_ref: String::from("some-reference"),
+    ///                 transaction_ref: Some(TransactionRef::generate()),
     ///             }
     ///         )
     ///         .await?;
@@ -85,7 +91,7 @@ impl<'a> CreateEversendPayoutTransaction for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateEversendPayoutTransactionResponse>>()
+            .decode::<ApiResponseBody<CreateEversendPayoutTransactionResponse>, _>()
             .await?;
 
         Ok(result.data.transaction)
@@ -153,7 +159,7 @@ mod tests {
             .create_eversend_payout_transaction(
                 &CreateEversendPayoutTransactionParams {
                     token: String::from("some-token"),
-                    transaction_ref: String::from("some-reference"),
+                    transaction_ref: Some("SOMEREFERENCE".parse().unwrap()),
                 }
             )
             .await
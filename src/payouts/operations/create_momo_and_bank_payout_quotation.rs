@@ -1,10 +1,13 @@
+use std::time::{Duration, SystemTime};
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{payouts::{Payouts, Quotation}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{core::jwt_expiry, payouts::{Payouts, Quotation}, ApiResponseBody, EversendError, EversendResult, QuoteError, QuoteHandle, ResponseExtension};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateMomoAndBankPayoutQuotationParams {
     /// Source amount to pay
     pub amount: u32,
@@ -40,10 +43,40 @@ impl From<CreateMomoAndBankPayoutQuotationError> for EversendError<CreateMomoAnd
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateQuotationResponse {
     pub quotation: Quotation,
     pub token: String,
+
+    /// When the token expires as an RFC 3339 timestamp, if sent by the API.
+    pub expires: Option<String>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CreateQuotationResponse {
+    /// How long a quotation is assumed to be valid when the response does not say, matching the
+    /// tokens issued by the API at the time of writing.
+    pub const DEFAULT_LIFETIME: Duration = Duration::from_secs(30 * 60);
+
+    /// Converts the response into a [`QuoteHandle`] that tracks when the token expires.
+    ///
+    /// The expiry is read from the `exp` claim of the token, or from `expires` if the token is not
+    /// a JWT or its `exp` is out of range. If neither is available, the token is assumed to expire
+    /// `lifetime` from now.
+    pub fn into_handle(self, lifetime: Duration) -> Result<QuoteHandle<Quotation>, QuoteError> {
+        if jwt_expiry(&self.token).is_ok() {
+            return QuoteHandle::from_jwt(self.token, self.quotation);
+        }
+
+        match &self.expires {
+            Some(expires) => QuoteHandle::from_rfc3339(self.token, self.quotation, expires),
+            None => Ok(QuoteHandle::new(self.token, self.quotation, SystemTime::now() + lifetime)),
+        }
+    }
 }
 
 /// [Eversend Docs: Create Payout Quotation - Momo & Bank](https://eversend.readme.io/reference/create-payout-quotation)
@@ -106,7 +139,7 @@ impl<'a> CreateMomoAndBankPayoutQuotation for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateQuotationResponse>>()
+            .decode::<ApiResponseBody<CreateQuotationResponse>, _>()
             .await?;
 
         Ok(result.data)
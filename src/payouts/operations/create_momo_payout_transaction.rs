@@ -2,9 +2,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{payouts::{Payouts, Transaction}, ApiResponseBody, EversendError, EversendResult, ResponseExtension, TransactionRef};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateMomoPayoutTransactionParams {
     /// Recipient Country Code e.g. Nigeria should be NG, Uganda should be UG, etc
     pub country: String,
@@ -25,8 +26,8 @@ pub struct CreateMomoPayoutTransactionParams {
     pub token: String,
 
     /// Optional unique alphanumeric string set by the client
-    #[serde(rename = "transactionRef")]
-    pub transaction_ref: String,
+    #[serde(rename = "transactionRef", skip_serializing_if = "Option::is_none")]
+    pub transaction_ref: Option<TransactionRef>,
 }
 
 /// An error returned from [`CreateMomoPayoutTransaction`].
@@ -40,8 +41,13 @@ impl From<CreateMomoPayoutTransactionError> for EversendError<CreateMomoPayoutTr
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateMomoPayoutResponse {
-    transaction: Transaction
+    transaction: Transaction,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Create Payout Transaction Non Beneficiary - Momo](https://eversend.readme.io/reference/create-payout-transaction-non-beneficiary-momo)
@@ -55,7 +61,7 @@ pub trait CreateMomoPayoutTransaction {
     /// ```
     /// # use eversend_rust_sdk::EversendResult;
     /// # use eversend_rust_sdk::payouts::*;
-    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
     ///
     /// # async fn run() -> EversendResult<(), CreateMomoPayoutTransactionError> {
     ///     let eversend = Eversend::new(
@@ -72,7 +78,7 @@ pub trait CreateMomoPayoutTransaction {
     ///                 last_name: String::from("Doe"),
     ///                 phone_number: String::from("+256789123456"),
     ///                 token: String::from("some-token"),
-    ///                 transaction_ref: String::from("some-reference")
+    ///                 transaction_ref: Some(TransactionRef::generate())
     ///             }
     ///         )
     ///         .await?;
@@ -104,7 +110,7 @@ impl<'a> CreateMomoPayoutTransaction for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<CreateMomoPayoutResponse>>()
+            .decode::<ApiResponseBody<CreateMomoPayoutResponse>, _>()
             .await?;
 
         Ok(result.data.transaction)
@@ -177,7 +183,7 @@ mod tests {
                     last_name: String::from("Doe"),
                     phone_number: String::from("+256789123456"),
                     token: String::from("some-token"),
-                    transaction_ref: String::from("some-reference")
+                    transaction_ref: Some("SOMEREFERENCE".parse().unwrap())
                 }
             )
             .await
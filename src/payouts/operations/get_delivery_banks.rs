@@ -63,7 +63,7 @@ impl<'a> GetDeliveryBanks for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<Vec<Bank>>>()
+            .decode::<ApiResponseBody<Vec<Bank>>, _>()
             .await?;
 
         Ok(result.data)
@@ -67,7 +67,7 @@ impl<'a> GetDeliveryCountries for Payouts<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<DeliveryCountriesApiResponse>>()
+            .decode::<ApiResponseBody<DeliveryCountriesApiResponse>, _>()
             .await?;
 
         Ok(result.data.countries)
@@ -169,4 +169,57 @@ mod tests {
         mock.assert();
 
     }
+
+    #[tokio::test]
+    async fn it_keeps_unknown_payment_types_and_fields() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), "countries-unknown"))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("GET", "/countries-unknown/payouts/countries")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "countries": [
+                            {
+                                "country": "NG",
+                                "id": "990",
+                                "name": "Nigeria",
+                                "paymentTypes": [
+                                    "bank",
+                                    "card"
+                                ],
+                                "phonePrefix": "+234",
+                                "currency": "NGN"
+                            }
+                        ]
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create();
+
+        let response = eversend
+            .payouts()
+            .get_delivery_countries()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response[0].payment_types,
+            vec![
+                CountryPaymentType::BANK,
+                CountryPaymentType::Unknown(String::from("card"))
+            ]
+        );
+        assert_eq!(response[0].extra["currency"], "NGN");
+
+        mock.assert();
+    }
 }
@@ -0,0 +1,764 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{
+    payouts::{
+        BankAccountCheck,
+        CountryPaymentType,
+        CreateBankPayoutTransactionParams,
+        CreateMomoAndBankPayoutQuotationParams,
+        CreateMomoPayoutTransactionParams,
+        GetDeliveryCountries,
+        NameMatchPolicy,
+        PayoutQuote,
+        PayoutQuoteError,
+        Payouts,
+        PayoutOutcome,
+        Quotation,
+        SubmitPayout,
+        SubmitPayoutError,
+        SubmitPayoutOptions,
+        SubmitPayoutParams,
+        Transaction,
+    },
+    transactions::{self, WaitForFinalStatus, WaitForFinalStatusError, WaitOptions},
+    Eversend,
+    EversendError,
+    EversendResult,
+    QuoteError,
+    QuoteHandle,
+    RateDelta,
+    TransactionRef,
+};
+
+/// The recipient of a [`PayoutRequest`].
+#[derive(Debug, Clone)]
+pub enum PayoutRecipient {
+    /// Pay out to a mobile money account.
+    Momo {
+        first_name: String,
+        last_name: String,
+        /// Phone number in international format.
+        phone_number: String,
+    },
+
+    /// Pay out to a bank account.
+    Bank {
+        first_name: String,
+        last_name: String,
+        /// Phone number in international format.
+        phone_number: String,
+        bank_account_name: String,
+        bank_account_number: String,
+        /// Bank code from Get Delivery Banks.
+        bank_code: String,
+        bank_name: String,
+    },
+}
+
+impl PayoutRecipient {
+    /// The payment type used to deliver the payout.
+    pub fn payment_type(&self) -> CountryPaymentType {
+        match self {
+            Self::Momo { .. } => CountryPaymentType::MOMO,
+            Self::Bank { .. } => CountryPaymentType::BANK,
+        }
+    }
+}
+
+/// A momo or bank payout to be driven by [`SendPayout`].
+#[derive(Debug, Clone)]
+pub struct PayoutRequest {
+    /// Amount to pay, interpreted according to `amount_type`.
+    pub amount: u32,
+
+    /// DESTINATION or SOURCE.
+    pub amount_type: String,
+
+    /// Destination country ALPHA-2 code e.g NG for Nigeria.
+    pub country: String,
+
+    /// Destination currency.
+    pub destination_currency: String,
+
+    /// Who receives the money and how.
+    pub recipient: PayoutRecipient,
+
+    /// Source wallet currency from Get Wallets.
+    pub source_wallet: String,
+
+    /// Unique alphanumeric string set by the client. Generated on approval when not set.
+    pub transaction_ref: Option<TransactionRef>,
+}
+
+impl PayoutRequest {
+    /// The parameters used to quote this payout.
+    pub fn quotation_params(&self) -> CreateMomoAndBankPayoutQuotationParams {
+        let transaction_type = match self.recipient.payment_type() {
+            CountryPaymentType::BANK => "bank",
+            _ => "momo",
+        };
+
+        CreateMomoAndBankPayoutQuotationParams {
+            amount: self.amount,
+            amount_type: self.amount_type.clone(),
+            destination_country: self.country.clone(),
+            destination_currency: self.destination_currency.clone(),
+            source_wallet: self.source_wallet.clone(),
+            transaction_type: transaction_type.to_string(),
+        }
+    }
+
+    /// The parameters used to submit this payout with the token of its quotation.
+    pub fn submit_params(&self, token: String) -> SubmitPayoutParams {
+        match &self.recipient {
+            PayoutRecipient::Momo { first_name, last_name, phone_number } => {
+                SubmitPayoutParams::Momo(CreateMomoPayoutTransactionParams {
+                    country: self.country.clone(),
+                    first_name: first_name.clone(),
+                    last_name: last_name.clone(),
+                    phone_number: phone_number.clone(),
+                    token,
+                    transaction_ref: self.transaction_ref.clone(),
+                })
+            },
+            PayoutRecipient::Bank {
+                first_name,
+                last_name,
+                phone_number,
+                bank_account_name,
+                bank_account_number,
+                bank_code,
+                bank_name,
+            } => {
+                SubmitPayoutParams::Bank(CreateBankPayoutTransactionParams {
+                    bank_account_name: bank_account_name.clone(),
+                    bank_account_number: bank_account_number.clone(),
+                    bank_code: bank_code.clone(),
+                    bank_name: bank_name.clone(),
+                    country: self.country.clone(),
+                    first_name: first_name.clone(),
+                    last_name: last_name.clone(),
+                    phone_number: phone_number.clone(),
+                    token,
+                    transaction_ref: self.transaction_ref.clone(),
+                })
+            },
+        }
+    }
+}
+
+/// The state of a [`PayoutFlow`].
+///
+/// A payout moves from `Quoted` to `Submitted` once approved, and from there to `Pending`,
+/// `Completed` or `Failed` while waiting on the transaction.
+#[derive(Debug, Clone)]
+pub enum PayoutState {
+    /// A quotation was created and is awaiting approval.
+    Quoted(QuoteHandle<Quotation>),
+
+    /// The payout was accepted by Eversend.
+    Submitted(Transaction),
+
+    /// The payout transaction was seen, but has not reached a final status yet.
+    Pending(transactions::Transaction),
+
+    /// The payout went through.
+    Completed(transactions::Transaction),
+
+    /// The payout failed.
+    Failed(transactions::Transaction),
+}
+
+impl PayoutState {
+    /// A short name for the state, used in error messages.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Quoted(_) => "quoted",
+            Self::Submitted(_) => "submitted",
+            Self::Pending(_) => "pending",
+            Self::Completed(_) => "completed",
+            Self::Failed(_) => "failed",
+        }
+    }
+
+    fn from_transaction(transaction: transactions::Transaction) -> Self {
+        match transaction.status {
+            transactions::TransactionStatusOption::SUCCESSFUL => Self::Completed(transaction),
+            transactions::TransactionStatusOption::FAILED => Self::Failed(transaction),
+            transactions::TransactionStatusOption::PENDING
+            | transactions::TransactionStatusOption::Unknown(_) => Self::Pending(transaction),
+        }
+    }
+
+    /// Returns `true` if the payout is completed or failed.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Completed(_) | Self::Failed(_))
+    }
+}
+
+/// An error returned from [`SendPayout`] and [`PayoutFlow`].
+#[derive(Debug, Error)]
+pub enum SendPayoutError {
+    /// The destination country does not accept the requested payment type.
+    #[error("{country} does not support {payment_type:?} payouts")]
+    UnsupportedDestination {
+        country: String,
+        payment_type: CountryPaymentType,
+    },
+
+    #[error(transparent)]
+    Quote(#[from] QuoteError),
+
+    #[error(transparent)]
+    Submit(#[from] SubmitPayoutError),
+
+    #[error(transparent)]
+    Wait(#[from] WaitForFinalStatusError),
+
+    /// The requested step is not allowed from the current state.
+    #[error("cannot {action} a payout that is {state}")]
+    InvalidTransition {
+        action: &'static str,
+        state: &'static str,
+    },
+}
+
+impl From<SendPayoutError> for EversendError<SendPayoutError> {
+    fn from(err: SendPayoutError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+impl From<QuoteError> for EversendError<SendPayoutError> {
+    fn from(err: QuoteError) -> Self {
+        Self::Operation(SendPayoutError::Quote(err))
+    }
+}
+
+/// A payout in progress, see [`SendPayout`].
+pub struct PayoutFlow<'a> {
+    eversend: &'a Eversend,
+    request: PayoutRequest,
+    state: PayoutState,
+    submit_options: SubmitPayoutOptions,
+    bank_account_check: Option<BankAccountCheck>,
+}
+
+impl<'a> PayoutFlow<'a> {
+    /// The request this payout was started from.
+    pub fn request(&self) -> &PayoutRequest {
+        &self.request
+    }
+
+    /// The current state of the payout.
+    pub fn state(&self) -> &PayoutState {
+        &self.state
+    }
+
+    /// Checks the account name of a bank payout against the one on record when it is approved,
+    /// see [`VerifyBankAccount`](crate::payouts::VerifyBankAccount). Has no effect on momo payouts.
+    pub fn verify_bank_account(&mut self, policy: NameMatchPolicy) -> &mut Self {
+        self.submit_options.verify_bank_account = Some(policy);
+        self
+    }
+
+    /// The outcome of the account name check, once an approved bank payout was verified.
+    pub fn bank_account_check(&self) -> Option<&BankAccountCheck> {
+        self.bank_account_check.as_ref()
+    }
+
+    /// The quotation, while the payout is awaiting approval.
+    pub fn quotation(&self) -> Option<&Quotation> {
+        match &self.state {
+            PayoutState::Quoted(handle) => Some(handle.quotation()),
+            _ => None,
+        }
+    }
+
+    /// Replaces the quotation with a fresh one and reports how the rate moved.
+    ///
+    /// Use this once the quotation has expired, before approving the payout at the new rate.
+    pub async fn requote(&mut self) -> EversendResult<RateDelta, SendPayoutError> {
+        let previous = match &self.state {
+            PayoutState::Quoted(handle) => handle,
+            state => return Err(Self::invalid_transition("requote", state)),
+        };
+
+        let (handle, delta) = Payouts::new(self.eversend)
+            .requote_payout(previous, &self.request.quotation_params())
+            .await
+            .map_err(|err| err.map_operation(|PayoutQuoteError::Quote(err)| SendPayoutError::Quote(err)))?;
+
+        self.state = PayoutState::Quoted(handle);
+
+        Ok(delta)
+    }
+
+    /// Approves the quotation and submits the payout through [`SubmitPayout`], so it is made at most once.
+    ///
+    /// Fails with [`QuoteError::Expired`] once the quotation has lapsed, see [`PayoutFlow::requote`].
+    /// Dropping the flow instead of approving it abandons the quotation without moving any money.
+    pub async fn approve(&mut self) -> EversendResult<&PayoutState, SendPayoutError> {
+        let token = match &self.state {
+            PayoutState::Quoted(handle) => handle.token()?.to_string(),
+            state => return Err(Self::invalid_transition("approve", state)),
+        };
+
+        let params = self.request.submit_params(token);
+
+        let submission = Payouts::new(self.eversend)
+            .submit_payout(&params, &self.submit_options)
+            .await
+            .map_err(|err| err.map_operation(SendPayoutError::Submit))?;
+
+        self.request.transaction_ref = Some(submission.transaction_ref);
+        self.bank_account_check = submission.bank_account_check;
+        self.state = match submission.outcome {
+            PayoutOutcome::Submitted(transaction) => PayoutState::Submitted(transaction),
+            PayoutOutcome::Reconciled(transaction) => PayoutState::from_transaction(transaction),
+        };
+
+        Ok(&self.state)
+    }
+
+    /// Waits for the submitted payout to complete or fail.
+    ///
+    /// If `options.timeout` elapses first the flow moves to [`PayoutState::Pending`] and `wait` may
+    /// be called again. If the transaction was not seen at all by then, the timeout is returned as
+    /// [`SendPayoutError::Wait`] and the flow stays [`PayoutState::Submitted`].
+    pub async fn wait(&mut self, options: &WaitOptions) -> EversendResult<&PayoutState, SendPayoutError> {
+        let transaction_id = match &self.state {
+            PayoutState::Submitted(transaction) => transaction.transaction_id.clone(),
+            PayoutState::Pending(transaction) => transaction.transaction_id.clone(),
+            state => return Err(Self::invalid_transition("wait for", state)),
+        };
+
+        let result = self
+            .eversend
+            .transactions()
+            .wait_for_final_status(&transaction_id, options)
+            .await;
+
+        match result {
+            Ok(transaction) => self.state = PayoutState::from_transaction(transaction),
+            Err(EversendError::Operation(WaitForFinalStatusError::Timeout { last_seen: Some(transaction) })) => {
+                self.state = PayoutState::Pending(*transaction);
+            },
+            Err(err) => return Err(err.map_operation(SendPayoutError::Wait)),
+        }
+
+        Ok(&self.state)
+    }
+
+    fn invalid_transition(action: &'static str, state: &PayoutState) -> EversendError<SendPayoutError> {
+        EversendError::Operation(SendPayoutError::InvalidTransition {
+            action,
+            state: state.name(),
+        })
+    }
+}
+
+/// Drives a momo or bank payout end to end.
+#[async_trait]
+pub trait SendPayout<'a> {
+    /// Start a [`PayoutFlow`].
+    ///
+    /// Checks that the destination country supports the payment type and creates a quotation.
+    /// Nothing is paid out until [`PayoutFlow::approve`] is called, which must happen before the
+    /// quotation expires.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::payouts::*;
+    /// # use eversend_rust_sdk::transactions::WaitOptions;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend,TransactionRef};
+    ///
+    /// # async fn run() -> EversendResult<(), SendPayoutError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let mut payout = eversend
+    ///         .payouts()
+    ///         .send(PayoutRequest {
+    ///             amount: 1000,
+    ///             amount_type: String::from("SOURCE"),
+    ///             country: String::from("UG"),
+    ///             destination_currency: String::from("UGX"),
+    ///             recipient: PayoutRecipient::Momo {
+    ///                 first_name: String::from("John"),
+    ///                 last_name: String::from("Doe"),
+    ///                 phone_number: String::from("+256789123456"),
+    ///             },
+    ///             source_wallet: String::from("UGX"),
+    ///             transaction_ref: Some(TransactionRef::generate()),
+    ///         })
+    ///         .await?;
+    ///
+    ///     if payout.quotation().unwrap().total_fees == "0" {
+    ///         payout.approve().await?;
+    ///         payout.wait(&WaitOptions::default()).await?;
+    ///     }
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn send(
+        &self,
+        request: PayoutRequest
+    ) -> EversendResult<PayoutFlow<'a>, SendPayoutError>;
+}
+
+#[async_trait]
+impl<'a> SendPayout<'a> for Payouts<'a> {
+    async fn send(
+        &self,
+        request: PayoutRequest
+    ) -> EversendResult<PayoutFlow<'a>, SendPayoutError> {
+        let payment_type = request.recipient.payment_type();
+
+        let countries = self
+            .get_delivery_countries()
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))?;
+
+        let supported = countries
+            .iter()
+            .any(|country| country.country == request.country && country.payment_types.contains(&payment_type));
+
+        if !supported {
+            return Err(EversendError::Operation(SendPayoutError::UnsupportedDestination {
+                country: request.country,
+                payment_type,
+            }));
+        }
+
+        let handle = self
+            .quote_payout(&request.quotation_params())
+            .await
+            .map_err(|err| err.map_operation(|PayoutQuoteError::Quote(err)| SendPayoutError::Quote(err)))?;
+
+        Ok(PayoutFlow {
+            eversend: self.eversend,
+            request,
+            state: PayoutState::Quoted(handle),
+            submit_options: SubmitPayoutOptions::default(),
+            bank_account_check: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Mock};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend() -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn request() -> PayoutRequest {
+        PayoutRequest {
+            amount: 1000,
+            amount_type: String::from("SOURCE"),
+            country: String::from("UG"),
+            destination_currency: String::from("UGX"),
+            recipient: PayoutRecipient::Momo {
+                first_name: String::from("John"),
+                last_name: String::from("Doe"),
+                phone_number: String::from("+256789123456"),
+            },
+            source_wallet: String::from("UGX"),
+            transaction_ref: Some("SOMEREFERENCE".parse().unwrap()),
+        }
+    }
+
+    fn mock_countries() -> Mock {
+        mock("GET", "/payouts/countries")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "countries": [
+                            {
+                                "country": "UG",
+                                "id": "999",
+                                "name": "Uganda",
+                                "paymentTypes": ["momo"],
+                                "phonePrefix": "+256"
+                            }
+                        ]
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create()
+    }
+
+    // A JWT whose exp claim lies in 2022.
+    const EXPIRED_TOKEN: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJleHAiOjE2NjE4ODU1NTd9.signature";
+
+    fn mock_quotation() -> Mock {
+        mock_quotation_with_token("some-quotation-token")
+    }
+
+    fn mock_quotation_with_token(token: &str) -> Mock {
+        mock("POST", "/payouts/quotation")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "token": token,
+                        "quotation": {
+                            "sourceCountry": "UG",
+                            "sourceCurrency": "UGX",
+                            "sourceAmount": "1000",
+                            "destinationCountry": "UG",
+                            "destinationCurrency": "UGX",
+                            "destinationAmount": "1000",
+                            "exchangeRate": "1",
+                            "totalFees": "500",
+                            "totalAmount": "1500.00",
+                            "type": "momo",
+                            "amountType": "SOURCE",
+                            "amount": "1000"
+                        }
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create()
+    }
+
+    fn mock_payout() -> Mock {
+        mock("POST", "/payouts")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "token": "some-quotation-token",
+                "transactionRef": "SOMEREFERENCE"
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "transaction": {
+                            "transactionId": "BP11678735362605",
+                            "currency": "UGX",
+                            "type": "payout",
+                            "amount": 1000,
+                            "fees": 500,
+                            "userId": 3,
+                            "balanceBefore": 0,
+                            "balanceAfter": 0,
+                            "sourceCurrency": "UGX",
+                            "destinationCurrency": "UGX",
+                            "destinationAmount": "1000",
+                            "destinationCountry": "UG",
+                            "beneficiary": {
+                                "firstName": "John",
+                                "lastName": "Doe",
+                                "phoneNumber": "+256789123456"
+                            },
+                            "reason": null,
+                            "status": "pending",
+                            "createdAt": "2023-03-13T19:22:46.070Z",
+                            "updatedAt": "2023-03-13T19:22:46.071Z"
+                        }
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create()
+    }
+
+    #[tokio::test]
+    async fn it_drives_a_momo_payout_to_completion() {
+        let eversend = eversend();
+
+        let _countries = mock_countries();
+        let _quotation = mock_quotation();
+
+        let payout_mock = mock_payout();
+
+        let transaction_mock = mock("GET", "/transactions/BP11678735362605")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "transactions": [
+                            {
+                                "id": 793,
+                                "transactionId": "BP11678735362605",
+                                "type": "payout",
+                                "currency": "UGX",
+                                "amount": "1000",
+                                "fees": "500",
+                                "balanceBefore": "1500",
+                                "balanceAfter": "0",
+                                "destinationCurrency": "UGX",
+                                "destinationAmount": "1000",
+                                "accountId": 3,
+                                "meta": {
+                                    "source": {
+                                        "amount": 1000,
+                                        "balance": { "after": "0", "before": "1500" },
+                                        "currency": "UGX"
+                                    },
+                                    "destination": {
+                                        "amount": 1000,
+                                        "balance": { "after": "1000", "before": "0" },
+                                        "currency": "UGX"
+                                    }
+                                },
+                                "isRefunded": false,
+                                "status": "successful",
+                                "createdAt": "2023-03-13T19:22:46.070Z",
+                                "updatedAt": "2023-03-13T19:22:50.000Z"
+                            }
+                        ]
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create();
+
+        let payouts = eversend.payouts();
+        let mut payout = payouts.send(request()).await.unwrap();
+
+        assert_eq!(payout.state().name(), "quoted");
+        assert_eq!(payout.quotation().unwrap().total_fees, "500");
+
+        let state = payout.approve().await.unwrap();
+        assert!(matches!(state, PayoutState::Submitted(transaction) if transaction.transaction_id == "BP11678735362605"));
+
+        let options = WaitOptions {
+            initial_interval: Duration::from_millis(5),
+            timeout: Duration::from_millis(200),
+            ..WaitOptions::default()
+        };
+        let state = payout.wait(&options).await.unwrap();
+        assert!(matches!(state, PayoutState::Completed(transaction) if transaction.id == 793));
+
+        payout_mock.assert();
+        transaction_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_reports_a_timeout_when_the_transaction_is_never_seen() {
+        let eversend = eversend();
+
+        let _countries = mock_countries();
+        let _quotation = mock_quotation();
+        let _payout = mock_payout();
+
+        let _transaction = mock("GET", "/transactions/BP11678735362605")
+            .with_status(200)
+            .with_body(json!({ "code": 200, "data": { "transactions": [] }, "success": true }).to_string())
+            .create();
+
+        let payouts = eversend.payouts();
+        let mut payout = payouts.send(request()).await.unwrap();
+
+        payout.approve().await.unwrap();
+
+        let options = WaitOptions {
+            initial_interval: Duration::from_millis(5),
+            timeout: Duration::from_millis(50),
+            ..WaitOptions::default()
+        };
+        let result = payout.wait(&options).await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SendPayoutError::Wait(WaitForFinalStatusError::Timeout { last_seen: None })))
+        ));
+        assert_eq!(payout.state().name(), "submitted");
+    }
+
+    #[tokio::test]
+    async fn it_refuses_unsupported_destinations() {
+        let eversend = eversend();
+
+        let _countries = mock_countries();
+
+        let mut request = request();
+        request.recipient = PayoutRecipient::Bank {
+            first_name: String::from("John"),
+            last_name: String::from("Doe"),
+            phone_number: String::from("+256789123456"),
+            bank_account_name: String::from("John Doe"),
+            bank_account_number: String::from("12345"),
+            bank_code: String::from("1234"),
+            bank_name: String::from("World Bank"),
+        };
+
+        let result = eversend.payouts().send(request).await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SendPayoutError::UnsupportedDestination { payment_type: CountryPaymentType::BANK, .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_refuses_to_wait_before_approval() {
+        let eversend = eversend();
+
+        let _countries = mock_countries();
+        let _quotation = mock_quotation();
+
+        let payouts = eversend.payouts();
+        let mut payout = payouts.send(request()).await.unwrap();
+
+        let result = payout.wait(&WaitOptions::default()).await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SendPayoutError::InvalidTransition { state: "quoted", .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_refuses_to_approve_an_expired_quotation_until_requoted() {
+        let eversend = eversend();
+
+        let _countries = mock_countries();
+        let expired = mock_quotation_with_token(EXPIRED_TOKEN);
+
+        let payouts = eversend.payouts();
+        let mut payout = payouts.send(request()).await.unwrap();
+
+        let result = payout.approve().await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SendPayoutError::Quote(QuoteError::Expired { .. })))
+        ));
+
+        drop(expired);
+        let _fresh = mock_quotation();
+
+        let delta = payout.requote().await.unwrap();
+
+        assert_eq!(delta.difference(), 0.0);
+        assert!(matches!(payout.state(), PayoutState::Quoted(handle) if !handle.is_expired()));
+    }
+}
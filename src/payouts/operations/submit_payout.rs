@@ -0,0 +1,729 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{payouts::{CreateBankPayoutTransaction, CreateBankPayoutTransactionParams, CreateEversendPayoutTransaction, CreateEversendPayoutTransactionParams, CreateMomoPayoutTransaction, CreateMomoPayoutTransactionParams, BankAccountCheck, NameMatchPolicy, Payouts, Transaction, VerifyBankAccount, VerifyBankAccountError}, transactions::{self, GetTransactions, GetTransactionsParams, TransactionTypeOption, Transactions, WaitOptions}, EversendError, EversendResult, TransactionRef};
+
+/// A payout to submit through [`SubmitPayout`].
+#[derive(Serialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum SubmitPayoutParams {
+    Bank(CreateBankPayoutTransactionParams),
+    Eversend(CreateEversendPayoutTransactionParams),
+    Momo(CreateMomoPayoutTransactionParams),
+}
+
+impl SubmitPayoutParams {
+    /// The transaction reference, if one was set.
+    pub fn transaction_ref(&self) -> Option<&TransactionRef> {
+        match self {
+            Self::Bank(params) => params.transaction_ref.as_ref(),
+            Self::Eversend(params) => params.transaction_ref.as_ref(),
+            Self::Momo(params) => params.transaction_ref.as_ref(),
+        }
+    }
+
+    fn set_transaction_ref(&mut self, transaction_ref: TransactionRef) {
+        match self {
+            Self::Bank(params) => params.transaction_ref = Some(transaction_ref),
+            Self::Eversend(params) => params.transaction_ref = Some(transaction_ref),
+            Self::Momo(params) => params.transaction_ref = Some(transaction_ref),
+        }
+    }
+}
+
+/// Retry behaviour for [`SubmitPayout`].
+#[derive(Debug, Clone)]
+pub struct SubmitPayoutOptions {
+    /// How many times the payout may be sent in total while the connection cannot be made, in which
+    /// case it certainly did not reach Eversend. Defaults to 3.
+    pub max_attempts: u32,
+
+    /// How the reference is polled for after an ambiguous failure, giving a payout that did go
+    /// through time to show up. Defaults to polling for up to a minute, backing off from 1 second
+    /// to 10 seconds.
+    pub lookup: WaitOptions,
+
+    /// When set, the account name of a bank payout is checked through [`VerifyBankAccount`]
+    /// before anything is sent. Defaults to `None`.
+    pub verify_bank_account: Option<NameMatchPolicy>,
+}
+
+impl Default for SubmitPayoutOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            lookup: WaitOptions {
+                initial_interval: Duration::from_secs(1),
+                max_interval: Duration::from_secs(10),
+                multiplier: 2,
+                timeout: Duration::from_secs(60),
+            },
+            verify_bank_account: None,
+        }
+    }
+}
+
+/// How a [`PayoutSubmission`] came about.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)] // Returned once per submission, not worth boxing.
+pub enum PayoutOutcome {
+    /// The payout was created by this submission.
+    Submitted(Transaction),
+
+    /// The response was lost, but the payout was found by its reference.
+    Reconciled(transactions::Transaction),
+}
+
+/// The result of [`SubmitPayout`].
+#[derive(Debug, Clone)]
+pub struct PayoutSubmission {
+    /// The reference the payout was submitted with, generated if none was given.
+    pub transaction_ref: TransactionRef,
+
+    pub outcome: PayoutOutcome,
+
+    /// The account name check, for bank payouts verified through [`SubmitPayoutOptions::verify_bank_account`].
+    /// Check [`BankAccountCheck::is_match`] when mismatches are flagged rather than refused.
+    pub bank_account_check: Option<BankAccountCheck>,
+}
+
+impl PayoutSubmission {
+    /// The Eversend transaction ID of the payout.
+    pub fn transaction_id(&self) -> &str {
+        match &self.outcome {
+            PayoutOutcome::Submitted(transaction) => &transaction.transaction_id,
+            PayoutOutcome::Reconciled(transaction) => &transaction.transaction_id,
+        }
+    }
+}
+
+/// An error returned from [`SubmitPayout`].
+#[derive(Debug, Error)]
+pub enum SubmitPayoutError {
+    /// It could not be established whether the payout went through. It must not be resubmitted
+    /// with a different reference until its status has been checked.
+    #[error("could not confirm whether payout {transaction_ref} was made")]
+    Unresolved {
+        transaction_ref: TransactionRef,
+    },
+
+    /// The bank account could not be verified, or does not belong to the named recipient.
+    #[error(transparent)]
+    Verification(#[from] VerifyBankAccountError),
+}
+
+impl From<SubmitPayoutError> for EversendError<SubmitPayoutError> {
+    fn from(err: SubmitPayoutError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// The result of looking a payout up by its reference.
+#[allow(clippy::large_enum_variant)] // Short-lived, not worth boxing.
+pub(crate) enum PayoutLookup {
+    /// The payout was made.
+    Found(transactions::Transaction),
+
+    /// No payout with the reference exists, yet.
+    Absent,
+
+    /// The lookup failed, so the payout may or may not exist.
+    Unknown,
+}
+
+/// Submits a payout exactly once, even when responses get lost.
+#[async_trait]
+pub trait SubmitPayout {
+    /// Submit a momo, bank or Eversend payout.
+    ///
+    /// A `transaction_ref` is generated when none is given. The payout is only sent again when the
+    /// connection could not be made at all. When the outcome of a submission is unknown (a timeout,
+    /// a dropped connection or a 5xx response) the reference is polled for through
+    /// [`GetTransactions`] as set by [`SubmitPayoutOptions::lookup`]. If the payout does not show up
+    /// before the deadline, [`SubmitPayoutError::Unresolved`] is returned rather than risking
+    /// paying twice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::payouts::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), SubmitPayoutError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let submission = eversend
+    ///         .payouts()
+    ///         .submit_payout(
+    ///             &SubmitPayoutParams::Momo(CreateMomoPayoutTransactionParams {
+    ///                 country: String::from("UG"),
+    ///                 first_name: String::from("John"),
+    ///                 last_name: String::from("Doe"),
+    ///                 phone_number: String::from("+256789123456"),
+    ///                 token: String::from("some-token"),
+    ///                 transaction_ref: None,
+    ///             }),
+    ///             &SubmitPayoutOptions::default()
+    ///         )
+    ///         .await?;
+    ///
+    ///     println!("{} sent as {}", submission.transaction_id(), submission.transaction_ref);
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn submit_payout(
+        &self,
+        params: &SubmitPayoutParams,
+        options: &SubmitPayoutOptions
+    ) -> EversendResult<PayoutSubmission, SubmitPayoutError>;
+}
+
+#[async_trait]
+impl<'a> SubmitPayout for Payouts<'a> {
+    async fn submit_payout(
+        &self,
+        params: &SubmitPayoutParams,
+        options: &SubmitPayoutOptions
+    ) -> EversendResult<PayoutSubmission, SubmitPayoutError> {
+        let transaction_ref = params
+            .transaction_ref()
+            .cloned()
+            .unwrap_or_else(TransactionRef::generate);
+
+        let bank_account_check = match (params, &options.verify_bank_account) {
+            (SubmitPayoutParams::Bank(bank), Some(policy)) => Some(
+                self
+                    .verify_bank_account(bank, policy)
+                    .await
+                    .map_err(|err| err.map_operation(SubmitPayoutError::Verification))?
+            ),
+            _ => None,
+        };
+
+        let mut params = params.clone();
+        params.set_transaction_ref(transaction_ref.clone());
+
+        let max_attempts = options.max_attempts.max(1);
+
+        for attempt in 1..=max_attempts {
+            match self.post_payout(&params).await {
+                Ok(transaction) => {
+                    return Ok(PayoutSubmission {
+                        transaction_ref,
+                        outcome: PayoutOutcome::Submitted(transaction),
+                        bank_account_check,
+                    });
+                },
+                Err(err) if is_unsent(&err) && attempt < max_attempts => continue,
+                Err(err) if is_unsent(&err) || !is_ambiguous(&err) => return Err(err),
+                Err(_) => break,
+            }
+        }
+
+        match self.poll_payout(&transaction_ref, &options.lookup).await {
+            PayoutLookup::Found(transaction) => Ok(PayoutSubmission {
+                transaction_ref,
+                outcome: PayoutOutcome::Reconciled(transaction),
+                bank_account_check,
+            }),
+            PayoutLookup::Absent | PayoutLookup::Unknown => {
+                Err(EversendError::Operation(SubmitPayoutError::Unresolved { transaction_ref }))
+            },
+        }
+    }
+}
+
+impl<'a> Payouts<'a> {
+    async fn post_payout(
+        &self,
+        params: &SubmitPayoutParams
+    ) -> EversendResult<Transaction, SubmitPayoutError> {
+        // Checked here, as the operations below expect a token to be set.
+        self.eversend.api_token().map_err(|_| EversendError::ApiTokenMissing)?;
+
+        match params {
+            SubmitPayoutParams::Bank(params) => self
+                .create_bank_payout_transaction(params)
+                .await
+                .map_err(|err| err.map_operation(|err| match err {})),
+            SubmitPayoutParams::Eversend(params) => self
+                .create_eversend_payout_transaction(params)
+                .await
+                .map_err(|err| err.map_operation(|err| match err {})),
+            SubmitPayoutParams::Momo(params) => self
+                .create_momo_payout_transaction(params)
+                .await
+                .map_err(|err| err.map_operation(|err| match err {})),
+        }
+    }
+
+    /// Looks a payout up by reference until it is found or `options.timeout` elapses.
+    async fn poll_payout(&self, transaction_ref: &TransactionRef, options: &WaitOptions) -> PayoutLookup {
+        let deadline = Instant::now().checked_add(options.timeout);
+        let multiplier = options.multiplier.max(1);
+        let mut interval = options.initial_interval;
+
+        loop {
+            let lookup = self.find_payout(transaction_ref).await;
+
+            if let PayoutLookup::Found(_) = lookup {
+                return lookup;
+            }
+
+            let delay = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return lookup;
+                    }
+
+                    interval.min(deadline - now)
+                },
+                None => interval,
+            };
+
+            tokio::time::sleep(delay).await;
+
+            interval = interval.saturating_mul(multiplier).min(options.max_interval);
+        }
+    }
+
+    /// Looks a payout up by reference, going through every page of matches.
+    pub(crate) async fn find_payout(&self, transaction_ref: &TransactionRef) -> PayoutLookup {
+        let transactions = Transactions::new(self.eversend);
+        let mut page_number = 1;
+
+        loop {
+            let params = GetTransactionsParams::builder()
+                .set_search(transaction_ref.as_str())
+                .set_type(TransactionTypeOption::PAYOUT)
+                .set_page(page_number)
+                .build();
+
+            let page = match params {
+                Ok(params) => match transactions.get_transactions(&params).await {
+                    Ok(page) => page,
+                    Err(_) => return PayoutLookup::Unknown,
+                },
+                Err(_) => return PayoutLookup::Unknown,
+            };
+
+            let has_next_page = page.has_next_page() && !page.transactions.is_empty();
+
+            let found = page.transactions
+                .into_iter()
+                .find(|transaction| transaction.transaction_ref.as_deref() == Some(transaction_ref.as_str()));
+
+            if let Some(transaction) = found {
+                return PayoutLookup::Found(transaction);
+            }
+
+            if !has_next_page {
+                return PayoutLookup::Absent;
+            }
+
+            page_number += 1;
+        }
+    }
+}
+
+/// Returns `true` if the payout certainly did not reach Eversend, as no connection could be made.
+fn is_unsent<E>(err: &EversendError<E>) -> bool {
+    matches!(err, EversendError::RequestError(err) if err.is_connect() || err.is_builder())
+}
+
+/// Returns `true` if the payout may or may not have been made, i.e. no 4xx response was received.
+fn is_ambiguous<E>(err: &EversendError<E>) -> bool {
+    match err {
+        EversendError::RequestError(err) => match err.status() {
+            Some(status) => status.is_server_error(),
+            None => !err.is_builder(),
+        },
+        // The payout was accepted, but the response could not be read.
+        EversendError::Decode(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend() -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn params(transaction_ref: Option<&str>) -> SubmitPayoutParams {
+        SubmitPayoutParams::Momo(CreateMomoPayoutTransactionParams {
+            country: String::from("UG"),
+            first_name: String::from("John"),
+            last_name: String::from("Doe"),
+            phone_number: String::from("+256789123456"),
+            token: String::from("some-token"),
+            transaction_ref: transaction_ref.map(|transaction_ref| transaction_ref.parse().unwrap()),
+        })
+    }
+
+    fn options() -> SubmitPayoutOptions {
+        SubmitPayoutOptions {
+            max_attempts: 3,
+            lookup: WaitOptions {
+                initial_interval: Duration::from_millis(5),
+                max_interval: Duration::from_millis(10),
+                multiplier: 2,
+                timeout: Duration::from_millis(50),
+            },
+            verify_bank_account: None,
+        }
+    }
+
+    fn payout_body() -> String {
+        json!({
+            "code": 200,
+            "data": {
+                "transaction": {
+                    "transactionId": "BP11678735362605",
+                    "transactionRef": "SOMEREFERENCE",
+                    "currency": "UGX",
+                    "type": "payout",
+                    "amount": 1000,
+                    "fees": 0,
+                    "userId": 3,
+                    "balanceBefore": 0,
+                    "balanceAfter": 0,
+                    "sourceCurrency": "UGX",
+                    "destinationCurrency": "UGX",
+                    "destinationAmount": "1000",
+                    "destinationCountry": "UG",
+                    "beneficiary": {
+                        "firstName": "John",
+                        "lastName": "Doe",
+                        "phoneNumber": "+256789123456"
+                    },
+                    "reason": null,
+                    "status": "pending",
+                    "createdAt": "2023-03-13T19:22:46.070Z",
+                    "updatedAt": "2023-03-13T19:22:46.071Z"
+                }
+            },
+            "success": true
+        }).to_string()
+    }
+
+    fn transactions_body(transaction_refs: &[&str]) -> String {
+        transactions_page_body(transaction_refs, transaction_refs.len(), 1)
+    }
+
+    fn transactions_page_body(transaction_refs: &[&str], total: usize, page: u32) -> String {
+        let transactions: Vec<_> = transaction_refs
+            .iter()
+            .map(|transaction_ref| json!({
+                "id": 794,
+                "transactionId": "BP11678735362605",
+                "transactionRef": transaction_ref,
+                "type": "payout",
+                "currency": "UGX",
+                "amount": "1000",
+                "balanceBefore": "1000",
+                "balanceAfter": "0",
+                "destinationAmount": "1000",
+                "accountId": 3,
+                "meta": {
+                    "source": {
+                        "amount": 1000,
+                        "balance": { "after": "0", "before": "1000" },
+                        "currency": "UGX"
+                    },
+                    "destination": {
+                        "amount": 1000,
+                        "balance": { "after": "1000", "before": "0" },
+                        "currency": "UGX"
+                    }
+                },
+                "isRefunded": false,
+                "status": "pending",
+                "createdAt": "2023-03-13T19:22:46.070Z",
+                "updatedAt": "2023-03-13T19:22:46.071Z"
+            }))
+            .collect();
+
+        json!({
+            "code": 200,
+            "data": {
+                "total_payouts": "0",
+                "total_collections": "0",
+                "balance": 0,
+                "transactions": transactions,
+                "total": total,
+                "limit": 10,
+                "page": page
+            },
+            "success": true
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn it_generates_a_transaction_ref_when_none_is_given() {
+        let mock = mock("POST", "/payouts")
+            .match_body(Matcher::Regex(String::from(r#""transactionRef":"[0-9A-Z]{27}""#)))
+            .with_status(200)
+            .with_body(payout_body())
+            .create();
+
+        let submission = eversend()
+            .payouts()
+            .submit_payout(&params(None), &options())
+            .await
+            .unwrap();
+
+        assert!(submission.transaction_ref.timestamp().is_some());
+        assert!(matches!(submission.outcome, PayoutOutcome::Submitted(_)));
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_reconciles_instead_of_resubmitting_after_a_server_error() {
+        let payout = mock("POST", "/payouts")
+            .with_status(502)
+            .expect(1)
+            .create();
+
+        let lookup = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "search": "SOMEREFERENCE", "type": "payout" })))
+            .with_status(200)
+            .with_body(transactions_body(&["SOMEREFERENCE"]))
+            .create();
+
+        let submission = eversend()
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await
+            .unwrap();
+
+        assert_eq!(submission.transaction_id(), "BP11678735362605");
+        assert!(matches!(submission.outcome, PayoutOutcome::Reconciled(_)));
+
+        payout.assert();
+        lookup.assert();
+    }
+
+    #[tokio::test]
+    async fn it_looks_through_every_page_of_matches() {
+        let payout = mock("POST", "/payouts")
+            .with_status(502)
+            .expect(1)
+            .create();
+
+        let others = ["OTHERREFERENCE"; 10];
+
+        let first = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "search": "SOMEREFERENCE", "page": 1 })))
+            .with_status(200)
+            .with_body(transactions_page_body(&others, 11, 1))
+            .expect(1)
+            .create();
+
+        let second = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "search": "SOMEREFERENCE", "page": 2 })))
+            .with_status(200)
+            .with_body(transactions_page_body(&["SOMEREFERENCE"], 11, 2))
+            .expect(1)
+            .create();
+
+        let submission = eversend()
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await
+            .unwrap();
+
+        assert!(matches!(submission.outcome, PayoutOutcome::Reconciled(_)));
+
+        payout.assert();
+        first.assert();
+        second.assert();
+    }
+
+    #[tokio::test]
+    async fn it_polls_and_does_not_resubmit_when_the_payout_was_not_found() {
+        let payout = mock("POST", "/payouts")
+            .with_status(503)
+            .expect(1)
+            .create();
+
+        let lookup = mock("POST", "/transactions")
+            .with_status(200)
+            .with_body(transactions_body(&["ANOTHERREFERENCE"]))
+            .expect_at_least(2)
+            .create();
+
+        let result = eversend()
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SubmitPayoutError::Unresolved { transaction_ref })) if transaction_ref.as_str() == "SOMEREFERENCE"
+        ));
+
+        payout.assert();
+        lookup.assert();
+    }
+
+    #[tokio::test]
+    async fn it_resends_when_the_connection_could_not_be_made() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url("http://127.0.0.1:1")
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let result = eversend
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await;
+
+        assert!(matches!(result, Err(EversendError::RequestError(err)) if err.is_connect()));
+    }
+
+    #[tokio::test]
+    async fn it_refuses_to_submit_without_an_api_token() {
+        let eversend = Eversend::new(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        );
+
+        let result = eversend
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await;
+
+        assert!(matches!(result, Err(EversendError::ApiTokenMissing)));
+    }
+
+    #[tokio::test]
+    async fn it_gives_up_when_the_lookup_keeps_failing() {
+        let payout = mock("POST", "/payouts")
+            .with_status(500)
+            .expect(1)
+            .create();
+
+        let lookup = mock("POST", "/transactions")
+            .with_status(500)
+            .expect_at_least(2)
+            .create();
+
+        let result = eversend()
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SubmitPayoutError::Unresolved { transaction_ref })) if transaction_ref.as_str() == "SOMEREFERENCE"
+        ));
+
+        payout.assert();
+        lookup.assert();
+    }
+
+    #[tokio::test]
+    async fn it_does_not_retry_client_errors() {
+        let payout = mock("POST", "/payouts")
+            .with_status(400)
+            .expect(1)
+            .create();
+
+        let lookup = mock("POST", "/transactions")
+            .expect(0)
+            .create();
+
+        let result = eversend()
+            .payouts()
+            .submit_payout(&params(Some("SOMEREFERENCE")), &options())
+            .await;
+
+        assert!(matches!(result, Err(EversendError::RequestError(_))));
+
+        payout.assert();
+        lookup.assert();
+    }
+
+    #[tokio::test]
+    async fn it_verifies_the_bank_account_before_submitting() {
+        let details = mock("POST", "/beneficiaries/accounts/banks")
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "bank_code": "14",
+                        "account_number": "0012033048",
+                        "account_name": "JANE SMITH"
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .expect(1)
+            .create();
+
+        let payout = mock("POST", "/payouts")
+            .expect(0)
+            .create();
+
+        let params = SubmitPayoutParams::Bank(CreateBankPayoutTransactionParams {
+            country: String::from("UG"),
+            first_name: String::from("John"),
+            last_name: String::from("Doe"),
+            phone_number: String::from("+256789123456"),
+            token: String::from("some-token"),
+            transaction_ref: None,
+            bank_account_name: String::from("John Doe"),
+            bank_account_number: String::from("0012033048"),
+            bank_code: String::from("14"),
+            bank_name: String::from("World Bank"),
+        });
+
+        let result = eversend()
+            .payouts()
+            .submit_payout(
+                &params,
+                &SubmitPayoutOptions {
+                    verify_bank_account: Some(NameMatchPolicy::default()),
+                    ..options()
+                }
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(SubmitPayoutError::Verification(VerifyBankAccountError::NameMismatch(_))))
+        ));
+
+        details.assert();
+        payout.assert();
+    }
+}
@@ -0,0 +1,347 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{beneficiaries::{GetBankDetails, GetBankDetailsParams}, payouts::{CreateBankPayoutTransactionParams, Payouts}, EversendError, EversendResult};
+
+/// What to do when the resolved account name does not match the provided one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MismatchAction {
+    /// Fail with [`VerifyBankAccountError::NameMismatch`]. The default.
+    #[default]
+    Refuse,
+
+    /// Let the payout go ahead and report the mismatch in the [`BankAccountCheck`].
+    Flag,
+}
+
+/// How closely the account name on record must match the name given for a bank payout.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NameMatchPolicy {
+    /// Lowest [`name_similarity`] accepted as a match, between 0 and 1. Defaults to 0.85.
+    pub threshold: f64,
+
+    /// What to do when the names don't match. Defaults to [`MismatchAction::Refuse`].
+    pub on_mismatch: MismatchAction,
+}
+
+impl Default for NameMatchPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.85,
+            on_mismatch: MismatchAction::Refuse,
+        }
+    }
+}
+
+/// The outcome of comparing a provided account name with the one on record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BankAccountCheck {
+    /// The `bank_account_name` given for the payout.
+    pub provided_name: String,
+
+    /// The account name returned by [`GetBankDetails`].
+    pub resolved_name: String,
+
+    /// The [`name_similarity`] of both names.
+    pub score: f64,
+
+    /// The threshold the score was held against.
+    pub threshold: f64,
+}
+
+impl BankAccountCheck {
+    /// Returns `true` if the names are similar enough.
+    pub fn is_match(&self) -> bool {
+        self.score >= self.threshold
+    }
+}
+
+/// An error returned from [`VerifyBankAccount`].
+#[derive(Debug, Error)]
+pub enum VerifyBankAccountError {
+    /// The account belongs to someone else, as far as the names tell.
+    #[error("account is held by {:?}, not {:?} (similarity {:.2})", .0.resolved_name, .0.provided_name, .0.score)]
+    NameMismatch(BankAccountCheck),
+}
+
+impl From<VerifyBankAccountError> for EversendError<VerifyBankAccountError> {
+    fn from(err: VerifyBankAccountError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Scores how alike two personal names are, from 0 (nothing in common) to 1 (the same).
+///
+/// Case, punctuation and word order are ignored, so `"DOE, JOHN"` matches `"John Doe"`. When one
+/// name has at least two words and each of them closely matches a different word of the other,
+/// extra words such as a middle name are not held against it.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let a = name_tokens(a);
+    let b = name_tokens(b);
+
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let whole = strsim::normalized_levenshtein(&a.join(" "), &b.join(" "));
+
+    let (shorter, longer) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+
+    if shorter.len() < 2 {
+        return whole;
+    }
+
+    // Pair the words up one to one, best matches first, so a repeated word is not matched twice.
+    let mut pairs = shorter
+        .iter()
+        .enumerate()
+        .flat_map(|(i, token)| {
+            longer
+                .iter()
+                .enumerate()
+                .map(move |(j, other)| (strsim::normalized_levenshtein(token, other), i, j))
+        })
+        .collect::<Vec<_>>();
+
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut used_shorter = vec![false; shorter.len()];
+    let mut used_longer = vec![false; longer.len()];
+    let mut total = 0.0;
+
+    for (score, i, j) in pairs {
+        if !used_shorter[i] && !used_longer[j] {
+            used_shorter[i] = true;
+            used_longer[j] = true;
+            total += score;
+        }
+    }
+
+    let subset = total / shorter.len() as f64;
+
+    whole.max(subset)
+}
+
+fn name_tokens(name: &str) -> Vec<String> {
+    let mut tokens = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_uppercase)
+        .collect::<Vec<_>>();
+
+    tokens.sort();
+
+    tokens
+}
+
+/// Checks the account name of a bank payout against the one on record before paying out.
+#[async_trait]
+pub trait VerifyBankAccount {
+    /// Resolve the account of a bank payout through [`GetBankDetails`] and compare its name with
+    /// `bank_account_name`.
+    ///
+    /// Fails with [`VerifyBankAccountError::NameMismatch`] if the names don't match and the policy
+    /// refuses mismatches. Otherwise the [`BankAccountCheck`] is returned for the caller to inspect.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::payouts::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), VerifyBankAccountError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let check = eversend
+    ///         .payouts()
+    ///         .verify_bank_account(
+    ///             &CreateBankPayoutTransactionParams {
+    ///                 country: String::from("UG"),
+    ///                 first_name: String::from("John"),
+    ///                 last_name: String::from("Doe"),
+    ///                 phone_number: String::from("+256789123456"),
+    ///                 token: String::from("some-token"),
+    ///                 transaction_ref: None,
+    ///                 bank_account_name: String::from("John Doe"),
+    ///                 bank_account_number: String::from("12345"),
+    ///                 bank_code: String::from("1234"),
+    ///                 bank_name: String::from("World Bank"),
+    ///             },
+    ///             &NameMatchPolicy {
+    ///                 threshold: 0.9,
+    ///                 on_mismatch: MismatchAction::Flag,
+    ///             }
+    ///         )
+    ///         .await?;
+    ///
+    ///     if !check.is_match() {
+    ///         println!("paying {} instead", check.resolved_name);
+    ///     }
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn verify_bank_account(
+        &self,
+        params: &CreateBankPayoutTransactionParams,
+        policy: &NameMatchPolicy
+    ) -> EversendResult<BankAccountCheck, VerifyBankAccountError>;
+}
+
+#[async_trait]
+impl<'a> VerifyBankAccount for Payouts<'a> {
+    async fn verify_bank_account(
+        &self,
+        params: &CreateBankPayoutTransactionParams,
+        policy: &NameMatchPolicy
+    ) -> EversendResult<BankAccountCheck, VerifyBankAccountError> {
+        let details = self
+            .eversend
+            .beneficiaries()
+            .get_bank_details(
+                &GetBankDetailsParams {
+                    account_number: params.bank_account_number.clone(),
+                    bank_code: params.bank_code.clone(),
+                    country_code: params.country.clone(),
+                }
+            )
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))?;
+
+        let check = BankAccountCheck {
+            score: name_similarity(&params.bank_account_name, &details.account_name),
+            provided_name: params.bank_account_name.clone(),
+            resolved_name: details.account_name,
+            threshold: policy.threshold,
+        };
+
+        if !check.is_match() && policy.on_mismatch == MismatchAction::Refuse {
+            return Err(EversendError::Operation(VerifyBankAccountError::NameMismatch(check)));
+        }
+
+        Ok(check)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend() -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build()
+    }
+
+    fn params(bank_account_name: &str) -> CreateBankPayoutTransactionParams {
+        CreateBankPayoutTransactionParams {
+            country: String::from("UG"),
+            first_name: String::from("John"),
+            last_name: String::from("Doe"),
+            phone_number: String::from("+256789123456"),
+            token: String::from("some-token"),
+            transaction_ref: None,
+            bank_account_name: String::from(bank_account_name),
+            bank_account_number: String::from("0012033048"),
+            bank_code: String::from("14"),
+            bank_name: String::from("World Bank"),
+        }
+    }
+
+    fn mock_bank_details(account_name: &str) -> mockito::Mock {
+        mock("POST", "/beneficiaries/accounts/banks")
+            .match_body(Matcher::Json(json!({
+                "accountNumber": "0012033048",
+                "bankCode": "14",
+                "countryCode": "UG"
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "bank_code": "14",
+                        "account_number": "0012033048",
+                        "account_name": account_name
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create()
+    }
+
+    #[test]
+    fn it_scores_name_similarity() {
+        assert_eq!(name_similarity("John Doe", "JOHN DOE"), 1.0);
+        assert_eq!(name_similarity("Doe, John", "john doe"), 1.0);
+        assert_eq!(name_similarity("John Doe", "JOHN PAUL DOE"), 1.0);
+        assert!(name_similarity("Jon Doe", "JOHN DOE") > 0.85);
+        assert!(name_similarity("John", "JOHN PAUL DOE") < 0.5);
+        assert!(name_similarity("Jane Smith", "JOHN DOE") < 0.5);
+        assert_eq!(name_similarity("", "JOHN DOE"), 0.0);
+    }
+
+    #[test]
+    fn it_matches_each_word_only_once() {
+        assert!(name_similarity("John John", "JOHN DOE") < 0.85);
+        assert!(name_similarity("Doe Doe", "JOHN PAUL DOE") < 0.85);
+        assert_eq!(name_similarity("John John Doe", "JOHN DOE JOHN"), 1.0);
+    }
+
+    #[tokio::test]
+    async fn it_accepts_a_matching_account_name() {
+        let mock = mock_bank_details("DOE JOHN");
+
+        let check = eversend()
+            .payouts()
+            .verify_bank_account(&params("John Doe"), &NameMatchPolicy::default())
+            .await
+            .unwrap();
+
+        assert!(check.is_match());
+        assert_eq!(check.resolved_name, "DOE JOHN");
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_refuses_or_flags_a_mismatched_account_name() {
+        let mock = mock_bank_details("JANE SMITH")
+            .expect(2);
+
+        let refused = eversend()
+            .payouts()
+            .verify_bank_account(&params("John Doe"), &NameMatchPolicy::default())
+            .await;
+
+        assert!(matches!(
+            refused,
+            Err(EversendError::Operation(VerifyBankAccountError::NameMismatch(check))) if check.resolved_name == "JANE SMITH"
+        ));
+
+        let flagged = eversend()
+            .payouts()
+            .verify_bank_account(
+                &params("John Doe"),
+                &NameMatchPolicy { on_mismatch: MismatchAction::Flag, ..NameMatchPolicy::default() }
+            )
+            .await
+            .unwrap();
+
+        assert!(!flagged.is_match());
+
+        mock.assert();
+    }
+}
@@ -1,6 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Beneficiary {
     /// The country.
     pub country: Option<String>,
@@ -40,4 +41,8 @@ pub struct Beneficiary {
     /// The bank Name.
     #[serde(rename = "bankName")]
     pub bank_name: Option<String>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+use crate::core::open_enum_schema;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Country {
 
     pub country: String,
@@ -14,9 +17,13 @@ pub struct Country {
 
     #[serde(rename = "phonePrefix")]
     pub phone_prefix: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
 pub enum CountryPaymentType {
     #[serde(rename = "momo")]
     MOMO,
@@ -25,5 +32,11 @@ pub enum CountryPaymentType {
     EVERSEND,
 
     #[serde(rename = "bank")]
-    BANK
+    BANK,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
+
+open_enum_schema!(CountryPaymentType, ["momo", "eversend", "bank"]);
@@ -1,6 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug)]
+use crate::QuoteError;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Quotation {
 
     pub amount: String,
@@ -38,10 +41,24 @@ pub struct Quotation {
     #[serde(rename = "type")]
     pub transaction_type: String,
 
-    pub merchant: Option<Merchant>
+    pub merchant: Option<Merchant>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Quotation {
+    /// The exchange rate as a number.
+    pub fn rate(&self) -> Result<f64, QuoteError> {
+        self.exchange_rate
+            .parse()
+            .map_err(|_| QuoteError::InvalidRate(self.exchange_rate.clone()))
+    }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Merchant {
     pub result: String,
 
@@ -68,10 +85,19 @@ pub struct Merchant {
     pub phone_number: PhoneNumber,
 
     pub tag: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PhoneNumber {
     pub prefix: String,
     pub number: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
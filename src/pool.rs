@@ -0,0 +1,14 @@
+//! A pool of [`Eversend`](crate::Eversend) clients, one per business account.
+//!
+//! Platforms operating several Eversend business accounts, e.g. one per country entity, register
+//! each account as a [`Tenant`] of an [`EversendPool`] and check clients out by tenant key. The pool
+//! generates and renews API tokens, limits how often and how many clients of each tenant are
+//! checked out at once, and how many requests each tenant sends per second.
+
+mod eversend_pool;
+mod pooled_client;
+mod tenant;
+
+pub use eversend_pool::*;
+pub use pooled_client::*;
+pub use tenant::*;
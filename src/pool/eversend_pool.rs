@@ -0,0 +1,398 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use thiserror::Error;
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
+
+use crate::{
+    auth::GenerateApiToken,
+    core::RateLimiter,
+    pool::{PooledClient, Tenant},
+    Eversend,
+    EversendError,
+    EversendResult,
+};
+
+/// An error returned from [`EversendPool::get`].
+#[derive(Debug, Error)]
+pub enum PoolError {
+    #[error("no tenant is registered under this key")]
+    UnknownTenant,
+}
+
+impl From<PoolError> for EversendError<PoolError> {
+    fn from(err: PoolError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Options for an [`EversendPool`].
+#[derive(Debug, Clone, Copy)]
+pub struct PoolOptions {
+    /// How long an API token is used when its expiry cannot be read from it. Defaults to 30
+    /// minutes.
+    pub token_lifetime: Duration,
+
+    /// How long before it expires an API token is renewed. Defaults to 1 minute.
+    pub token_renewal_margin: Duration,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            token_lifetime: Duration::from_secs(30 * 60),
+            token_renewal_margin: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Eversend clients of several business accounts, keyed by tenant.
+///
+/// Each tenant has its own credentials, API token, caches and
+/// [`TenantLimits`](crate::pool::TenantLimits). Tokens are generated on first use and renewed
+/// shortly before they expire.
+///
+/// # Examples
+/// ```
+/// # use eversend_rust_sdk::EversendResult;
+/// # use eversend_rust_sdk::pool::*;
+/// use eversend_rust_sdk::wallets::GetWallets;
+/// use eversend_rust_sdk::{ClientId,ClientSecret};
+///
+/// # async fn run() -> EversendResult<(), PoolError> {
+///     let mut pool = EversendPool::new();
+///
+///     pool.insert("UG", Tenant::new(
+///         &ClientId::from("sk_example_123456789"),
+///         &ClientSecret::from("sk_example_123456780")
+///     ));
+///
+///     pool.insert("KE", Tenant {
+///         limits: TenantLimits {
+///             max_concurrent: 2,
+///             max_checkouts_per_second: Some(5),
+///             max_requests_per_second: Some(20),
+///         },
+///         ..Tenant::new(
+///             &ClientId::from("sk_example_223456789"),
+///             &ClientSecret::from("sk_example_223456780")
+///         )
+///     });
+///
+///     let wallets = pool
+///         .get("KE")
+///         .await?
+///         .wallets()
+///         .get_wallets()
+///         .await
+///         .map_err(|err| err.map_operation(|err| match err {}))?;
+///
+///     Ok(())
+/// # }
+/// ```
+pub struct EversendPool<K> {
+    options: PoolOptions,
+    tenants: HashMap<K, TenantState>,
+}
+
+struct TenantState {
+    /// The client of the tenant, without an API token.
+    eversend: Eversend,
+    permits: Arc<Semaphore>,
+    checkouts: Option<RateLimiter>,
+
+    /// Locked while a token is generated, so concurrent checkouts share the same token.
+    token: AsyncMutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    eversend: Eversend,
+    expires_at: SystemTime,
+}
+
+impl<K: Eq + Hash> EversendPool<K> {
+    /// Returns an empty pool with the default options.
+    pub fn new() -> Self {
+        Self::with_options(PoolOptions::default())
+    }
+
+    /// Returns an empty pool.
+    pub fn with_options(options: PoolOptions) -> Self {
+        Self {
+            options,
+            tenants: HashMap::new(),
+        }
+    }
+
+    /// Registers a tenant, replacing any tenant registered under the same key.
+    pub fn insert(&mut self, key: K, tenant: Tenant) {
+        let mut builder = Eversend::builder(&tenant.client_id, &tenant.client_secret);
+
+        if let Some(base_url) = &tenant.base_url {
+            builder = builder.set_base_url(base_url);
+        }
+
+        if let Some(max_requests_per_second) = tenant.limits.max_requests_per_second {
+            builder = builder.set_max_requests_per_second(max_requests_per_second);
+        }
+
+        self.tenants.insert(key, TenantState {
+            eversend: builder.build(),
+            permits: Arc::new(Semaphore::new(tenant.limits.max_concurrent.max(1))),
+            checkouts: tenant.limits.max_checkouts_per_second.and_then(RateLimiter::new),
+            token: AsyncMutex::new(None),
+        });
+    }
+
+    /// Unregisters a tenant. Clients already checked out keep working.
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.tenants.remove(key).is_some()
+    }
+
+    /// Returns `true` if a tenant is registered under `key`.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.tenants.contains_key(key)
+    }
+
+    /// The keys of every tenant, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.tenants.keys()
+    }
+
+    /// Check out the client of a tenant.
+    ///
+    /// Waits for the tenant to be within its [`TenantLimits`](crate::pool::TenantLimits), and
+    /// generates an API token if the tenant has none or it is about to expire. Requests made with
+    /// the client afterwards wait for the request rate limit of the tenant, if any.
+    pub async fn get<Q>(&self, key: &Q) -> EversendResult<PooledClient, PoolError>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let tenant = self.tenants.get(key).ok_or(PoolError::UnknownTenant)?;
+
+        let permit = tenant
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+
+        if let Some(checkouts) = &tenant.checkouts {
+            checkouts.wait().await;
+        }
+
+        let eversend = self.authenticated(tenant).await?;
+
+        Ok(PooledClient {
+            eversend,
+            _permit: permit,
+        })
+    }
+
+    /// Forget the API token of a tenant, e.g. after it was rejected as
+    /// [`Unauthorized`](EversendError::Unauthorized). The next checkout generates a new one.
+    pub async fn invalidate_token<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        if let Some(tenant) = self.tenants.get(key) {
+            *tenant.token.lock().await = None;
+        }
+    }
+
+    async fn authenticated(&self, tenant: &TenantState) -> EversendResult<Eversend, PoolError> {
+        let mut token = tenant.token.lock().await;
+        let renew_after = SystemTime::now() + self.options.token_renewal_margin;
+
+        if let Some(token) = token.as_ref().filter(|token| token.expires_at > renew_after) {
+            return Ok(token.eversend.clone());
+        }
+
+        let api_token = tenant
+            .eversend
+            .auth()
+            .generate_api_token()
+            .await
+            .map_err(|err| err.map_operation(|err| match err {}))?;
+
+        let expires_at = api_token
+            .expires_at()
+            .unwrap_or_else(|| SystemTime::now() + self.options.token_lifetime);
+
+        let eversend = tenant.eversend.with_api_token(api_token);
+
+        *token = Some(CachedToken {
+            eversend: eversend.clone(),
+            expires_at,
+        });
+
+        Ok(eversend)
+    }
+}
+
+impl<K: Eq + Hash> Default for EversendPool<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crate::{pool::TenantLimits, wallets::GetWallets, ClientId, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher};
+    use serde_json::json;
+    use tokio;
+
+    fn tenant(prefix: &str, limits: TenantLimits) -> Tenant {
+        Tenant {
+            base_url: Some(format!("{}/{}", mockito::server_url(), prefix)),
+            limits,
+            ..Tenant::new(
+                &ClientId::from(format!("{}_client_id", prefix)),
+                &ClientSecret::from(format!("{}_client_secret", prefix))
+            )
+        }
+    }
+
+    fn token_mock(prefix: &str, token: &str) -> mockito::Mock {
+        mock("GET", format!("/{}/auth/token", prefix).as_str())
+            .match_header("clientId", format!("{}_client_id", prefix).as_str())
+            .with_status(200)
+            .with_body(json!({ "status": 200, "token": token }).to_string())
+    }
+
+    #[tokio::test]
+    async fn it_routes_calls_by_tenant_and_reuses_tokens() {
+        let mut pool = EversendPool::new();
+        pool.insert("UG", tenant("pool-ug", TenantLimits::default()));
+        pool.insert("KE", tenant("pool-ke", TenantLimits::default()));
+
+        let ug_token = token_mock("pool-ug", "ug_token").expect(1).create();
+        let ke_token = token_mock("pool-ke", "ke_token").expect(1).create();
+
+        let wallets = mock("GET", Matcher::Regex(String::from("^/pool-(ug|ke)/wallets$")))
+            .match_header("authorization", Matcher::Regex(String::from("^Bearer (ug|ke)_token$")))
+            .with_status(200)
+            .with_body(json!({ "code": 200, "data": [], "success": true }).to_string())
+            .expect(3)
+            .create();
+
+        pool.get("UG").await.unwrap().wallets().get_wallets().await.unwrap();
+        pool.get("UG").await.unwrap().wallets().get_wallets().await.unwrap();
+        pool.get("KE").await.unwrap().wallets().get_wallets().await.unwrap();
+
+        assert!(matches!(pool.get("TZ").await, Err(EversendError::Operation(PoolError::UnknownTenant))));
+
+        ug_token.assert();
+        ke_token.assert();
+        wallets.assert();
+    }
+
+    #[tokio::test]
+    async fn it_renews_expired_and_invalidated_tokens() {
+        let mut pool = EversendPool::new();
+        pool.insert("UG", tenant("pool-renew", TenantLimits::default()));
+
+        // {"exp":1}, long expired.
+        let expired = token_mock("pool-renew", "eyJhbGciOiJIUzI1NiJ9.eyJleHAiOjF9.c2lnbmF0dXJl").expect(2).create();
+
+        pool.get("UG").await.unwrap();
+        pool.get("UG").await.unwrap();
+
+        expired.assert();
+        drop(expired);
+
+        let opaque = token_mock("pool-renew", "opaque_token").expect(2).create();
+
+        pool.get("UG").await.unwrap();
+        pool.get("UG").await.unwrap();
+        pool.invalidate_token("UG").await;
+        pool.get("UG").await.unwrap();
+
+        opaque.assert();
+    }
+
+    #[tokio::test]
+    async fn it_limits_concurrent_checkouts() {
+        let mut pool = EversendPool::new();
+        pool.insert("UG", tenant("pool-concurrency", TenantLimits {
+            max_concurrent: 1,
+            ..TenantLimits::default()
+        }));
+
+        let _token = token_mock("pool-concurrency", "ug_token").create();
+
+        let first = pool.get("UG").await.unwrap();
+
+        let blocked = tokio::time::timeout(Duration::from_millis(100), pool.get("UG")).await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        pool.get("UG").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_paces_checkouts() {
+        let mut pool = EversendPool::new();
+        pool.insert("UG", tenant("pool-pacing", TenantLimits {
+            max_concurrent: 4,
+            max_checkouts_per_second: Some(10),
+            ..TenantLimits::default()
+        }));
+
+        let _token = token_mock("pool-pacing", "ug_token").create();
+
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            pool.get("UG").await.unwrap();
+        }
+
+        // The second and third checkouts wait 100ms each.
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn it_paces_requests_made_with_a_checked_out_client() {
+        let mut pool = EversendPool::new();
+        pool.insert("UG", tenant("pool-request-pacing", TenantLimits {
+            max_requests_per_second: Some(10),
+            ..TenantLimits::default()
+        }));
+
+        let _token = token_mock("pool-request-pacing", "ug_token").create();
+        let _wallets = mock("GET", "/pool-request-pacing/wallets")
+            .with_status(200)
+            .with_body(json!({ "code": 200, "data": [], "success": true }).to_string())
+            .create();
+
+        let eversend = pool.get("UG").await.unwrap();
+        let started = Instant::now();
+
+        for _ in 0..3 {
+            eversend.wallets().get_wallets().await.unwrap();
+        }
+
+        // The token was generated 100ms before the first request, which waits for its turn.
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,23 @@
+use std::ops::Deref;
+
+use tokio::sync::OwnedSemaphorePermit;
+
+use crate::Eversend;
+
+/// A client checked out of an [`EversendPool`](crate::pool::EversendPool), authenticated as its
+/// tenant.
+///
+/// Dereferences to [`Eversend`]. The tenant's concurrency slot is released when it is dropped, so
+/// keep it only as long as the calls it is needed for.
+pub struct PooledClient {
+    pub(crate) eversend: Eversend,
+    pub(crate) _permit: OwnedSemaphorePermit,
+}
+
+impl Deref for PooledClient {
+    type Target = Eversend;
+
+    fn deref(&self) -> &Eversend {
+        &self.eversend
+    }
+}
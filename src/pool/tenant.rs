@@ -0,0 +1,51 @@
+use crate::{ClientId, ClientSecret};
+
+/// Limits on how the client of a [`Tenant`] is checked out of an
+/// [`EversendPool`](crate::pool::EversendPool), and on the requests it sends.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantLimits {
+    /// How many clients of the tenant may be checked out at once. Defaults to 4, and is at least 1.
+    pub max_concurrent: usize,
+
+    /// How many clients of the tenant may be checked out per second. Defaults to `None`, i.e.
+    /// unlimited.
+    pub max_checkouts_per_second: Option<u32>,
+
+    /// How many requests the clients of the tenant may send per second, together. Includes the
+    /// requests generating API tokens. Defaults to `None`, i.e. unlimited.
+    pub max_requests_per_second: Option<u32>,
+}
+
+impl Default for TenantLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            max_checkouts_per_second: None,
+            max_requests_per_second: None,
+        }
+    }
+}
+
+/// A business account of an [`EversendPool`](crate::pool::EversendPool).
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub client_id: ClientId,
+    pub client_secret: ClientSecret,
+
+    /// Overrides the base URL of the Eversend API, e.g. to use a sandbox. Defaults to `None`.
+    pub base_url: Option<String>,
+
+    pub limits: TenantLimits,
+}
+
+impl Tenant {
+    /// Returns a [`Tenant`] with the default limits.
+    pub fn new(client_id: &ClientId, client_secret: &ClientSecret) -> Self {
+        Self {
+            client_id: client_id.clone(),
+            client_secret: client_secret.clone(),
+            base_url: None,
+            limits: TenantLimits::default(),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Reconciliation of a local ledger against Eversend transactions.
+//!
+//! Ledger entries are matched with transactions by transaction ID when known, or by the
+//! `transaction_ref` set when the transaction was created, and sorted into buckets for matched
+//! entries, entries missing on either side, and entries whose amount or status differ.
+
+mod ledger_entry;
+mod reconcile_ledger;
+mod reconciliation;
+
+pub use ledger_entry::*;
+pub use reconcile_ledger::*;
+pub use reconciliation::*;
@@ -0,0 +1,63 @@
+use std::io::Read;
+
+use serde::Deserialize;
+
+use crate::{reconcile::ReconcileError, transactions::{TransactionCurrencyOption, TransactionStatusOption}, Decimal};
+
+/// A transaction as recorded in the local ledger.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LedgerEntry {
+    /// The reference the transaction was created with.
+    pub transaction_ref: String,
+
+    /// The Eversend transaction ID, if it was recorded.
+    #[serde(default)]
+    pub transaction_id: Option<String>,
+
+    /// The amount in `currency`, as charged to the source wallet.
+    pub amount: Decimal,
+
+    pub currency: TransactionCurrencyOption,
+
+    /// The status the transaction is expected to have on Eversend.
+    pub expected_status: TransactionStatusOption,
+}
+
+impl LedgerEntry {
+    /// Reads entries from CSV with a header line, e.g. a spreadsheet export of the ledger.
+    ///
+    /// The header names are the field names, `currency` is a code such as `UGX` and
+    /// `expected_status` is `pending`, `successful` or `failed`.
+    pub fn from_csv<R: Read>(reader: R) -> Result<Vec<Self>, ReconcileError> {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ReconcileError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_entries_from_csv() {
+        let csv = "\
+transaction_ref,transaction_id,amount,currency,expected_status
+INV001,,1000,UGX,successful
+INV002,BP11678735362605,25.50,KES,pending
+";
+
+        let entries = LedgerEntry::from_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].transaction_id, None);
+        assert_eq!(entries[0].expected_status, TransactionStatusOption::SUCCESSFUL);
+        assert_eq!(entries[1].transaction_id.as_deref(), Some("BP11678735362605"));
+        assert_eq!(entries[1].amount, Decimal::new(2550, 2));
+        assert_eq!(entries[1].currency, TransactionCurrencyOption::KES);
+    }
+}
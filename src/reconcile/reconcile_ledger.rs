@@ -0,0 +1,456 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{
+    reconcile::{LedgerEntry, Reconciliation},
+    transactions::{
+        GetTransaction,
+        GetTransactionError,
+        GetTransactionParams,
+        GetTransactions,
+        GetTransactionsError,
+        GetTransactionsParams,
+        Transaction,
+        Transactions,
+    },
+    Decimal,
+    EversendError,
+    EversendResult,
+};
+
+/// An error returned from [`ReconcileLedger`].
+#[derive(Debug, Error)]
+pub enum ReconcileError {
+    /// The ledger CSV could not be read.
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+
+    /// The transaction filters are invalid.
+    #[error(transparent)]
+    Filter(#[from] GetTransactionsError),
+
+    /// Two entries share the same `transaction_ref`, so only one of them could be matched.
+    #[error("transaction reference {0} is used by more than one entry")]
+    DuplicateTransactionRef(String),
+
+    /// Two entries share the same `transaction_id`, so only one of them could be matched.
+    #[error("transaction ID {0} is used by more than one entry")]
+    DuplicateTransactionId(String),
+}
+
+impl From<ReconcileError> for EversendError<ReconcileError> {
+    fn from(err: ReconcileError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+impl From<GetTransactionsError> for EversendError<ReconcileError> {
+    fn from(err: GetTransactionsError) -> Self {
+        Self::Operation(ReconcileError::Filter(err))
+    }
+}
+
+/// Options for [`ReconcileLedger`].
+#[derive(Debug, Clone)]
+pub struct ReconcileOptions {
+    /// Selects the Eversend transactions to reconcile with, typically a date range matching the
+    /// ledger period. The page is ignored, as every page is fetched.
+    pub filters: GetTransactionsParams,
+
+    /// Amounts within this distance of each other are considered equal. Defaults to 0.005.
+    pub amount_tolerance: Decimal,
+
+    /// Whether entries not found within `filters` are looked up one by one, by transaction ID or
+    /// reference, before being reported as missing. Defaults to `true`.
+    pub lookup_unmatched: bool,
+}
+
+impl Default for ReconcileOptions {
+    fn default() -> Self {
+        Self {
+            filters: GetTransactionsParams::default(),
+            amount_tolerance: Decimal::new(5, 3),
+            lookup_unmatched: true,
+        }
+    }
+}
+
+/// Reconciles a local ledger against Eversend transactions.
+#[async_trait]
+pub trait ReconcileLedger {
+    /// Fetch the transactions selected by `options.filters` and reconcile `entries` with them.
+    ///
+    /// See [`Reconciliation::compute`] for how entries are matched. Entries must not share a
+    /// `transaction_ref` or `transaction_id`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::reconcile::*;
+    /// use eversend_rust_sdk::transactions::GetTransactionsParams;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), ReconcileError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let entries = LedgerEntry::from_csv(
+    ///         "transaction_ref,amount,currency,expected_status\nINV001,1000,UGX,successful\n".as_bytes()
+    ///     )?;
+    ///
+    ///     let reconciliation = eversend
+    ///         .transactions()
+    ///         .reconcile(
+    ///             &entries,
+    ///             &ReconcileOptions {
+    ///                 filters: GetTransactionsParams::builder()
+    ///                     .set_from("2024-01-01")
+    ///                     .set_to("2024-01-31")
+    ///                     .build()?,
+    ///                 ..ReconcileOptions::default()
+    ///             }
+    ///         )
+    ///         .await?;
+    ///
+    ///     for pair in &reconciliation.amount_mismatches {
+    ///         println!("{} is off by {:?}", pair.entry.transaction_ref, pair.amount_difference());
+    ///     }
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn reconcile(
+        &self,
+        entries: &[LedgerEntry],
+        options: &ReconcileOptions
+    ) -> EversendResult<Reconciliation, ReconcileError>;
+}
+
+#[async_trait]
+impl<'a> ReconcileLedger for Transactions<'a> {
+    async fn reconcile(
+        &self,
+        entries: &[LedgerEntry],
+        options: &ReconcileOptions
+    ) -> EversendResult<Reconciliation, ReconcileError> {
+        let mut transaction_refs = HashSet::new();
+        let mut transaction_ids = HashSet::new();
+
+        for entry in entries {
+            if !transaction_refs.insert(entry.transaction_ref.as_str()) {
+                return Err(ReconcileError::DuplicateTransactionRef(entry.transaction_ref.clone()).into());
+            }
+
+            if let Some(transaction_id) = &entry.transaction_id {
+                if !transaction_ids.insert(transaction_id.as_str()) {
+                    return Err(ReconcileError::DuplicateTransactionId(transaction_id.clone()).into());
+                }
+            }
+        }
+
+        let mut params = options.filters.clone();
+        params.page = Some(1);
+
+        let mut transactions = Vec::new();
+
+        loop {
+            let page = self
+                .get_transactions(&params)
+                .await
+                .map_err(|err| err.map_operation(ReconcileError::Filter))?;
+
+            let has_next_page = page.has_next_page() && !page.transactions.is_empty();
+            transactions.extend(page.transactions);
+
+            if !has_next_page {
+                break;
+            }
+
+            params.page = Some(page.page + 1);
+        }
+
+        let reconciliation = Reconciliation::compute(entries, transactions.clone(), options.amount_tolerance);
+
+        if !options.lookup_unmatched || reconciliation.missing_on_eversend.is_empty() {
+            return Ok(reconciliation);
+        }
+
+        for entry in &reconciliation.missing_on_eversend {
+            let Some(transaction) = self.find_entry(entry).await? else {
+                continue;
+            };
+
+            // Already fetched, but not matching the entry, e.g. an entry with another ID.
+            if transactions.iter().any(|fetched| fetched.transaction_id == transaction.transaction_id) {
+                continue;
+            }
+
+            transactions.push(transaction);
+        }
+
+        Ok(Reconciliation::compute(entries, transactions, options.amount_tolerance))
+    }
+}
+
+impl<'a> Transactions<'a> {
+    /// Looks up the transaction of a single ledger entry, by ID if known or by reference otherwise.
+    async fn find_entry(&self, entry: &LedgerEntry) -> EversendResult<Option<Transaction>, ReconcileError> {
+        if let Some(transaction_id) = &entry.transaction_id {
+            let params = GetTransactionParams {
+                transaction_id: transaction_id.clone(),
+            };
+
+            return match self.get_transaction(&params).await {
+                Ok(transaction) => Ok(Some(transaction)),
+                Err(EversendError::Operation(GetTransactionError::NotFound)) => Ok(None),
+                Err(EversendError::ApiTokenMissing) => Err(EversendError::ApiTokenMissing),
+                Err(EversendError::Unauthorized) => Err(EversendError::Unauthorized),
+                Err(EversendError::RequestError(err)) => Err(EversendError::RequestError(err)),
+                Err(EversendError::Decode(err)) => Err(EversendError::Decode(err)),
+            };
+        }
+
+        let mut params = GetTransactionsParams {
+            search: Some(entry.transaction_ref.clone()),
+            ..GetTransactionsParams::default()
+        };
+
+        loop {
+            let page = self
+                .get_transactions(&params)
+                .await
+                .map_err(|err| err.map_operation(ReconcileError::Filter))?;
+
+            let has_next_page = page.has_next_page() && !page.transactions.is_empty();
+            let next_page = page.page + 1;
+
+            let found = page.transactions
+                .into_iter()
+                .find(|transaction| transaction.transaction_ref.as_deref() == Some(entry.transaction_ref.as_str()));
+
+            if found.is_some() || !has_next_page {
+                return Ok(found);
+            }
+
+            params.page = Some(next_page);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, transactions::{TransactionCurrencyOption, TransactionStatusOption}, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher};
+    use serde_json::{json, Value};
+    use tokio;
+
+    fn transaction(transaction_id: &str, transaction_ref: &str) -> Value {
+        json!({
+            "id": 794,
+            "transactionId": transaction_id,
+            "transactionRef": transaction_ref,
+            "type": "payout",
+            "currency": "UGX",
+            "amount": "1000",
+            "balanceBefore": "1000",
+            "balanceAfter": "0",
+            "destinationAmount": "1000",
+            "accountId": 3,
+            "meta": {
+                "source": {
+                    "amount": 1000,
+                    "balance": { "after": "0", "before": "1000" },
+                    "currency": "UGX"
+                },
+                "destination": {
+                    "amount": 1000,
+                    "balance": { "after": "1000", "before": "0" },
+                    "currency": "UGX"
+                }
+            },
+            "isRefunded": false,
+            "status": "successful",
+            "createdAt": "2023-03-13T19:22:46.070Z",
+            "updatedAt": "2023-03-13T19:22:46.071Z"
+        })
+    }
+
+    fn page(transactions: Vec<Value>, total: u32, page: u32) -> String {
+        json!({
+            "code": 200,
+            "data": {
+                "total_payouts": "0",
+                "total_collections": "0",
+                "balance": 0,
+                "transactions": transactions,
+                "total": total,
+                "limit": 1,
+                "page": page
+            },
+            "success": true
+        }).to_string()
+    }
+
+    fn entry(transaction_ref: &str) -> LedgerEntry {
+        LedgerEntry {
+            transaction_ref: String::from(transaction_ref),
+            transaction_id: None,
+            amount: Decimal::from(1000),
+            currency: TransactionCurrencyOption::UGX,
+            expected_status: TransactionStatusOption::SUCCESSFUL,
+        }
+    }
+
+    #[tokio::test]
+    async fn it_fetches_every_page_and_looks_up_unmatched_entries() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let first = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "page": 1, "limit": 1 })))
+            .with_status(200)
+            .with_body(page(vec![transaction("BP1", "INV001")], 2, 1))
+            .create();
+
+        let second = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "page": 2, "limit": 1 })))
+            .with_status(200)
+            .with_body(page(vec![transaction("BP2", "OTHER")], 2, 2))
+            .create();
+
+        let lookup = mock("POST", "/transactions")
+            .match_body(Matcher::Json(json!({ "search": "INV003" })))
+            .with_status(200)
+            .with_body(page(vec![transaction("BP3", "INV003")], 1, 1))
+            .create();
+
+        let missing = mock("POST", "/transactions")
+            .match_body(Matcher::Json(json!({ "search": "INV004" })))
+            .with_status(200)
+            .with_body(page(vec![], 0, 1))
+            .create();
+
+        let reconciliation = eversend
+            .transactions()
+            .reconcile(
+                &[entry("INV001"), entry("INV003"), entry("INV004")],
+                &ReconcileOptions {
+                    filters: GetTransactionsParams::builder().set_limit(1).build().unwrap(),
+                    ..ReconcileOptions::default()
+                }
+            )
+            .await
+            .unwrap();
+
+        let matched = reconciliation
+            .matched
+            .iter()
+            .map(|pair| pair.transaction.transaction_id.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(matched, ["BP1", "BP3"]);
+        assert_eq!(reconciliation.missing_on_eversend, [entry("INV004")]);
+        assert_eq!(reconciliation.missing_locally.len(), 1);
+        assert_eq!(reconciliation.missing_locally[0].transaction_id, "BP2");
+
+        first.assert();
+        second.assert();
+        lookup.assert();
+        missing.assert();
+    }
+
+    #[tokio::test]
+    async fn it_rejects_entries_sharing_a_reference_or_id() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/reconcile-duplicates", mockito::server_url()))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let fetch = mock("POST", "/reconcile-duplicates/transactions")
+            .expect(0)
+            .create();
+
+        let result = eversend
+            .transactions()
+            .reconcile(&[entry("INV001"), entry("INV002"), entry("INV001")], &ReconcileOptions::default())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(ReconcileError::DuplicateTransactionRef(transaction_ref))) if transaction_ref == "INV001"
+        ));
+
+        let with_id = |transaction_ref: &str| LedgerEntry {
+            transaction_id: Some(String::from("BP1")),
+            ..entry(transaction_ref)
+        };
+
+        let result = eversend
+            .transactions()
+            .reconcile(&[with_id("INV001"), with_id("INV002")], &ReconcileOptions::default())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(EversendError::Operation(ReconcileError::DuplicateTransactionId(transaction_id))) if transaction_id == "BP1"
+        ));
+
+        fetch.assert();
+    }
+
+    #[tokio::test]
+    async fn it_looks_up_entries_past_the_first_page_of_search_results() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/reconcile-search-pages", mockito::server_url()))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let listing = mock("POST", "/reconcile-search-pages/transactions")
+            .match_body(Matcher::Json(json!({ "page": 1 })))
+            .with_status(200)
+            .with_body(page(vec![], 0, 1))
+            .create();
+
+        let first = mock("POST", "/reconcile-search-pages/transactions")
+            .match_body(Matcher::Json(json!({ "search": "INV001" })))
+            .with_status(200)
+            .with_body(page(vec![transaction("BP9", "INV0010")], 2, 1))
+            .create();
+
+        let second = mock("POST", "/reconcile-search-pages/transactions")
+            .match_body(Matcher::Json(json!({ "search": "INV001", "page": 2 })))
+            .with_status(200)
+            .with_body(page(vec![transaction("BP1", "INV001")], 2, 2))
+            .create();
+
+        let reconciliation = eversend
+            .transactions()
+            .reconcile(&[entry("INV001")], &ReconcileOptions::default())
+            .await
+            .unwrap();
+
+        assert_eq!(reconciliation.matched.len(), 1);
+        assert_eq!(reconciliation.matched[0].transaction.transaction_id, "BP1");
+
+        listing.assert();
+        first.assert();
+        second.assert();
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use crate::{reconcile::LedgerEntry, transactions::Transaction, Decimal};
+
+/// A ledger entry together with the Eversend transaction it was matched with.
+#[derive(Debug, Clone)]
+pub struct ReconciledPair {
+    pub entry: LedgerEntry,
+    pub transaction: Transaction,
+}
+
+impl ReconciledPair {
+    /// The Eversend amount minus the ledger amount, or `None` if the Eversend amount is not a number.
+    pub fn amount_difference(&self) -> Option<Decimal> {
+        self.transaction
+            .amount
+            .parse::<Decimal>()
+            .ok()
+            .map(|amount| amount - self.entry.amount)
+    }
+}
+
+/// The outcome of reconciling ledger entries with Eversend transactions.
+#[derive(Debug, Clone, Default)]
+pub struct Reconciliation {
+    /// Entries whose transaction has the same amount, currency and status.
+    pub matched: Vec<ReconciledPair>,
+
+    /// Entries without a transaction on Eversend.
+    pub missing_on_eversend: Vec<LedgerEntry>,
+
+    /// Transactions without an entry in the ledger.
+    pub missing_locally: Vec<Transaction>,
+
+    /// Entries whose transaction has a different amount or currency. Takes precedence over a
+    /// status mismatch.
+    pub amount_mismatches: Vec<ReconciledPair>,
+
+    /// Entries whose transaction has the same amount but a different status.
+    pub status_mismatches: Vec<ReconciledPair>,
+}
+
+impl Reconciliation {
+    /// Reconciles `entries` with `transactions` that were already fetched, e.g. from an export.
+    ///
+    /// An entry is matched by its `transaction_id` when set, and by its `transaction_ref`
+    /// otherwise. Amounts within `amount_tolerance` of each other are considered equal.
+    pub fn compute(entries: &[LedgerEntry], transactions: Vec<Transaction>, amount_tolerance: Decimal) -> Self {
+        let mut by_id = HashMap::new();
+        let mut by_ref = HashMap::new();
+
+        for (index, transaction) in transactions.iter().enumerate() {
+            by_id.entry(transaction.transaction_id.clone()).or_insert(index);
+
+            if let Some(transaction_ref) = &transaction.transaction_ref {
+                by_ref.entry(transaction_ref.clone()).or_insert(index);
+            }
+        }
+
+        let mut transactions = transactions.into_iter().map(Some).collect::<Vec<_>>();
+        let mut reconciliation = Self::default();
+
+        for entry in entries {
+            let index = match &entry.transaction_id {
+                Some(transaction_id) => by_id.get(transaction_id),
+                None => by_ref.get(&entry.transaction_ref),
+            };
+
+            let transaction = match index.and_then(|index| transactions[*index].take()) {
+                Some(transaction) => transaction,
+                None => {
+                    reconciliation.missing_on_eversend.push(entry.clone());
+                    continue;
+                },
+            };
+
+            let pair = ReconciledPair {
+                entry: entry.clone(),
+                transaction,
+            };
+
+            let amount_matches = pair
+                .amount_difference()
+                .is_some_and(|difference| difference.abs() <= amount_tolerance);
+
+            if !amount_matches || pair.transaction.currency != pair.entry.currency {
+                reconciliation.amount_mismatches.push(pair);
+            } else if pair.transaction.status != pair.entry.expected_status {
+                reconciliation.status_mismatches.push(pair);
+            } else {
+                reconciliation.matched.push(pair);
+            }
+        }
+
+        reconciliation.missing_locally = transactions.into_iter().flatten().collect();
+
+        reconciliation
+    }
+
+    /// Returns `true` if every entry was matched and no transaction is missing locally.
+    pub fn is_balanced(&self) -> bool {
+        self.missing_on_eversend.is_empty()
+            && self.missing_locally.is_empty()
+            && self.amount_mismatches.is_empty()
+            && self.status_mismatches.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::transactions::{TransactionCurrencyOption, TransactionStatusOption};
+
+    use super::*;
+
+    fn transaction(transaction_id: &str, transaction_ref: &str, amount: &str, status: &str) -> Transaction {
+        serde_json::from_value(json!({
+            "id": 794,
+            "transactionId": transaction_id,
+            "transactionRef": transaction_ref,
+            "type": "payout",
+            "currency": "UGX",
+            "amount": amount,
+            "balanceBefore": "1000",
+            "balanceAfter": "0",
+            "destinationAmount": amount,
+            "accountId": 3,
+            "meta": {
+                "source": {
+                    "amount": 1000,
+                    "balance": { "after": "0", "before": "1000" },
+                    "currency": "UGX"
+                },
+                "destination": {
+                    "amount": 1000,
+                    "balance": { "after": "1000", "before": "0" },
+                    "currency": "UGX"
+                }
+            },
+            "isRefunded": false,
+            "status": status,
+            "createdAt": "2023-03-13T19:22:46.070Z",
+            "updatedAt": "2023-03-13T19:22:46.071Z"
+        })).unwrap()
+    }
+
+    fn entry(transaction_ref: &str, amount: Decimal, expected_status: TransactionStatusOption) -> LedgerEntry {
+        LedgerEntry {
+            transaction_ref: String::from(transaction_ref),
+            transaction_id: None,
+            amount,
+            currency: TransactionCurrencyOption::UGX,
+            expected_status,
+        }
+    }
+
+    #[test]
+    fn it_sorts_entries_into_buckets() {
+        let entries = [
+            entry("INV001", Decimal::from(1000), TransactionStatusOption::SUCCESSFUL),
+            entry("INV002", Decimal::from(1000), TransactionStatusOption::SUCCESSFUL),
+            entry("INV003", Decimal::from(1000), TransactionStatusOption::SUCCESSFUL),
+            entry("INV004", Decimal::from(1000), TransactionStatusOption::SUCCESSFUL),
+            LedgerEntry {
+                transaction_id: Some(String::from("BP5")),
+                ..entry("INV005", Decimal::from(1000), TransactionStatusOption::PENDING)
+            },
+        ];
+
+        let transactions = vec![
+            transaction("BP1", "INV001", "1000.00", "successful"),
+            transaction("BP2", "INV002", "999", "successful"),
+            transaction("BP3", "INV003", "1000", "failed"),
+            transaction("BP5", "SOMETHINGELSE", "1000", "pending"),
+            transaction("BP6", "INV006", "1000", "successful"),
+        ];
+
+        let reconciliation = Reconciliation::compute(&entries, transactions, Decimal::new(5, 3));
+
+        let refs = |pairs: &[ReconciledPair]| pairs.iter().map(|pair| pair.entry.transaction_ref.clone()).collect::<Vec<_>>();
+
+        assert_eq!(refs(&reconciliation.matched), ["INV001", "INV005"]);
+        assert_eq!(refs(&reconciliation.amount_mismatches), ["INV002"]);
+        assert_eq!(reconciliation.amount_mismatches[0].amount_difference(), Some(Decimal::from(-1)));
+        assert_eq!(refs(&reconciliation.status_mismatches), ["INV003"]);
+        assert_eq!(reconciliation.missing_on_eversend, [entries[3].clone()]);
+        assert_eq!(reconciliation.missing_locally.len(), 1);
+        assert_eq!(reconciliation.missing_locally[0].transaction_id, "BP6");
+        assert!(!reconciliation.is_balanced());
+    }
+
+    #[test]
+    fn it_treats_a_different_currency_as_an_amount_mismatch() {
+        let entries = [LedgerEntry {
+            currency: TransactionCurrencyOption::KES,
+            ..entry("INV001", Decimal::from(1000), TransactionStatusOption::SUCCESSFUL)
+        }];
+
+        let reconciliation = Reconciliation::compute(
+            &entries,
+            vec![transaction("BP1", "INV001", "1000", "successful")],
+            Decimal::new(5, 3)
+        );
+
+        assert_eq!(reconciliation.amount_mismatches.len(), 1);
+    }
+}
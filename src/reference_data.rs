@@ -0,0 +1,296 @@
+//! A cache of slow-changing reference data: delivery countries, delivery banks and asset chains.
+//!
+//! Values are kept for a TTL set per kind of data in [`ReferenceDataOptions`]. Past its TTL, a
+//! value is still returned for a while and refreshed in the background, so rendering a payout
+//! form never waits on the API once the cache is warm. The cache may be persisted to disk.
+
+mod cache;
+mod options;
+
+pub(crate) use cache::ReferenceDataCache;
+pub use options::*;
+
+use std::sync::Arc;
+
+use tokio::runtime::Handle;
+
+use crate::{
+    crypto::{AssetChain, FetchAssetChainsError},
+    payouts::{Bank, Country, GetDeliveryBanksError, GetDeliveryCountriesError},
+    Eversend,
+    EversendResult,
+};
+
+use cache::{Lookup, Reference};
+
+/// Cached reference data.
+///
+/// The cache is shared by an [`Eversend`] client and its clones.
+///
+/// # Examples
+/// ```
+/// # use eversend_rust_sdk::EversendResult;
+/// # use eversend_rust_sdk::payouts::GetDeliveryBanksError;
+/// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+///
+/// # async fn run() -> EversendResult<(), GetDeliveryBanksError> {
+///     let eversend = Eversend::new(
+///         &ClientId::from("sk_example_123456789"),
+///         &ClientSecret::from("sk_example_123456780")
+///     );
+///
+///     // Fetched once, then served from memory.
+///     let banks = eversend.reference_data().delivery_banks("UG").await?;
+///     let banks = eversend.reference_data().delivery_banks("UG").await?;
+///
+///     Ok(())
+/// # }
+/// ```
+pub struct ReferenceData<'a> {
+    eversend: &'a Eversend,
+}
+
+impl<'a> ReferenceData<'a> {
+    pub fn new(eversend: &'a Eversend) -> Self {
+        Self { eversend }
+    }
+
+    /// Countries payouts can be sent to, see
+    /// [`GetDeliveryCountries`](crate::payouts::GetDeliveryCountries).
+    pub async fn delivery_countries(&self) -> EversendResult<Arc<Vec<Country>>, GetDeliveryCountriesError> {
+        self.get("").await
+    }
+
+    /// Banks of a country, by ALPHA-2 code, see [`GetDeliveryBanks`](crate::payouts::GetDeliveryBanks).
+    pub async fn delivery_banks(&self, country: &str) -> EversendResult<Arc<Vec<Bank>>, GetDeliveryBanksError> {
+        self.get(country).await
+    }
+
+    /// Chains of a coin, e.g. USDT, see [`FetchAssetChains`](crate::crypto::FetchAssetChains).
+    pub async fn asset_chains(&self, coin: &str) -> EversendResult<Arc<Vec<AssetChain>>, FetchAssetChainsError> {
+        self.get(coin).await
+    }
+
+    /// Drops the cached delivery countries, so the next call fetches them.
+    pub fn invalidate_delivery_countries(&self) {
+        self.eversend.reference_data_cache().invalidate::<Vec<Country>>("");
+    }
+
+    /// Drops the cached delivery banks of a country, so the next call fetches them.
+    pub fn invalidate_delivery_banks(&self, country: &str) {
+        self.eversend.reference_data_cache().invalidate::<Vec<Bank>>(country);
+    }
+
+    /// Drops the cached chains of a coin, so the next call fetches them.
+    pub fn invalidate_asset_chains(&self, coin: &str) {
+        self.eversend.reference_data_cache().invalidate::<Vec<AssetChain>>(coin);
+    }
+
+    /// Drops everything cached.
+    pub fn clear(&self) {
+        self.eversend.reference_data_cache().clear();
+    }
+
+    async fn get<R: Reference>(&self, key: &str) -> EversendResult<Arc<R>, R::Error> {
+        let cache = self.eversend.reference_data_cache();
+
+        match cache.lookup::<R>(key) {
+            Lookup::Hit(value) => Ok(value),
+            Lookup::Revalidate(stale) => {
+                if let Ok(runtime) = Handle::try_current() {
+                    let eversend = self.eversend.clone();
+                    let key = key.to_string();
+
+                    runtime.spawn(async move {
+                        refresh::<R>(&eversend, &key).await;
+                    });
+
+                    return Ok(stale);
+                }
+
+                // Without a Tokio runtime to refresh in the background, refresh right away.
+                Ok(refresh::<R>(self.eversend, key).await.unwrap_or(stale))
+            },
+            Lookup::Miss => {
+                let value = R::fetch(self.eversend, key).await?;
+                Ok(cache.insert(key, value))
+            },
+        }
+    }
+}
+
+/// Fetches a stale value again. On failure the stale value is kept, to be retried later.
+async fn refresh<R: Reference>(eversend: &Eversend, key: &str) -> Option<Arc<R>> {
+    let cache = eversend.reference_data_cache();
+
+    match R::fetch(eversend, key).await {
+        Ok(value) => Some(cache.insert(key, value)),
+        Err(_) => {
+            cache.revalidation_failed::<R>(key);
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{crypto::Chain, ClientId, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock};
+    use serde_json::json;
+    use tokio;
+
+    fn eversend(prefix: &str, options: ReferenceDataOptions) -> Eversend {
+        Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&format!("{}/{}", mockito::server_url(), prefix))
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .set_reference_data_options(options)
+            .build()
+    }
+
+    fn banks(name: &str) -> String {
+        json!({
+            "code": 200,
+            "data": [{
+                "active": true,
+                "branch": {
+                    "city": "Kampala",
+                    "code": "001",
+                    "id": "1",
+                    "name": "Main",
+                    "state": "Central"
+                },
+                "id": "50",
+                "name": name
+            }],
+            "success": true
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn it_serves_cached_values_until_invalidated() {
+        let eversend = eversend("reference-cache-hit", ReferenceDataOptions::default());
+
+        let mock = mock("GET", "/reference-cache-hit/payouts/banks/UG")
+            .with_status(200)
+            .with_body(banks("Stanbic"))
+            .expect(2)
+            .create();
+
+        let first = eversend.reference_data().delivery_banks("UG").await.unwrap();
+        let second = eversend.clone().reference_data().delivery_banks("UG").await.unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+
+        eversend.reference_data().invalidate_delivery_banks("UG");
+        eversend.reference_data().delivery_banks("UG").await.unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_serves_stale_values_while_revalidating() {
+        let eversend = eversend("reference-cache-stale", ReferenceDataOptions {
+            delivery_banks_ttl: Duration::ZERO,
+            ..ReferenceDataOptions::default()
+        });
+
+        let old = mock("GET", "/reference-cache-stale/payouts/banks/UG")
+            .with_status(200)
+            .with_body(banks("Old"))
+            .expect(1)
+            .create();
+
+        eversend.reference_data().delivery_banks("UG").await.unwrap();
+        old.assert();
+        drop(old);
+
+        let new = mock("GET", "/reference-cache-stale/payouts/banks/UG")
+            .with_status(200)
+            .with_body(banks("New"))
+            .expect(1)
+            .create();
+
+        let stale = eversend.reference_data().delivery_banks("UG").await.unwrap();
+        assert_eq!(stale[0].name, "Old");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        new.assert();
+
+        // The refreshed value is stale right away too, and is returned while refreshing again.
+        let refreshed = eversend.reference_data().delivery_banks("UG").await.unwrap();
+        assert_eq!(refreshed[0].name, "New");
+    }
+
+    #[tokio::test]
+    async fn it_refetches_expired_values() {
+        let eversend = eversend("reference-cache-expired", ReferenceDataOptions {
+            delivery_countries_ttl: Duration::ZERO,
+            stale_while_revalidate: Duration::ZERO,
+            ..ReferenceDataOptions::default()
+        });
+
+        let mock = mock("GET", "/reference-cache-expired/payouts/countries")
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "countries": [{
+                        "country": "UG",
+                        "id": "1",
+                        "name": "Uganda",
+                        "paymentTypes": ["momo", "bank"],
+                        "phonePrefix": "+256"
+                    }]
+                },
+                "success": true
+            }).to_string())
+            .expect(2)
+            .create();
+
+        eversend.reference_data().delivery_countries().await.unwrap();
+        let countries = eversend.reference_data().delivery_countries().await.unwrap();
+
+        assert_eq!(countries[0].country, "UG");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn it_persists_the_cache_across_clients() {
+        let path = std::env::temp_dir().join(format!("eversend-reference-data-{}.json", std::process::id()));
+        let options = ReferenceDataOptions {
+            persist_path: Some(path.clone()),
+            ..ReferenceDataOptions::default()
+        };
+
+        let mock = mock("GET", "/reference-cache-persist/crypto/assets/USDT")
+            .with_status(200)
+            .with_body(json!({
+                "code": 200,
+                "data": {
+                    "chains": {
+                        "Binance Smart Chain (BEP20)": "USDT_BSC_TEST",
+                        "Ethereum (ERC20)": "USDT_ERC20",
+                        "TRON (TRC20)": "TRX_USDT_S2UZ"
+                    }
+                },
+                "success": true
+            }).to_string())
+            .expect(1)
+            .create();
+
+        eversend("reference-cache-persist", options.clone()).reference_data().asset_chains("USDT").await.unwrap();
+        let chains = eversend("reference-cache-persist", options).reference_data().asset_chains("USDT").await.unwrap();
+
+        let _ = std::fs::remove_file(&path);
+
+        assert!(chains.iter().any(|chain| chain.network == Chain::Tron && chain.asset_id.as_str() == "TRX_USDT_S2UZ"));
+        mock.assert();
+    }
+}
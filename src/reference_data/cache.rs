@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    core::write_atomically,
+    crypto::{AssetChain, FetchAssetChains, FetchAssetChainsError, FetchAssetChainsParams},
+    payouts::{Bank, Country, GetDeliveryBanks, GetDeliveryBanksError, GetDeliveryCountries, GetDeliveryCountriesError},
+    reference_data::ReferenceDataOptions,
+    Eversend,
+    EversendResult,
+};
+
+/// A cached value and when it was fetched.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+pub(crate) struct Entry<T> {
+    value: Arc<T>,
+    fetched_at: SystemTime,
+
+    /// Whether a caller is already refreshing the stale value.
+    #[serde(skip)]
+    refreshing: bool,
+}
+
+/// Every cached value, keyed by country or coin. Delivery countries use an empty key.
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct Store {
+    #[serde(default)]
+    delivery_countries: HashMap<String, Entry<Vec<Country>>>,
+
+    #[serde(default)]
+    delivery_banks: HashMap<String, Entry<Vec<Bank>>>,
+
+    #[serde(default)]
+    asset_chains: HashMap<String, Entry<Vec<AssetChain>>>,
+}
+
+/// A kind of reference data, along with where it is cached and how it is fetched.
+#[async_trait]
+pub(crate) trait Reference: Serialize + DeserializeOwned + Send + Sync + 'static {
+    type Error: Send + 'static;
+
+    fn ttl(options: &ReferenceDataOptions) -> Duration;
+
+    fn entries(store: &mut Store) -> &mut HashMap<String, Entry<Self>>;
+
+    async fn fetch(eversend: &Eversend, key: &str) -> EversendResult<Self, Self::Error>;
+}
+
+#[async_trait]
+impl Reference for Vec<Country> {
+    type Error = GetDeliveryCountriesError;
+
+    fn ttl(options: &ReferenceDataOptions) -> Duration {
+        options.delivery_countries_ttl
+    }
+
+    fn entries(store: &mut Store) -> &mut HashMap<String, Entry<Self>> {
+        &mut store.delivery_countries
+    }
+
+    async fn fetch(eversend: &Eversend, _key: &str) -> EversendResult<Self, Self::Error> {
+        eversend.payouts().get_delivery_countries().await
+    }
+}
+
+#[async_trait]
+impl Reference for Vec<Bank> {
+    type Error = GetDeliveryBanksError;
+
+    fn ttl(options: &ReferenceDataOptions) -> Duration {
+        options.delivery_banks_ttl
+    }
+
+    fn entries(store: &mut Store) -> &mut HashMap<String, Entry<Self>> {
+        &mut store.delivery_banks
+    }
+
+    async fn fetch(eversend: &Eversend, key: &str) -> EversendResult<Self, Self::Error> {
+        eversend.payouts().get_delivery_banks(key.to_string()).await
+    }
+}
+
+#[async_trait]
+impl Reference for Vec<AssetChain> {
+    type Error = FetchAssetChainsError;
+
+    fn ttl(options: &ReferenceDataOptions) -> Duration {
+        options.asset_chains_ttl
+    }
+
+    fn entries(store: &mut Store) -> &mut HashMap<String, Entry<Self>> {
+        &mut store.asset_chains
+    }
+
+    async fn fetch(eversend: &Eversend, key: &str) -> EversendResult<Self, Self::Error> {
+        eversend
+            .crypto()
+            .fetch_asset_chains(&FetchAssetChainsParams { coin: key.to_string() })
+            .await
+    }
+}
+
+/// The outcome of looking up a cached value.
+pub(crate) enum Lookup<T> {
+    /// The value can be used as is.
+    Hit(Arc<T>),
+
+    /// The value is stale and the caller is expected to refresh it.
+    Revalidate(Arc<T>),
+
+    /// There is no usable value.
+    Miss,
+}
+
+/// The reference data cache shared by an [`Eversend`] client and its clones.
+pub(crate) struct ReferenceDataCache {
+    options: ReferenceDataOptions,
+    store: Mutex<Store>,
+}
+
+impl ReferenceDataCache {
+    pub(crate) fn new(options: ReferenceDataOptions) -> Self {
+        let store = options
+            .persist_path
+            .as_deref()
+            .and_then(load)
+            .unwrap_or_default();
+
+        Self {
+            options,
+            store: Mutex::new(store),
+        }
+    }
+
+    pub(crate) fn lookup<R: Reference>(&self, key: &str) -> Lookup<R> {
+        let ttl = R::ttl(&self.options);
+        let stale_ttl = ttl.saturating_add(self.options.stale_while_revalidate);
+
+        let mut store = self.store();
+
+        let Some(entry) = R::entries(&mut store).get_mut(key) else {
+            return Lookup::Miss;
+        };
+
+        // A value fetched in the future, after the clock was set back, is treated as new.
+        let age = entry.fetched_at.elapsed().unwrap_or_default();
+
+        if age < ttl || (age < stale_ttl && entry.refreshing) {
+            Lookup::Hit(entry.value.clone())
+        } else if age < stale_ttl {
+            entry.refreshing = true;
+            Lookup::Revalidate(entry.value.clone())
+        } else {
+            Lookup::Miss
+        }
+    }
+
+    pub(crate) fn insert<R: Reference>(&self, key: &str, value: R) -> Arc<R> {
+        let value = Arc::new(value);
+        let mut store = self.store();
+
+        R::entries(&mut store).insert(key.to_string(), Entry {
+            value: value.clone(),
+            fetched_at: SystemTime::now(),
+            refreshing: false,
+        });
+
+        self.persist(&store);
+
+        value
+    }
+
+    /// Lets the next lookup of a stale value try to refresh it again.
+    pub(crate) fn revalidation_failed<R: Reference>(&self, key: &str) {
+        if let Some(entry) = R::entries(&mut self.store()).get_mut(key) {
+            entry.refreshing = false;
+        }
+    }
+
+    pub(crate) fn invalidate<R: Reference>(&self, key: &str) {
+        let mut store = self.store();
+
+        if R::entries(&mut store).remove(key).is_some() {
+            self.persist(&store);
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut store = self.store();
+        *store = Store::default();
+        self.persist(&store);
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        // The store is left consistent by every method, even one that panicked.
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn persist(&self, store: &Store) {
+        if let Some(path) = &self.options.persist_path {
+            save(path, store);
+        }
+    }
+}
+
+fn load(path: &Path) -> Option<Store> {
+    serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+fn save(path: &Path, store: &Store) {
+    if let Ok(json) = serde_json::to_vec(store) {
+        let _ = write_atomically(path, &json);
+    }
+}
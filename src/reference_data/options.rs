@@ -0,0 +1,39 @@
+use std::{path::PathBuf, time::Duration};
+
+/// How long [`ReferenceData`](crate::reference_data::ReferenceData) keeps each kind of data.
+///
+/// Set with [`EversendBuilder::set_reference_data_options`](crate::EversendBuilder::set_reference_data_options).
+#[derive(Debug, Clone)]
+pub struct ReferenceDataOptions {
+    /// How long delivery countries are fresh. Defaults to 24 hours.
+    pub delivery_countries_ttl: Duration,
+
+    /// How long the delivery banks of a country are fresh. Defaults to 24 hours.
+    pub delivery_banks_ttl: Duration,
+
+    /// How long the chains of a coin are fresh. Defaults to 1 hour.
+    pub asset_chains_ttl: Duration,
+
+    /// How long past its TTL a value is still returned while it is refreshed in the background.
+    /// Older values are refetched before returning. Defaults to 1 hour.
+    pub stale_while_revalidate: Duration,
+
+    /// A file the cache is loaded from when the client is built, and saved to whenever it changes,
+    /// so it survives restarts. Defaults to `None`, keeping the cache in memory only.
+    ///
+    /// The file is a best effort: if it cannot be read or written, the cache starts empty or
+    /// stays in memory.
+    pub persist_path: Option<PathBuf>,
+}
+
+impl Default for ReferenceDataOptions {
+    fn default() -> Self {
+        Self {
+            delivery_countries_ttl: Duration::from_secs(24 * 60 * 60),
+            delivery_banks_ttl: Duration::from_secs(24 * 60 * 60),
+            asset_chains_ttl: Duration::from_secs(60 * 60),
+            stale_while_revalidate: Duration::from_secs(60 * 60),
+            persist_path: None,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod export;
+
 mod operations;
 mod types;
 
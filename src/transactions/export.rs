@@ -0,0 +1,12 @@
+//! Exports of [`Transaction`](crate::transactions::Transaction)s to CSV and JSON Lines.
+//!
+//! Columns follow a versioned [`ExportSchema`], so reports built on an export keep working as
+//! the SDK evolves. Phone and account numbers can be masked before they leave the process.
+
+mod export_transactions;
+mod exporter;
+mod schema;
+
+pub use export_transactions::*;
+pub use exporter::*;
+pub use schema::*;
@@ -0,0 +1,185 @@
+use std::io::Write;
+
+use async_trait::async_trait;
+
+use crate::{transactions::{export::{ExportError, ExportOptions, TransactionExporter}, GetTransactions, GetTransactionsParams, Transactions}, EversendResult};
+
+/// Exports every transaction matching a set of filters.
+#[async_trait]
+pub trait ExportTransactions {
+    /// Fetch every page of transactions matching `params` and stream them to `writer`.
+    ///
+    /// The page set in `params` is ignored. Returns the number of transactions written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::transactions::export::*;
+    /// use eversend_rust_sdk::transactions::GetTransactionsParams;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), ExportError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let rows = eversend
+    ///         .transactions()
+    ///         .export_transactions(
+    ///             &GetTransactionsParams::builder()
+    ///                 .set_from("2024-01-01")
+    ///                 .set_to("2024-01-07")
+    ///                 .build()
+    ///                 .map_err(ExportError::from)?,
+    ///             std::io::stdout(),
+    ///             ExportOptions {
+    ///                 mask_pii: true,
+    ///                 ..ExportOptions::default()
+    ///             }
+    ///         )
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    async fn export_transactions<W: Write + Send>(
+        &self,
+        params: &GetTransactionsParams,
+        writer: W,
+        options: ExportOptions
+    ) -> EversendResult<u64, ExportError>;
+}
+
+#[async_trait]
+impl<'a> ExportTransactions for Transactions<'a> {
+    async fn export_transactions<W: Write + Send>(
+        &self,
+        params: &GetTransactionsParams,
+        writer: W,
+        options: ExportOptions
+    ) -> EversendResult<u64, ExportError> {
+        let mut exporter = TransactionExporter::new(writer, options)?;
+
+        let mut params = params.clone();
+        params.page = Some(1);
+
+        loop {
+            let page = self
+                .get_transactions(&params)
+                .await
+                .map_err(|err| err.map_operation(ExportError::Filter))?;
+
+            for transaction in &page.transactions {
+                exporter.write(transaction)?;
+            }
+
+            if !page.has_next_page() || page.transactions.is_empty() {
+                break;
+            }
+
+            params.page = Some(page.page + 1);
+        }
+
+        let rows = exporter.rows();
+        exporter.finish()?;
+
+        Ok(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+
+    use super::*;
+    use mockito::{self, mock, Matcher};
+    use serde_json::json;
+    use tokio;
+
+    fn page(transaction_id: &str, page: u32) -> String {
+        json!({
+            "code": 200,
+            "data": {
+                "total_payouts": "0",
+                "total_collections": "0",
+                "balance": 0,
+                "transactions": [{
+                    "id": 794,
+                    "transactionId": transaction_id,
+                    "type": "payout",
+                    "currency": "UGX",
+                    "amount": "1000",
+                    "balanceBefore": "1000",
+                    "balanceAfter": "0",
+                    "destinationAmount": "1000",
+                    "accountId": 3,
+                    "meta": {
+                        "source": {
+                            "amount": 1000,
+                            "balance": { "after": "0", "before": "1000" },
+                            "currency": "UGX"
+                        },
+                        "destination": {
+                            "amount": 1000,
+                            "balance": { "after": "1000", "before": "0" },
+                            "currency": "UGX"
+                        }
+                    },
+                    "isRefunded": false,
+                    "status": "successful",
+                    "createdAt": "2023-03-13T19:22:46.070Z",
+                    "updatedAt": "2023-03-13T19:22:46.071Z"
+                }],
+                "total": 2,
+                "limit": 1,
+                "page": page
+            },
+            "success": true
+        }).to_string()
+    }
+
+    #[tokio::test]
+    async fn it_exports_every_page() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let first = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "page": 1, "search": "EXPORT" })))
+            .with_status(200)
+            .with_body(page("BP1", 1))
+            .create();
+
+        let second = mock("POST", "/transactions")
+            .match_body(Matcher::PartialJson(json!({ "page": 2, "search": "EXPORT" })))
+            .with_status(200)
+            .with_body(page("BP2", 2))
+            .create();
+
+        let mut csv = Vec::new();
+
+        let rows = eversend
+            .transactions()
+            .export_transactions(
+                &GetTransactionsParams::builder().set_search("EXPORT").set_limit(1).build().unwrap(),
+                &mut csv,
+                ExportOptions::default()
+            )
+            .await
+            .unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let ids = csv.lines().skip(1).map(|line| line.split(',').next().unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(rows, 2);
+        assert_eq!(ids, ["BP1", "BP2"]);
+
+        first.assert();
+        second.assert();
+    }
+}
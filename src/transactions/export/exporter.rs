@@ -0,0 +1,261 @@
+use std::io::{self, Write};
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::{transactions::{export::{mask_digits, ExportColumn, ExportSchema}, GetTransactionsError, Transaction}, EversendError};
+
+/// An error returned while exporting transactions.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The transaction filters are invalid.
+    #[error(transparent)]
+    Filter(#[from] GetTransactionsError),
+}
+
+impl From<ExportError> for EversendError<ExportError> {
+    fn from(err: ExportError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// The file format of an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Comma separated values with a header line.
+    #[default]
+    Csv,
+
+    /// One JSON object per line, with the column names as keys.
+    JsonLines,
+}
+
+/// Options for a [`TransactionExporter`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+
+    /// Defaults to [`ExportSchema::V1`].
+    pub schema: ExportSchema,
+
+    /// Masks phone and account numbers in the columns marked as [`ExportColumn::pii`], see
+    /// [`mask_digits`]. Defaults to `false`.
+    pub mask_pii: bool,
+}
+
+enum Output<W: Write> {
+    Csv(Box<csv::Writer<W>>),
+    JsonLines(W),
+}
+
+/// Streams transactions to a writer, one row at a time.
+///
+/// # Examples
+/// ```
+/// use eversend_rust_sdk::transactions::export::*;
+///
+/// # fn run() -> Result<(), ExportError> {
+/// let mut exporter = TransactionExporter::new(
+///     Vec::new(),
+///     ExportOptions {
+///         format: ExportFormat::JsonLines,
+///         mask_pii: true,
+///         ..ExportOptions::default()
+///     }
+/// )?;
+///
+/// # let transactions = vec![];
+/// for transaction in &transactions {
+///     exporter.write(transaction)?;
+/// }
+///
+/// let jsonl = exporter.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TransactionExporter<W: Write> {
+    options: ExportOptions,
+    output: Output<W>,
+    rows: u64,
+}
+
+impl<W: Write> TransactionExporter<W> {
+    /// Returns a new [`TransactionExporter`], writing the CSV header right away.
+    pub fn new(writer: W, options: ExportOptions) -> Result<Self, ExportError> {
+        let output = match options.format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(writer);
+                writer.write_record(options.schema.columns().iter().map(|column| column.name))?;
+                Output::Csv(Box::new(writer))
+            },
+            ExportFormat::JsonLines => Output::JsonLines(writer),
+        };
+
+        Ok(Self {
+            options,
+            output,
+            rows: 0,
+        })
+    }
+
+    /// Writes a single transaction.
+    pub fn write(&mut self, transaction: &Transaction) -> Result<(), ExportError> {
+        let columns = self.options.schema.columns();
+        let mut values = self.options.schema.values(transaction);
+
+        if self.options.mask_pii {
+            for (column, value) in columns.iter().zip(values.iter_mut()) {
+                if column.pii {
+                    *value = value.as_deref().map(mask_digits);
+                }
+            }
+        }
+
+        match &mut self.output {
+            Output::Csv(writer) => {
+                writer.write_record(values.iter().map(|value| value.as_deref().unwrap_or_default()))?;
+            },
+            Output::JsonLines(writer) => {
+                serde_json::to_writer(&mut *writer, &Row { columns, values: &values })?;
+                writer.write_all(b"\n")?;
+            },
+        }
+
+        self.rows += 1;
+
+        Ok(())
+    }
+
+    /// The number of transactions written so far.
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Flushes the export and returns the writer.
+    pub fn finish(self) -> Result<W, ExportError> {
+        match self.output {
+            Output::Csv(writer) => writer.into_inner().map_err(|err| ExportError::Io(err.into_error())),
+            Output::JsonLines(mut writer) => {
+                writer.flush()?;
+                Ok(writer)
+            },
+        }
+    }
+}
+
+/// A row serialized as a JSON object with its keys in column order.
+struct Row<'a> {
+    columns: &'a [ExportColumn],
+    values: &'a [Option<String>],
+}
+
+impl<'a> Serialize for Row<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+
+        for (column, value) in self.columns.iter().zip(self.values) {
+            map.serialize_entry(column.name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn transaction() -> Transaction {
+        serde_json::from_value(json!({
+            "id": 794,
+            "transactionId": "BC11678735362605",
+            "transactionRef": "INV001",
+            "type": "collection",
+            "currency": "UGX",
+            "amount": "1000",
+            "balanceBefore": "0",
+            "balanceAfter": "1000",
+            "destinationAmount": "1000",
+            "accountId": 3,
+            "beneficiary": "John Doe +256789123456",
+            "meta": {
+                "source": {
+                    "amount": 1000,
+                    "balance": { "after": "0", "before": "1000" },
+                    "currency": "UGX"
+                },
+                "destination": {
+                    "amount": 1000,
+                    "balance": { "after": "1000", "before": "0" },
+                    "currency": "UGX"
+                }
+            },
+            "isRefunded": true,
+            "status": "successful",
+            "createdAt": "2023-03-13T19:22:46.070Z",
+            "updatedAt": "2023-03-13T19:22:46.071Z"
+        })).unwrap()
+    }
+
+    #[test]
+    fn it_exports_csv_with_a_stable_header() {
+        let mut exporter = TransactionExporter::new(Vec::new(), ExportOptions::default()).unwrap();
+        exporter.write(&transaction()).unwrap();
+
+        assert_eq!(exporter.rows(), 1);
+
+        let csv = String::from_utf8(exporter.finish().unwrap()).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next().unwrap(),
+            "transaction_id,transaction_ref,type,status,created_at,updated_at,currency,amount,fees,\
+             balance_before,balance_after,source_currency,source_amount,source_balance_before,source_balance_after,\
+             destination_currency,destination_amount,destination_balance_before,destination_balance_after,\
+             source_country,destination_country,is_refunded,account_id,beneficiary_id,beneficiary,user,reason"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "BC11678735362605,INV001,collection,successful,2023-03-13T19:22:46.070Z,2023-03-13T19:22:46.071Z,\
+             UGX,1000,,0,1000,UGX,1000,1000,0,UGX,1000,0,1000,,,true,3,,John Doe +256789123456,,"
+        );
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn it_exports_json_lines_with_masked_pii() {
+        let mut exporter = TransactionExporter::new(
+            Vec::new(),
+            ExportOptions {
+                format: ExportFormat::JsonLines,
+                mask_pii: true,
+                ..ExportOptions::default()
+            }
+        ).unwrap();
+
+        exporter.write(&transaction()).unwrap();
+        exporter.write(&transaction()).unwrap();
+
+        let jsonl = String::from_utf8(exporter.finish().unwrap()).unwrap();
+        let lines = jsonl.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"transaction_id":"BC11678735362605","transaction_ref":"INV001","type":"collection""#));
+
+        let row = serde_json::from_str::<serde_json::Value>(lines[0]).unwrap();
+        assert_eq!(row["beneficiary"], "John Doe +********3456");
+        assert_eq!(row["fees"], serde_json::Value::Null);
+        assert_eq!(row["is_refunded"], "true");
+    }
+}
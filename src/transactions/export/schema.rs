@@ -0,0 +1,227 @@
+use serde::Serialize;
+
+use crate::transactions::Transaction;
+
+/// A column of an [`ExportSchema`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportColumn {
+    /// The CSV header and JSON key.
+    pub name: &'static str,
+
+    /// What the column holds.
+    pub description: &'static str,
+
+    /// Whether the column may hold phone or account numbers, which are masked when
+    /// [`ExportOptions::mask_pii`](crate::transactions::export::ExportOptions::mask_pii) is set.
+    pub pii: bool,
+}
+
+const fn column(name: &'static str, description: &'static str) -> ExportColumn {
+    ExportColumn { name, description, pii: false }
+}
+
+const fn pii_column(name: &'static str, description: &'static str) -> ExportColumn {
+    ExportColumn { name, description, pii: true }
+}
+
+const V1: &[ExportColumn] = &[
+    column("transaction_id", "Eversend transaction ID"),
+    column("transaction_ref", "Reference set by the client, if any"),
+    column("type", "collection, exchange or payout"),
+    column("status", "pending, successful or failed"),
+    column("created_at", "When the transaction was created, as returned by the API"),
+    column("updated_at", "When the transaction last changed, as returned by the API"),
+    column("currency", "Currency of amount and fees"),
+    column("amount", "Amount of the transaction"),
+    column("fees", "Fees charged, if any"),
+    column("balance_before", "Wallet balance before the transaction"),
+    column("balance_after", "Wallet balance after the transaction"),
+    column("source_currency", "Currency of the source wallet (meta.source)"),
+    column("source_amount", "Amount taken from the source wallet (meta.source)"),
+    column("source_balance_before", "Source wallet balance before the transaction (meta.source)"),
+    column("source_balance_after", "Source wallet balance after the transaction (meta.source)"),
+    column("destination_currency", "Currency of the destination (meta.destination)"),
+    column("destination_amount", "Amount received at the destination (meta.destination)"),
+    column("destination_balance_before", "Destination balance before the transaction (meta.destination)"),
+    column("destination_balance_after", "Destination balance after the transaction (meta.destination)"),
+    column("source_country", "Source country ALPHA-2 code, if any"),
+    column("destination_country", "Destination country ALPHA-2 code, if any"),
+    column("is_refunded", "true if the transaction was refunded"),
+    column("account_id", "Eversend account the transaction belongs to"),
+    column("beneficiary_id", "Beneficiary ID, if any"),
+    pii_column("beneficiary", "Beneficiary details, if any"),
+    pii_column("user", "User details, if any"),
+    pii_column("reason", "Reason given for the transaction, if any"),
+];
+
+/// The set and order of columns written by an export.
+///
+/// Columns of a version never change. Changes are released as a new version, and older versions
+/// remain available so existing consumers can keep reading exports until they migrate.
+///
+/// | Version | Columns |
+/// |---------|---------|
+/// | `V1` | `transaction_id`, `transaction_ref`, `type`, `status`, `created_at`, `updated_at`, `currency`, `amount`, `fees`, `balance_before`, `balance_after`, `source_currency`, `source_amount`, `source_balance_before`, `source_balance_after`, `destination_currency`, `destination_amount`, `destination_balance_before`, `destination_balance_after`, `source_country`, `destination_country`, `is_refunded`, `account_id`, `beneficiary_id`, `beneficiary`, `user`, `reason` |
+///
+/// See [`ExportSchema::columns`] for what each column holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportSchema {
+    #[default]
+    V1,
+}
+
+impl ExportSchema {
+    /// The latest version.
+    pub const LATEST: Self = Self::V1;
+
+    /// The version number.
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::V1 => 1,
+        }
+    }
+
+    /// The columns in the order they are written.
+    pub fn columns(&self) -> &'static [ExportColumn] {
+        match self {
+            Self::V1 => V1,
+        }
+    }
+
+    /// The values of a transaction, aligned with [`ExportSchema::columns`].
+    pub fn values(&self, transaction: &Transaction) -> Vec<Option<String>> {
+        match self {
+            Self::V1 => {
+                let source = &transaction.meta.source;
+                let destination = &transaction.meta.destination;
+
+                vec![
+                    Some(transaction.transaction_id.clone()),
+                    transaction.transaction_ref.clone(),
+                    Some(as_string(&transaction.transaction_type)),
+                    Some(transaction.status.as_str().to_string()),
+                    Some(transaction.created_at.clone()),
+                    Some(transaction.updated_at.clone()),
+                    Some(as_string(&transaction.currency)),
+                    Some(transaction.amount.clone()),
+                    transaction.fees.clone(),
+                    Some(transaction.balance_before.clone()),
+                    Some(transaction.balance_after.clone()),
+                    Some(as_string(&source.currency)),
+                    Some(source.amount.to_string()),
+                    Some(source.balance.before.clone()),
+                    Some(source.balance.after.clone()),
+                    Some(as_string(&destination.currency)),
+                    Some(destination.amount.to_string()),
+                    Some(destination.balance.before.clone()),
+                    Some(destination.balance.after.clone()),
+                    transaction.source_country.clone(),
+                    transaction.destination_country.clone(),
+                    Some(transaction.is_refunded.to_string()),
+                    Some(transaction.account_id.to_string()),
+                    transaction.beneficiary_id.map(|beneficiary_id| beneficiary_id.to_string()),
+                    transaction.beneficiary.clone(),
+                    transaction.user.clone(),
+                    transaction.reason.clone(),
+                ]
+            },
+        }
+    }
+}
+
+/// The name an enum is sent as by the API, e.g. `UGX` or `payout`.
+fn as_string<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(value)) => value,
+        _ => String::new(),
+    }
+}
+
+/// Masks every run of 6 or more digits except its last 4, e.g. `+256789123456` becomes
+/// `+********3456`. Shorter numbers, such as IDs, are left alone.
+pub fn mask_digits(value: &str) -> String {
+    let chars = value.chars().collect::<Vec<_>>();
+    let mut masked = String::with_capacity(value.len());
+    let mut start = 0;
+
+    while start < chars.len() {
+        let end = chars[start..]
+            .iter()
+            .position(|c| c.is_ascii_digit() != chars[start].is_ascii_digit())
+            .map_or(chars.len(), |len| start + len);
+
+        if chars[start].is_ascii_digit() && end - start >= 6 {
+            masked.extend(std::iter::repeat_n('*', end - start - 4));
+            masked.extend(&chars[end - 4..end]);
+        } else {
+            masked.extend(&chars[start..end]);
+        }
+
+        start = end;
+    }
+
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_masks_long_digit_runs() {
+        assert_eq!(mask_digits("+256789123456"), "+********3456");
+        assert_eq!(mask_digits("Acc 0012033048, ref 123"), "Acc ******3048, ref 123");
+        assert_eq!(mask_digits("12345"), "12345");
+        assert_eq!(mask_digits(""), "");
+    }
+
+    #[test]
+    fn it_keeps_values_aligned_with_columns() {
+        let transaction = serde_json::from_value::<Transaction>(serde_json::json!({
+            "id": 794,
+            "transactionId": "BP11678735362605",
+            "transactionRef": "INV001",
+            "type": "payout",
+            "currency": "UGX",
+            "amount": "1000",
+            "fees": "250",
+            "balanceBefore": "1250",
+            "balanceAfter": "0",
+            "destinationAmount": "1000",
+            "accountId": 3,
+            "meta": {
+                "source": {
+                    "amount": 1250,
+                    "balance": { "after": "0", "before": "1250" },
+                    "currency": "UGX"
+                },
+                "destination": {
+                    "amount": 1000,
+                    "balance": { "after": "1000", "before": "0" },
+                    "currency": "UGX"
+                }
+            },
+            "isRefunded": false,
+            "status": "successful",
+            "createdAt": "2023-03-13T19:22:46.070Z",
+            "updatedAt": "2023-03-13T19:22:46.071Z"
+        })).unwrap();
+
+        let schema = ExportSchema::LATEST;
+        let values = schema.values(&transaction);
+
+        assert_eq!(values.len(), schema.columns().len());
+
+        let value = |name: &str| {
+            let index = schema.columns().iter().position(|column| column.name == name).unwrap();
+            values[index].clone()
+        };
+
+        assert_eq!(value("type").as_deref(), Some("payout"));
+        assert_eq!(value("currency").as_deref(), Some("UGX"));
+        assert_eq!(value("source_balance_before").as_deref(), Some("1250"));
+        assert_eq!(value("destination_amount").as_deref(), Some("1000"));
+        assert_eq!(value("is_refunded").as_deref(), Some("false"));
+        assert_eq!(value("beneficiary"), None);
+    }
+}
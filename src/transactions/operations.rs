@@ -1,5 +1,7 @@
 mod get_transaction;
 mod get_transactions;
+mod wait_for_final_status;
 
 pub use get_transaction::*;
 pub use get_transactions::*;
+pub use wait_for_final_status::*;
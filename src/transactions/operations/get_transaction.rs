@@ -5,6 +5,7 @@ use thiserror::Error;
 use crate::{transactions::{Transaction, Transactions}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
 #[derive(Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetTransactionParams {
     /// transactionId from Get Transactions
     #[serde(rename = "transactionId")]
@@ -25,8 +26,13 @@ impl From<GetTransactionError> for EversendError<GetTransactionError> {
 }
 
 #[derive(Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetTransactionResponse {
     pub transactions: Vec<Transaction>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// [Eversend Docs: Get Transaction](https://eversend.readme.io/reference/get-transaction)
@@ -83,12 +89,12 @@ impl<'a> GetTransaction for Transactions<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<GetTransactionResponse>>()
+            .decode::<ApiResponseBody<GetTransactionResponse>, _>()
             .await?;
 
         let transaction_result = result.data.transactions.first();
 
-        if let None = transaction_result {
+        if transaction_result.is_none() {
             return Err(EversendError::Operation(GetTransactionError::NotFound));
         }
 
@@ -98,7 +104,10 @@ impl<'a> GetTransaction for Transactions<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret};
+    use crate::{
+        transactions::{TransactionCurrencyOption, TransactionStatusOption, TransactionTypeOption},
+        ClientId, eversend::Eversend, ApiToken, ClientSecret,
+    };
 
     use super::*;
     use mockito::{self, mock};
@@ -194,4 +203,105 @@ mod tests {
         mock.assert();
 
     }
+
+    #[tokio::test]
+    async fn it_keeps_values_unknown_to_the_sdk() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+        let transaction_id = String::from("BE11640235387620");
+
+        let mock = mock("GET", format!("/transactions/{}", transaction_id).as_str())
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                        "transactions": [
+                            {
+                                "id": 792,
+                                "transactionId": "BE31661876379861",
+                                "transactionRef": null,
+                                "type": "reversal",
+                                "currency": "ZMW",
+                                "amount": "100",
+                                "fees": null,
+                                "balanceBefore": "398.78",
+                                "balanceAfter": "398.78",
+                                "remitOneId": null,
+                                "sourceCurrency": null,
+                                "destinationCurrency": "KES",
+                                "destinationAmount": "3.1007201981367",
+                                "sourceCountry": null,
+                                "destinationCountry": null,
+                                "pesapotId": null,
+                                "pesapotResponse": null,
+                                "merchantId": null,
+                                "accountId": 3,
+                                "userId": null,
+                                "beneficiaryId": null,
+                                "customer": null,
+                                "meta": {
+                                    "source": {
+                                        "amount": 100,
+                                        "balance": {
+                                            "after": "398.78",
+                                            "before": "398.78"
+                                        },
+                                        "currency": "UGX"
+                                    },
+                                    "destination": {
+                                        "amount": 3.1,
+                                        "balance": {
+                                            "after": "1783.82",
+                                            "before": "1783.82"
+                                        },
+                                        "currency": "KES"
+                                    }
+                                },
+                                "reason": null,
+                                "isRefunded": false,
+                                "status": "reversed",
+                                "channel": "api",
+                                "createdAt": "2022-08-30T16:19:39.864Z",
+                                "updatedAt": "2022-08-30T16:19:39.864Z",
+                                "user": null,
+                                "beneficiary": null
+                            }
+                        ]
+                    },
+                    "success": true
+                  }).to_string(),
+            )
+            .create();
+
+        let transaction = eversend
+            .transactions()
+            .get_transaction(
+                &GetTransactionParams {
+                    transaction_id,
+                }
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.transaction_type, TransactionTypeOption::Unknown(String::from("reversal")));
+        assert_eq!(transaction.currency, TransactionCurrencyOption::Unknown(String::from("ZMW")));
+        assert_eq!(transaction.status, TransactionStatusOption::Unknown(String::from("reversed")));
+        assert!(!transaction.status.is_final());
+        assert_eq!(transaction.meta.source.currency, TransactionCurrencyOption::UGX);
+        assert_eq!(transaction.extra["channel"], "api");
+        assert_eq!(transaction.extra["customer"], serde_json::Value::Null);
+
+        let serialized = serde_json::to_value(&transaction).unwrap();
+        assert_eq!(serialized["status"], "reversed");
+        assert_eq!(serialized["channel"], "api");
+
+        mock.assert();
+
+    }
 }
@@ -1,43 +1,182 @@
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::Serialize;
 use thiserror::Error;
 
-use crate::{transactions::{Transaction, TransactionCurrencyOption, TransactionRangeOption, TransactionStatusOption, TransactionTypeOption, Transactions}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
+use crate::{transactions::{TransactionCurrencyOption, TransactionRangeOption, TransactionStatusOption, TransactionPage, TransactionTypeOption, Transactions}, ApiResponseBody, EversendError, EversendResult, ResponseExtension};
 
-#[derive(Serialize)]
+/// Filters for [`GetTransactions`].
+///
+/// Every filter is optional and left out of the request when unset, in which case the API applies
+/// its own default. Use [`GetTransactionsParams::builder`] to construct a validated set of filters.
+#[derive(Serialize, Default, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GetTransactionsParams {
-    /// Defaults to UGX
-    pub currency: TransactionCurrencyOption,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<TransactionCurrencyOption>,
 
     /// format YYYY-MM-dd
-    pub from: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
 
     /// Start from 1. Default value is 10
-    pub limit: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
 
     /// Start from 1. Default value is 1
-    pub page: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
 
-    pub range: TransactionRangeOption,
+    /// A relative period. Cannot be combined with `from` or `to`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<TransactionRangeOption>,
 
     /// The transaction reference.
-    pub search: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
 
-    /// Defaults to pending
-    #[serde(rename = "status")]
-    pub transaction_status: TransactionStatusOption,
+    #[serde(rename = "status", skip_serializing_if = "Option::is_none")]
+    pub transaction_status: Option<TransactionStatusOption>,
 
     /// format YYYY-MM-dd
-    pub to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
 
-    /// Defaults to payout
-    #[serde(rename = "type")]
-    pub transaction_type: TransactionTypeOption,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<TransactionTypeOption>,
+}
+
+impl GetTransactionsParams {
+    /// Returns a [`GetTransactionsParamsBuilder`] with no filters set.
+    pub fn builder() -> GetTransactionsParamsBuilder {
+        GetTransactionsParamsBuilder::default()
+    }
+
+    /// Checks that the date filters are well formed and do not contradict each other.
+    pub fn validate(&self) -> Result<(), GetTransactionsError> {
+        if self.range.is_some() && (self.from.is_some() || self.to.is_some()) {
+            return Err(GetTransactionsError::ConflictingDateFilters);
+        }
+
+        for date in [&self.from, &self.to].into_iter().flatten() {
+            if !is_valid_date(date) {
+                return Err(GetTransactionsError::InvalidDate(date.clone()));
+            }
+        }
+
+        if let (Some(from), Some(to)) = (&self.from, &self.to) {
+            // Dates are zero padded, so comparing them as strings orders them chronologically.
+            if from > to {
+                return Err(GetTransactionsError::InvalidDateRange {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_valid_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+
+    // Zero padding is checked separately, as chrono also accepts single-digit months and days.
+    bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        })
+        && NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+}
+
+/// A builder for [`GetTransactionsParams`].
+#[derive(Default)]
+pub struct GetTransactionsParamsBuilder {
+    params: GetTransactionsParams,
+}
+
+impl GetTransactionsParamsBuilder {
+    /// Only return transactions in the given currency.
+    pub fn set_currency(mut self, currency: TransactionCurrencyOption) -> Self {
+        self.params.currency = Some(currency);
+        self
+    }
+
+    /// Only return transactions created on or after the given date (format YYYY-MM-dd).
+    pub fn set_from(mut self, from: &str) -> Self {
+        self.params.from = Some(from.to_string());
+        self
+    }
+
+    /// Only return transactions created on or before the given date (format YYYY-MM-dd).
+    pub fn set_to(mut self, to: &str) -> Self {
+        self.params.to = Some(to.to_string());
+        self
+    }
+
+    /// Only return transactions within the given relative period.
+    pub fn set_range(mut self, range: TransactionRangeOption) -> Self {
+        self.params.range = Some(range);
+        self
+    }
+
+    /// Search by transaction reference.
+    pub fn set_search(mut self, search: &str) -> Self {
+        self.params.search = Some(search.to_string());
+        self
+    }
+
+    /// Only return transactions with the given status.
+    pub fn set_status(mut self, status: TransactionStatusOption) -> Self {
+        self.params.transaction_status = Some(status);
+        self
+    }
+
+    /// Only return transactions of the given type.
+    pub fn set_type(mut self, transaction_type: TransactionTypeOption) -> Self {
+        self.params.transaction_type = Some(transaction_type);
+        self
+    }
+
+    /// Sets the page size.
+    pub fn set_limit(mut self, limit: u32) -> Self {
+        self.params.limit = Some(limit);
+        self
+    }
+
+    /// Sets the page to fetch, starting from 1.
+    pub fn set_page(mut self, page: u32) -> Self {
+        self.params.page = Some(page);
+        self
+    }
+
+    /// Validates and returns the constructed [`GetTransactionsParams`].
+    pub fn build(self) -> Result<GetTransactionsParams, GetTransactionsError> {
+        self.params.validate()?;
+
+        Ok(self.params)
+    }
 }
 
 /// An error returned from [`GetTransactions`].
 #[derive(Debug, Error)]
-pub enum GetTransactionsError {}
+pub enum GetTransactionsError {
+    /// A relative `range` was combined with an explicit `from` or `to` date.
+    #[error("range cannot be combined with from/to dates")]
+    ConflictingDateFilters,
+
+    /// A date filter is not in the YYYY-MM-dd format.
+    #[error("invalid date: {0}, expected YYYY-MM-dd")]
+    InvalidDate(String),
+
+    /// The `from` date is after the `to` date.
+    #[error("from date {from} is after to date {to}")]
+    InvalidDateRange {
+        from: String,
+        to: String,
+    },
+}
 
 impl From<GetTransactionsError> for EversendError<GetTransactionsError> {
     fn from(err: GetTransactionsError) -> Self {
@@ -45,21 +184,10 @@ impl From<GetTransactionsError> for EversendError<GetTransactionsError> {
     }
 }
 
-#[derive(Deserialize)]
-pub struct GetTransactionsResponse {
-    pub total_payouts: String,
-    pub total_collections: String,
-    pub balance: u32,
-    pub transactions: Vec<Transaction>,
-    pub total: u32,
-    pub limit: u32,
-    pub page: u32,
-}
-
 /// [Eversend Docs: Get Transactions](https://eversend.readme.io/reference/get-transactions)
 #[async_trait]
 pub trait GetTransactions {
-    /// Get Transactions.
+    /// Get a [`TransactionPage`].
     ///
     /// [Eversend Docs: Get Transactions](https://eversend.readme.io/reference/get-transactions)
     ///
@@ -75,20 +203,18 @@ pub trait GetTransactions {
     ///         &ClientSecret::from("sk_example_123456780")
     ///     );
     ///
-    ///     let transactions = eversend
+    ///     let page = eversend
     ///         .transactions()
     ///         .get_transactions(
-    ///             &GetTransactionsParams {
-    ///                 currency: TransactionCurrencyOption::UGX,
-    ///                 from: String::from("2024-01-01"),
-    ///                 to: String::from("2024-01-01"),
-    ///                 limit: 10,
-    ///                 page: 1,
-    ///                 range: TransactionRangeOption::MONTH,
-    ///                 search: String::from("BE11640235387619"),
-    ///                 transaction_status: TransactionStatusOption::PENDING,
-    ///                 transaction_type: TransactionTypeOption::PAYOUT,
-    ///             }
+    ///             &GetTransactionsParams::builder()
+    ///                 .set_currency(TransactionCurrencyOption::UGX)
+    ///                 .set_from("2024-01-01")
+    ///                 .set_to("2024-01-31")
+    ///                 .set_status(TransactionStatusOption::PENDING)
+    ///                 .set_type(TransactionTypeOption::PAYOUT)
+    ///                 .set_limit(10)
+    ///                 .set_page(1)
+    ///                 .build()?
     ///         )
     ///         .await?;
     ///
@@ -99,7 +225,7 @@ pub trait GetTransactions {
     async fn get_transactions(
         &self,
         params: &GetTransactionsParams
-    ) -> EversendResult<Vec<Transaction>, GetTransactionsError>;
+    ) -> EversendResult<TransactionPage, GetTransactionsError>;
 }
 
 #[async_trait]
@@ -107,7 +233,9 @@ impl<'a> GetTransactions for Transactions<'a> {
     async fn get_transactions(
         &self,
         params: &GetTransactionsParams
-    ) -> EversendResult<Vec<Transaction>, GetTransactionsError> {
+    ) -> EversendResult<TransactionPage, GetTransactionsError> {
+        params.validate()?;
+
         let url = format!("{}/transactions", self.eversend.base_url());
 
         let result = self
@@ -119,10 +247,10 @@ impl<'a> GetTransactions for Transactions<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<GetTransactionsResponse>>()
+            .decode::<ApiResponseBody<TransactionPage>, _>()
             .await?;
 
-        Ok(result.data.transactions)
+        Ok(result.data)
     }
 }
 
@@ -146,6 +274,11 @@ mod tests {
             .build();
 
         let mock = mock("POST", "/transactions")
+            .match_body(mockito::Matcher::Json(json!({
+                "currency": "UGX",
+                "range": "month",
+                "search": "BE11640235387619"
+            })))
             .with_status(200)
             .with_body(
                 json!({
@@ -214,27 +347,176 @@ mod tests {
             )
             .create();
 
-        let transactions = eversend
+        let page = eversend
             .transactions()
             .get_transactions(
-                &GetTransactionsParams {
-                    currency: TransactionCurrencyOption::UGX,
-                    from: String::from("2024-01-01"),
-                    to: String::from("2024-01-01"),
-                    limit: 10,
-                    page: 1,
-                    range: TransactionRangeOption::MONTH,
-                    search: String::from("BE11640235387619"),
-                    transaction_status: TransactionStatusOption::PENDING,
-                    transaction_type: TransactionTypeOption::PAYOUT,
-                }
+                &GetTransactionsParams::builder()
+                    .set_currency(TransactionCurrencyOption::UGX)
+                    .set_range(TransactionRangeOption::MONTH)
+                    .set_search("BE11640235387619")
+                    .build()
+                    .unwrap()
             )
             .await
             .unwrap();
 
-        assert_eq!(transactions[0].id, 792);
+        assert_eq!(page.transactions[0].id, 792);
+        assert_eq!(page.total_payouts, "20000");
+        assert_eq!(page.total_collections, "19000");
+        assert_eq!(page.total, 1);
+        assert_eq!(page.limit, 10);
+        assert_eq!(page.page, 1);
 
         mock.assert();
 
     }
+
+    #[tokio::test]
+    async fn it_reports_where_the_response_did_not_match() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("POST", "/transactions")
+            .match_body(mockito::Matcher::Json(json!({
+                "search": "BE31661876379861"
+            })))
+            .with_status(200)
+            .with_body(
+                json!({
+                    "code": 200,
+                    "data": {
+                    "total_payouts": "20000",
+                    "total_collections": "19000",
+                    "balance": 0,
+                    "transactions": [
+                        {
+                            "id": 792,
+                            "transactionId": "BE31661876379861",
+                            "transactionRef": null,
+                            "type": "exchange",
+                            "currency": "UGX",
+                            "amount": "100",
+                            "fees": null,
+                            "balanceBefore": "398.78",
+                            "balanceAfter": "398.78",
+                            "remitOneId": null,
+                            "sourceCurrency": null,
+                            "destinationCurrency": "KES",
+                            "destinationAmount": 3.1007201981367,
+                            "sourceCountry": null,
+                            "destinationCountry": null,
+                            "pesapotId": null,
+                            "pesapotResponse": null,
+                            "merchantId": null,
+                            "accountId": 3,
+                            "userId": null,
+                            "beneficiaryId": null,
+                            "customer": null,
+                            "meta": {
+                                "source": {
+                                    "amount": 100,
+                                    "balance": {
+                                        "after": "398.78",
+                                        "before": "398.78"
+                                    },
+                                    "currency": "UGX"
+                                },
+                                "destination": {
+                                    "amount": 3.1,
+                                    "balance": {
+                                        "after": "1783.82",
+                                        "before": "1783.82"
+                                    },
+                                    "currency": "KES"
+                                }
+                            },
+                            "reason": null,
+                            "isRefunded": false,
+                            "status": "successful",
+                            "createdAt": "2022-08-30T16:19:39.864Z",
+                            "updatedAt": "2022-08-30T16:19:39.864Z",
+                            "user": null,
+                            "beneficiary": null
+                        }
+                    ],
+                    "total": 1,
+                    "limit": 10,
+                    "page": 1
+                    },
+                    "success": true
+                }).to_string(),
+            )
+            .create();
+
+        let result = eversend
+            .transactions()
+            .get_transactions(&GetTransactionsParams::builder().set_search("BE31661876379861").build().unwrap())
+            .await;
+
+        let Err(EversendError::Decode(err)) = result else {
+            panic!("expected a decode error, got {:?}", result.map(|page| page.total));
+        };
+
+        assert_eq!(err.path, "data.transactions[0].destinationAmount");
+        assert_eq!(err.expected.as_deref(), Some("a string"));
+        assert!(err.body.contains(r#""destinationAmount":3.1007201981367"#));
+        assert!(err.body.contains(r#""merchantId":null"#));
+
+        mock.assert();
+    }
+
+    #[test]
+    fn it_skips_unset_filters() {
+        let params = GetTransactionsParams::builder()
+            .set_status(TransactionStatusOption::FAILED)
+            .build()
+            .unwrap();
+
+        assert_eq!(serde_json::to_value(&params).unwrap(), json!({ "status": "failed" }));
+    }
+
+    #[test]
+    fn it_rejects_a_range_combined_with_dates() {
+        let result = GetTransactionsParams::builder()
+            .set_range(TransactionRangeOption::WEEK)
+            .set_from("2024-01-01")
+            .build();
+
+        assert!(matches!(result, Err(GetTransactionsError::ConflictingDateFilters)));
+    }
+
+    #[test]
+    fn it_rejects_malformed_and_inverted_dates() {
+        let result = GetTransactionsParams::builder()
+            .set_from("01/01/2024")
+            .build();
+
+        assert!(matches!(result, Err(GetTransactionsError::InvalidDate(_))));
+
+        for date in ["2024-13-45", "2023-02-29", "2024-04-31"] {
+            let result = GetTransactionsParams::builder()
+                .set_to(date)
+                .build();
+
+            assert!(matches!(result, Err(GetTransactionsError::InvalidDate(_))), "{date}");
+        }
+
+        let result = GetTransactionsParams::builder()
+            .set_from("2024-02-29")
+            .build();
+
+        assert!(result.is_ok());
+
+        let result = GetTransactionsParams::builder()
+            .set_from("2024-02-01")
+            .set_to("2024-01-01")
+            .build();
+
+        assert!(matches!(result, Err(GetTransactionsError::InvalidDateRange { .. })));
+    }
 }
@@ -0,0 +1,344 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+use crate::{transactions::{GetTransaction, GetTransactionError, GetTransactionParams, Transaction, Transactions}, EversendError, EversendResult};
+
+/// Polling behaviour for [`WaitForFinalStatus`].
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    /// Delay before the second poll. Defaults to 1 second.
+    pub initial_interval: Duration,
+
+    /// Upper bound for the delay between polls. Defaults to 30 seconds.
+    pub max_interval: Duration,
+
+    /// Factor the delay grows by after every poll, at least 1. Defaults to 2.
+    pub multiplier: u32,
+
+    /// How long to keep polling before giving up. Defaults to 5 minutes.
+    pub timeout: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(30),
+            multiplier: 2,
+            timeout: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// An error returned from [`WaitForFinalStatus`].
+#[derive(Debug, Error)]
+pub enum WaitForFinalStatusError {
+    /// The transaction did not reach a final status before the deadline.
+    ///
+    /// Carries the last state that was seen, if the transaction was found at all.
+    #[error("transaction did not reach a final status in time")]
+    Timeout {
+        last_seen: Option<Box<Transaction>>,
+    },
+}
+
+impl From<WaitForFinalStatusError> for EversendError<WaitForFinalStatusError> {
+    fn from(err: WaitForFinalStatusError) -> Self {
+        Self::Operation(err)
+    }
+}
+
+/// Polls [`GetTransaction`] until a transaction is either successful or failed.
+#[async_trait]
+pub trait WaitForFinalStatus {
+    /// Wait for a [`Transaction`] to reach a final status.
+    ///
+    /// The transaction is fetched straight away and then with an exponentially growing delay until
+    /// its status is final or `options.timeout` elapses. A transaction that cannot be found yet is
+    /// treated like a pending one, and server errors or failed connections are retried until the
+    /// deadline. Other errors, such as 4xx responses, are returned straight away.
+    ///
+    /// # Examples
+    /// ```
+    /// # use eversend_rust_sdk::EversendResult;
+    /// # use eversend_rust_sdk::transactions::*;
+    /// use eversend_rust_sdk::{ClientId,ClientSecret,Eversend};
+    ///
+    /// # async fn run() -> EversendResult<(), WaitForFinalStatusError> {
+    ///     let eversend = Eversend::new(
+    ///         &ClientId::from("sk_example_123456789"),
+    ///         &ClientSecret::from("sk_example_123456780")
+    ///     );
+    ///
+    ///     let transaction = eversend
+    ///         .transactions()
+    ///         .wait_for_final_status("BE11640235387619", &WaitOptions::default())
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
+    ///
+    async fn wait_for_final_status(
+        &self,
+        transaction_id: &str,
+        options: &WaitOptions
+    ) -> EversendResult<Transaction, WaitForFinalStatusError>;
+}
+
+#[async_trait]
+impl<'a> WaitForFinalStatus for Transactions<'a> {
+    async fn wait_for_final_status(
+        &self,
+        transaction_id: &str,
+        options: &WaitOptions
+    ) -> EversendResult<Transaction, WaitForFinalStatusError> {
+        // A timeout too large to represent is treated as no deadline at all.
+        let deadline = Instant::now().checked_add(options.timeout);
+        let multiplier = options.multiplier.max(1);
+        let params = GetTransactionParams {
+            transaction_id: transaction_id.to_string(),
+        };
+
+        let mut interval = options.initial_interval;
+        let mut last_seen = None;
+
+        loop {
+            match self.get_transaction(&params).await {
+                Ok(transaction) if transaction.status.is_final() => return Ok(transaction),
+                Ok(transaction) => last_seen = Some(Box::new(transaction)),
+                Err(EversendError::Operation(GetTransactionError::NotFound)) => {},
+                Err(EversendError::RequestError(err)) if is_transient(&err) => {},
+                Err(EversendError::ApiTokenMissing) => return Err(EversendError::ApiTokenMissing),
+                Err(EversendError::Unauthorized) => return Err(EversendError::Unauthorized),
+                Err(EversendError::RequestError(err)) => return Err(EversendError::RequestError(err)),
+                Err(EversendError::Decode(err)) => return Err(EversendError::Decode(err)),
+            }
+
+            let delay = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+
+                    if now >= deadline {
+                        return Err(EversendError::Operation(WaitForFinalStatusError::Timeout { last_seen }));
+                    }
+
+                    interval.min(deadline - now)
+                },
+                None => interval,
+            };
+
+            tokio::time::sleep(delay).await;
+
+            interval = interval.saturating_mul(multiplier).min(options.max_interval);
+        }
+    }
+}
+
+/// Returns `true` for server errors and failures to get a response at all, which are worth
+/// retrying, as opposed to 4xx responses.
+fn is_transient(err: &reqwest::Error) -> bool {
+    match err.status() {
+        Some(status) => status.is_server_error(),
+        None => !err.is_builder(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClientId, eversend::Eversend, ApiToken, ClientSecret, transactions::TransactionStatusOption};
+
+    use super::*;
+    use mockito::{self, mock};
+    use serde_json::json;
+    use tokio;
+
+    fn transaction_body(transaction_id: &str, status: &str) -> String {
+        json!({
+            "code": 200,
+            "data": {
+                "transactions": [
+                    {
+                        "id": 792,
+                        "transactionId": transaction_id,
+                        "transactionRef": null,
+                        "type": "payout",
+                        "currency": "UGX",
+                        "amount": "100",
+                        "fees": null,
+                        "balanceBefore": "398.78",
+                        "balanceAfter": "298.78",
+                        "remitOneId": null,
+                        "sourceCurrency": null,
+                        "destinationCurrency": "UGX",
+                        "destinationAmount": "100",
+                        "sourceCountry": null,
+                        "destinationCountry": null,
+                        "pesapotId": null,
+                        "pesapotResponse": null,
+                        "merchantId": null,
+                        "accountId": 3,
+                        "userId": null,
+                        "beneficiaryId": null,
+                        "customer": null,
+                        "meta": {
+                            "source": {
+                                "amount": 100,
+                                "balance": {
+                                    "after": "298.78",
+                                    "before": "398.78"
+                                },
+                                "currency": "UGX"
+                            },
+                            "destination": {
+                                "amount": 100,
+                                "balance": {
+                                    "after": "100",
+                                    "before": "0"
+                                },
+                                "currency": "UGX"
+                            }
+                        },
+                        "reason": null,
+                        "isRefunded": false,
+                        "status": status,
+                        "createdAt": "2022-08-30T16:19:39.864Z",
+                        "updatedAt": "2022-08-30T16:19:39.864Z",
+                        "user": null,
+                        "beneficiary": null
+                    }
+                ]
+            },
+            "success": true
+        }).to_string()
+    }
+
+    fn options() -> WaitOptions {
+        WaitOptions {
+            initial_interval: Duration::from_millis(5),
+            max_interval: Duration::from_millis(20),
+            multiplier: 2,
+            timeout: Duration::from_millis(200),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_polls_until_the_transaction_is_final() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let pending = mock("GET", "/transactions/BE11111111111111")
+            .with_status(200)
+            .with_body(transaction_body("BE11111111111111", "pending"))
+            .expect(2)
+            .create();
+
+        let successful = mock("GET", "/transactions/BE11111111111111")
+            .with_status(200)
+            .with_body(transaction_body("BE11111111111111", "successful"))
+            .create();
+
+        let transaction = eversend
+            .transactions()
+            .wait_for_final_status("BE11111111111111", &options())
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.status, TransactionStatusOption::SUCCESSFUL);
+
+        pending.assert();
+        successful.assert();
+    }
+
+    #[tokio::test]
+    async fn it_times_out_with_the_last_seen_state() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let _mock = mock("GET", "/transactions/BE22222222222222")
+            .with_status(200)
+            .with_body(transaction_body("BE22222222222222", "pending"))
+            .create();
+
+        let result = eversend
+            .transactions()
+            .wait_for_final_status("BE22222222222222", &options())
+            .await;
+
+        match result {
+            Err(EversendError::Operation(WaitForFinalStatusError::Timeout { last_seen })) => {
+                assert_eq!(last_seen.unwrap().status, TransactionStatusOption::PENDING);
+            },
+            _ => panic!("expected a timeout"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_retries_server_errors_until_the_transaction_is_final() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let unavailable = mock("GET", "/transactions/BE33333333333333")
+            .with_status(503)
+            .expect(2)
+            .create();
+
+        let successful = mock("GET", "/transactions/BE33333333333333")
+            .with_status(200)
+            .with_body(transaction_body("BE33333333333333", "successful"))
+            .create();
+
+        let transaction = eversend
+            .transactions()
+            .wait_for_final_status("BE33333333333333", &WaitOptions { multiplier: 0, ..options() })
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.status, TransactionStatusOption::SUCCESSFUL);
+
+        unavailable.assert();
+        successful.assert();
+    }
+
+    #[tokio::test]
+    async fn it_returns_client_errors_straight_away() {
+        let eversend = Eversend::builder(
+            &ClientId::from("sk_example_123456789"),
+            &ClientSecret::from("sk_example_123456780")
+        )
+            .set_base_url(&mockito::server_url())
+            .set_api_token(&ApiToken::from("some_test_token"))
+            .build();
+
+        let mock = mock("GET", "/transactions/BE44444444444444")
+            .with_status(400)
+            .expect(1)
+            .create();
+
+        let result = eversend
+            .transactions()
+            .wait_for_final_status("BE44444444444444", &WaitOptions { timeout: Duration::MAX, ..options() })
+            .await;
+
+        assert!(matches!(result, Err(EversendError::RequestError(err)) if err.status().unwrap().as_u16() == 400));
+
+        mock.assert();
+    }
+}
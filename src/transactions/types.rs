@@ -1,3 +1,5 @@
 mod transaction;
+mod transaction_page;
 
 pub use transaction::*;
+pub use transaction_page::*;
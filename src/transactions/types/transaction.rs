@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Clone)]
+use crate::core::open_enum_schema;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Transaction {
 
     #[serde(rename = "accountId")]
@@ -70,6 +73,9 @@ pub struct Transaction {
     #[serde(rename = "transactionId")]
     pub transaction_id: String,
 
+    #[serde(rename = "transactionRef")]
+    pub transaction_ref: Option<String>,
+
     #[serde(rename = "type")]
     pub transaction_type: TransactionTypeOption,
 
@@ -80,9 +86,13 @@ pub struct Transaction {
 
     #[serde(rename = "userId")]
     pub user_id: Option<u32>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum TransactionCurrencyOption {
     GHS,
     KES,
@@ -91,9 +101,15 @@ pub enum TransactionCurrencyOption {
     TZS,
     UGX,
     USD,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+open_enum_schema!(TransactionCurrencyOption, ["GHS", "KES", "NGN", "RWF", "TZS", "UGX", "USD"]);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum TransactionTypeOption {
     #[serde(rename = "collection")]
     COLLECTION,
@@ -103,9 +119,15 @@ pub enum TransactionTypeOption {
 
     #[serde(rename = "payout")]
     PAYOUT,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+open_enum_schema!(TransactionTypeOption, ["collection", "exchange", "payout"]);
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum TransactionStatusOption {
     #[serde(rename = "failed")]
     FAILED,
@@ -115,9 +137,35 @@ pub enum TransactionStatusOption {
 
     #[serde(rename = "successful")]
     SUCCESSFUL,
+
+    /// A value not known to this version of the SDK, as sent by the API.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+open_enum_schema!(TransactionStatusOption, ["failed", "pending", "successful"]);
+
+impl TransactionStatusOption {
+    /// Returns `true` if the status will not change anymore.
+    ///
+    /// [`Unknown`](Self::Unknown) statuses are not considered final.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::FAILED | Self::SUCCESSFUL)
+    }
+
+    /// The status as sent by the API, e.g. `pending`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::FAILED => "failed",
+            Self::PENDING => "pending",
+            Self::SUCCESSFUL => "successful",
+            Self::Unknown(status) => status,
+        }
+    }
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum TransactionRangeOption {
     #[serde(rename = "day")]
     DAY,
@@ -132,22 +180,37 @@ pub enum TransactionRangeOption {
     YEAR
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransactionMetaData {
     pub source: TransationAccount,
     pub destination: TransationAccount,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TransationAccount {
     pub amount: f32,
     pub balance: AccountBalance,
     pub currency: TransactionCurrencyOption,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AccountBalance {
     pub after: String,
     pub before: String,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
@@ -0,0 +1,82 @@
+use serde::Deserialize;
+
+use super::Transaction;
+
+/// A page of [`Transaction`]s along with the period totals returned by Get Transactions.
+#[derive(Debug, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TransactionPage {
+    /// Current account balance.
+    pub balance: u32,
+
+    /// The page size used for this listing.
+    pub limit: u32,
+
+    /// The current page, starting from 1.
+    pub page: u32,
+
+    /// Total number of transactions matching the filters, across all pages.
+    pub total: u32,
+
+    /// Sum of all collections within the requested period.
+    pub total_collections: String,
+
+    /// Sum of all payouts within the requested period.
+    pub total_payouts: String,
+
+    /// The transactions on this page.
+    pub transactions: Vec<Transaction>,
+
+    /// Fields not known to this version of the SDK.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl TransactionPage {
+    /// Returns the total number of pages available for the current page size.
+    pub fn total_pages(&self) -> u32 {
+        if self.limit == 0 {
+            return 0;
+        }
+
+        self.total.div_ceil(self.limit)
+    }
+
+    /// Returns `true` if there are more pages after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(total: u32, limit: u32, page: u32) -> TransactionPage {
+        TransactionPage {
+            balance: 0,
+            limit,
+            page,
+            total,
+            total_collections: String::from("0"),
+            total_payouts: String::from("0"),
+            transactions: vec![],
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn it_computes_the_total_number_of_pages() {
+        assert_eq!(page(0, 10, 1).total_pages(), 0);
+        assert_eq!(page(10, 10, 1).total_pages(), 1);
+        assert_eq!(page(11, 10, 1).total_pages(), 2);
+        assert_eq!(page(11, 0, 1).total_pages(), 0);
+    }
+
+    #[test]
+    fn it_knows_when_there_is_a_next_page() {
+        assert!(page(11, 10, 1).has_next_page());
+        assert!(!page(11, 10, 2).has_next_page());
+        assert!(!page(0, 10, 1).has_next_page());
+    }
+}
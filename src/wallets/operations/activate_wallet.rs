@@ -5,6 +5,7 @@ use crate::{wallets::{Wallet, WalletId, Wallets}, ApiResponseBody, EversendError
 
 /// The parameters for [`ActivateWallet`].
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ActivateWalletParams<'a> {
     /// The ID of the wallet e.g. UGX, NGN, etc
     pub wallet: &'a WalletId
@@ -80,7 +81,7 @@ impl<'a> ActivateWallet for Wallets<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<WalletResponseData>>()
+            .decode::<ApiResponseBody<WalletResponseData>, _>()
             .await?;
 
         Ok(wallet.data.wallet)
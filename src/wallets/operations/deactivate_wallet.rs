@@ -6,6 +6,7 @@ use crate::{wallets::{Wallet, WalletId, Wallets}, ApiResponseBody, EversendError
 
 /// The parameters for [`DeactivateWallet`].
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeActivateWalletParams<'a> {
     /// The ID of the wallet e.g. UGX, NGN, etc
     pub wallet: &'a WalletId
@@ -81,7 +82,7 @@ impl<'a> DeactivateWallet for Wallets<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<WalletResponseData>>()
+            .decode::<ApiResponseBody<WalletResponseData>, _>()
             .await?;
 
         Ok(wallet.data.wallet)
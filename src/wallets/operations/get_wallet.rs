@@ -69,7 +69,7 @@ impl<'a> GetWallet for Wallets<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<WalletResponseData>>()
+            .decode::<ApiResponseBody<WalletResponseData>, _>()
             .await?;
 
         Ok(wallet.data.wallet)
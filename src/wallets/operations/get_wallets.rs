@@ -59,7 +59,7 @@ impl<'a> GetWallets for Wallets<'a> {
             .send()
             .await?
             .handle_unauthorized_or_generic_error()?
-            .json::<ApiResponseBody<Vec<Wallet>>>()
+            .decode::<ApiResponseBody<Vec<Wallet>>, _>()
             .await?;
 
         Ok(wallets)